@@ -0,0 +1,530 @@
+use crate::{
+    bench::BenchArgs,
+    capability::ColorDepth,
+    grid::{Boundary, Cell, Engine},
+    lifewiki::FetchArgs,
+    recording::ReplayArgs,
+    render::{Renderer, CELL_WIDTHS},
+    rule::Rule,
+    search::{SearchArgs, SubmitArgs},
+    seed::Symmetry,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+/// Conway's Game of Life in your terminal.
+#[derive(Debug, Default, PartialEq, Parser)]
+#[command(version)]
+pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Board width in cells [default: fit the terminal]
+    #[arg(long)]
+    pub width: Option<usize>,
+
+    /// Board height in cells [default: fit the terminal]
+    #[arg(long)]
+    pub height: Option<usize>,
+
+    /// Rule as a B/S rulestring, e.g. B36/S23, or a preset name, e.g. highlife [default: B3/S23]
+    #[arg(long)]
+    pub rule: Option<Rule>,
+
+    /// What happens at the board's edges [default: open]
+    #[arg(long, value_enum)]
+    pub boundary: Option<Boundary>,
+
+    /// How seeds placed by hand are mirrored across the middle of the board [default: none]
+    #[arg(long, value_enum)]
+    pub symmetry: Option<Symmetry>,
+
+    /// Ask again before placing a seed by hand over live cells
+    #[arg(long)]
+    pub confirm_overlap: bool,
+
+    /// How the next generation is computed [default: sparse]
+    #[arg(long, value_enum)]
+    pub engine: Option<Engine>,
+
+    /// Generations per second, or 0 for as fast as possible [default: 15]
+    #[arg(long)]
+    pub fps: Option<u32>,
+
+    /// Start paused (the default)
+    #[arg(long, conflicts_with = "autoplay")]
+    pub paused: bool,
+
+    /// Start playing right away
+    #[arg(long)]
+    pub autoplay: bool,
+
+    /// Load a TOML or JSON scene at startup
+    #[arg(long, value_name = "FILE")]
+    pub scene: Option<PathBuf>,
+
+    /// Place a pattern file (or a built-in seed, apgcode or fetched pattern) at startup
+    #[arg(long, value_name = "FILE")]
+    pub pattern: Option<String>,
+
+    /// Where to place the pattern [default: the cursor]
+    #[arg(
+        long,
+        value_name = "X,Y",
+        value_parser = parse_cell,
+        allow_hyphen_values = true,
+        requires = "pattern"
+    )]
+    pub at: Option<Cell>,
+
+    /// How many copies of the pattern to place
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "pattern")]
+    pub count: usize,
+
+    /// Offset between copies [default: pattern width + 2, 0]
+    #[arg(
+        long,
+        value_name = "DX,DY",
+        value_parser = parse_cell,
+        allow_hyphen_values = true,
+        requires = "pattern"
+    )]
+    pub spacing: Option<Cell>,
+
+    /// Color theme: emoji, classic, phosphor, solarized, high-contrast, deuteranopia, protanopia or one from config.toml [default: emoji]
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+
+    /// How many colors the terminal shows, the theme's colors turned to the nearest of them [default: by COLORTERM and TERM]
+    #[arg(long, value_enum, value_name = "COLORS")]
+    pub colors: Option<ColorDepth>,
+
+    /// How cells are drawn: as blocks two columns wide, half blocks two to a character, braille dots eight to one, or ascii characters [default: blocks, or half-block where the terminal draws emoji one column wide]
+    #[arg(long, value_enum)]
+    pub renderer: Option<Renderer>,
+
+    /// Draw cells as plain characters like # and ., one column wide, for terminals that garble the emoji; short for --renderer ascii
+    #[arg(long, conflicts_with = "renderer")]
+    pub ascii: bool,
+
+    /// How many columns each cell takes when drawn as blocks or ascii: 1 for twice as many across, 2 for about square ones [default: 2 for blocks, 1 for ascii]
+    #[arg(long, value_name = "N", value_parser = parse_cell_width)]
+    pub cell_width: Option<u16>,
+
+    /// Take clicks, drags and the wheel, even where the terminal doesn't seem to report them [default: all but the consoles]
+    #[arg(long, conflicts_with = "no_mouse")]
+    pub mouse: bool,
+
+    /// Leave the mouse to the terminal, to select text with it
+    #[arg(long)]
+    pub no_mouse: bool,
+
+    /// Add every pattern in Golly's collection, a directory or zip of it, to the seeds
+    #[arg(long, value_name = "PATH")]
+    pub golly: Option<PathBuf>,
+
+    /// Pick seeds from the pattern files in DIR, read again as they change
+    /// [default: ~/.config/game-of-life-cli/patterns]
+    #[arg(long, value_name = "DIR")]
+    pub patterns_dir: Option<PathBuf>,
+
+    /// Record every change to the board to FILE, to play back with the replay subcommand
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Log each generation's births, deaths and population to FILE as CSV
+    #[arg(long, value_name = "FILE")]
+    pub stats: Option<PathBuf>,
+
+    /// Remove gliders and *WSSes once they fly away from everything else
+    #[arg(long)]
+    pub remove_escaped: bool,
+
+    /// Seed for everything random, like soups and the rule explorer, to repeat a run [default: picked at random]
+    #[arg(long, value_name = "N")]
+    pub rng_seed: Option<u64>,
+
+    /// Crop exports to the live cells, plus this many cells around them
+    #[arg(long, value_name = "PADDING")]
+    pub crop: Option<usize>,
+
+    /// Read a plaintext board (O and .) from stdin and write each generation to stdout
+    #[arg(long)]
+    pub pipe: bool,
+
+    /// Stop after this many generations [default: run until stdout is closed]
+    #[arg(long, value_name = "N", requires = "pipe")]
+    pub generations: Option<usize>,
+
+    /// How frames are written
+    #[arg(long, value_enum, default_value_t, requires = "pipe")]
+    pub format: Format,
+
+    /// Line written after each text frame
+    #[arg(
+        long,
+        value_name = "TEXT",
+        default_value = "",
+        allow_hyphen_values = true,
+        requires = "pipe"
+    )]
+    pub separator: String,
+
+    /// Print the settings in effect, from the options, GOL_* variables and config.toml, and exit
+    #[arg(long)]
+    pub print_config: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    /// Time the engine on standard workloads and report generations per second
+    Bench(BenchArgs),
+    /// Download a pattern from LifeWiki and keep it with your seeds, to place by name
+    Fetch(FetchArgs),
+    /// Print the key each action is bound to, as config.toml's [keys] takes them
+    Keys,
+    /// Play back a recording made with --record, at the speed set with --fps
+    Replay(ReplayArgs),
+    /// Run random soups until they settle, counting what they leave and noting the rare ones
+    Search(SearchArgs),
+    /// Write the totals kept by search as a Catagolue haul
+    Submit(SubmitArgs),
+}
+
+/// How `--pipe` writes each generation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Rows of `O` and `.`, followed by the separator.
+    #[default]
+    Text,
+    /// One JSON object per line with the generation, population and live cells.
+    Json,
+}
+
+fn parse_cell(input: &str) -> Result<Cell, String> {
+    parse_coordinates(input).ok_or_else(|| format!("expected X,Y, got {:?}", input))
+}
+
+/// Parses how many columns a cell takes, as many as [`CELL_WIDTHS`] allows.
+fn parse_cell_width(input: &str) -> Result<u16, String> {
+    match input.parse() {
+        Ok(width) if CELL_WIDTHS.contains(&width) => Ok(width),
+        _ => Err(format!(
+            "expected {} to {}, got {:?}",
+            CELL_WIDTHS.start(),
+            CELL_WIDTHS.end(),
+            input
+        )),
+    }
+}
+
+/// Parses `x,y` (or `x y`) into a cell.
+pub fn parse_coordinates(input: &str) -> Option<Cell> {
+    let mut parts = input
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|part| !part.is_empty());
+
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+
+    match parts.next() {
+        Some(_) => None,
+        None => Some((x, y)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+        Args::try_parse_from(std::iter::once("cli-game-of-life").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_parse_defaults() {
+        assert_eq!(
+            parse(&[]).unwrap(),
+            Args {
+                count: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_board() {
+        let args = parse(&[
+            "--width",
+            "80",
+            "--height",
+            "40",
+            "--rule",
+            "B36/S23",
+            "--fps",
+            "30",
+            "--autoplay",
+        ])
+        .unwrap();
+
+        assert_eq!((args.width, args.height), (Some(80), Some(40)));
+        assert_eq!(args.rule, Some("B36/S23".parse().unwrap()));
+        assert_eq!(
+            parse(&["--rule", "seeds"]).unwrap().rule,
+            Some("B2/S".parse().unwrap())
+        );
+        assert_eq!(args.fps, Some(30));
+        assert!(args.autoplay);
+    }
+
+    #[test]
+    fn test_parse_rng_seed() {
+        assert_eq!(parse(&["--rng-seed", "42"]).unwrap().rng_seed, Some(42));
+        assert_eq!(parse(&[]).unwrap().rng_seed, None);
+        assert!(parse(&["--rng-seed", "-1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_engine() {
+        assert_eq!(parse(&[]).unwrap().engine, None);
+        assert_eq!(
+            parse(&["--engine", "dense"]).unwrap().engine,
+            Some(Engine::Dense)
+        );
+        assert!(parse(&["--engine", "gpu"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_boundary() {
+        assert_eq!(parse(&[]).unwrap().boundary, None);
+        assert_eq!(
+            parse(&["--boundary", "torus"]).unwrap().boundary,
+            Some(Boundary::Torus)
+        );
+        assert!(parse(&["--boundary", "sphere"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_symmetry() {
+        assert_eq!(parse(&[]).unwrap().symmetry, None);
+        assert_eq!(
+            parse(&["--symmetry", "four-fold"]).unwrap().symmetry,
+            Some(Symmetry::FourFold)
+        );
+        assert!(parse(&["--symmetry", "radial"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_renderer() {
+        assert_eq!(parse(&[]).unwrap().renderer, None);
+        assert_eq!(
+            parse(&["--renderer", "braille"]).unwrap().renderer,
+            Some(Renderer::Braille)
+        );
+        assert!(parse(&["--ascii"]).unwrap().ascii);
+        assert!(parse(&["--ascii", "--renderer", "blocks"]).is_err());
+        assert!(parse(&["--renderer", "sixel"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_colors() {
+        assert_eq!(parse(&[]).unwrap().colors, None);
+        assert_eq!(
+            parse(&["--colors", "256"]).unwrap().colors,
+            Some(ColorDepth::Ansi256)
+        );
+        assert_eq!(
+            parse(&["--colors", "truecolor"]).unwrap().colors,
+            Some(ColorDepth::TrueColor)
+        );
+        assert!(parse(&["--colors", "8"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_cell_width() {
+        assert_eq!(parse(&[]).unwrap().cell_width, None);
+        assert_eq!(parse(&["--cell-width", "1"]).unwrap().cell_width, Some(1));
+        assert!(parse(&["--cell-width", "3"]).is_err());
+        assert!(parse(&["--cell-width", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_mouse() {
+        assert!(parse(&["--mouse"]).unwrap().mouse);
+        assert!(parse(&["--no-mouse"]).unwrap().no_mouse);
+        assert!(parse(&["--mouse", "--no-mouse"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_scene() {
+        assert_eq!(
+            parse(&["--scene", "lab.toml"]).unwrap().scene,
+            Some(PathBuf::from("lab.toml"))
+        );
+    }
+
+    #[test]
+    fn test_parse_pattern() {
+        let args = parse(&[
+            "--pattern",
+            "glider.rle",
+            "--at",
+            "10,20",
+            "--count",
+            "5",
+            "--spacing",
+            "8,0",
+        ])
+        .unwrap();
+
+        assert_eq!(args.pattern.as_deref(), Some("glider.rle"));
+        assert_eq!(args.at, Some((10, 20)));
+        assert_eq!(args.count, 5);
+        assert_eq!(args.spacing, Some((8, 0)));
+
+        let args = parse(&["--pattern", "glider.rle", "--at", "-3,-4"]).unwrap();
+        assert_eq!(args.at, Some((-3, -4)));
+    }
+
+    #[test]
+    fn test_parse_crop() {
+        assert_eq!(parse(&["--crop", "2"]).unwrap().crop, Some(2));
+        assert!(parse(&["--crop"]).is_err());
+        assert!(parse(&["--crop", "wide"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_pipe() {
+        let args = parse(&["--pipe", "--generations", "10", "--separator", "---"]).unwrap();
+        assert!(args.pipe);
+        assert_eq!(args.generations, Some(10));
+        assert_eq!(args.separator, "---");
+
+        assert_eq!(args.format, Format::Text);
+        assert_eq!(
+            parse(&["--pipe", "--format", "json"]).unwrap().format,
+            Format::Json
+        );
+
+        assert!(parse(&["--generations", "10"]).is_err()); // Needs --pipe
+        assert!(parse(&["--pipe", "--format", "yaml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        assert_eq!(
+            parse(&["bench", "--generations", "50", "--seed", "3"])
+                .unwrap()
+                .command,
+            Some(Command::Bench(BenchArgs {
+                generations: 50,
+                seed: 3
+            }))
+        );
+        assert_eq!(
+            parse(&["bench"]).unwrap().command,
+            Some(Command::Bench(BenchArgs {
+                generations: 1000,
+                seed: 0
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_search() {
+        assert_eq!(
+            parse(&["search", "--soups", "50", "--methuselah", "500"])
+                .unwrap()
+                .command,
+            Some(Command::Search(SearchArgs {
+                soups: 50,
+                seed: None,
+                methuselah: 500,
+                log: PathBuf::from("search.rle"),
+                results: PathBuf::from("search.json"),
+                threads: None,
+            }))
+        );
+        assert!(matches!(
+            parse(&["search", "--seed", "7", "--threads", "4"])
+                .unwrap()
+                .command,
+            Some(Command::Search(SearchArgs {
+                seed: Some(7),
+                threads: Some(4),
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_submit() {
+        assert_eq!(
+            parse(&["submit", "--output", "haul.txt"]).unwrap().command,
+            Some(Command::Submit(SubmitArgs {
+                results: PathBuf::from("search.json"),
+                output: Some(PathBuf::from("haul.txt")),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_fetch() {
+        assert_eq!(
+            parse(&["fetch", "Gosper glider gun", "--refresh"])
+                .unwrap()
+                .command,
+            Some(Command::Fetch(FetchArgs {
+                name: String::from("Gosper glider gun"),
+                refresh: true,
+            }))
+        );
+        assert!(parse(&["fetch"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(parse(&["keys"]).unwrap().command, Some(Command::Keys));
+        assert!(parse(&["keys", "quit"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_replay() {
+        assert_eq!(
+            parse(&["--record", "run.txt.gz"]).unwrap().record,
+            Some(PathBuf::from("run.txt.gz"))
+        );
+
+        let args = parse(&["--fps", "30", "replay", "run.txt.gz"]).unwrap();
+        assert_eq!(args.fps, Some(30));
+        assert_eq!(
+            args.command,
+            Some(Command::Replay(ReplayArgs {
+                path: PathBuf::from("run.txt.gz")
+            }))
+        );
+        assert!(parse(&["replay"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["--scene"]).is_err());
+        assert!(parse(&["--nope"]).is_err());
+        assert!(parse(&["--pattern", "a.rle", "--at", "10"]).is_err());
+        assert!(parse(&["--pattern", "a.rle", "--count", "-1"]).is_err());
+        assert!(parse(&["--at", "1,2"]).is_err()); // Needs a pattern
+        assert!(parse(&["--rule", "23/3x"]).is_err());
+        assert!(parse(&["--paused", "--autoplay"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_coordinates() {
+        assert_eq!(parse_coordinates("10,20"), Some((10, 20)));
+        assert_eq!(parse_coordinates(" 3 4 "), Some((3, 4)));
+        assert_eq!(parse_coordinates("3, 4"), Some((3, 4)));
+        assert_eq!(parse_coordinates("3"), None);
+        assert_eq!(parse_coordinates("1,2,3"), None);
+        assert_eq!(parse_coordinates("-1,2"), Some((-1, 2)));
+        assert_eq!(parse_coordinates("1.5,2"), None);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    grid::{Boundary, Engine, Grid},
+    pattern::Pattern,
+    soup::Soup,
+};
+use clap::Args as ClapArgs;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
+
+/// The R-pentomino settles into still lifes, oscillators and six gliders after
+/// this many generations.
+pub const R_PENTOMINO_GENERATIONS: usize = 1103;
+const R_PENTOMINO: &str = "x = 3, y = 3\nb2o$2o$bo!";
+/// Leaves room for the R-pentomino's gliders to fly away without reaching an edge.
+const R_PENTOMINO_BOARD: usize = 1024;
+const SOUP_SIZE: usize = 256;
+const SOUP_DENSITY: f64 = 0.5;
+
+/// Options for the `bench` subcommand.
+#[derive(Debug, Clone, PartialEq, ClapArgs)]
+pub struct BenchArgs {
+    /// Generations to run the random soup for
+    #[arg(long, value_name = "N", default_value_t = 1000)]
+    pub generations: usize,
+
+    /// Seed for the random soup, so runs can be compared
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+}
+
+/// How a workload went.
+#[derive(Debug)]
+pub struct Report {
+    pub name: String,
+    pub generations: usize,
+    pub elapsed: Duration,
+    pub population: usize,
+    /// The most memory the process has used so far, in bytes, where known.
+    pub peak_memory: Option<u64>,
+}
+
+impl Report {
+    pub fn generations_per_second(&self) -> f64 {
+        self.generations as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Runs the standard workloads, writing a line for each as it finishes.
+pub fn run<W: Write>(args: &BenchArgs, mut output: W) -> std::io::Result<()> {
+    writeln!(
+        output,
+        "{:<24} {:>11} {:>9} {:>10} {:>10} {:>12}",
+        "Workload", "Generations", "Time", "Gen/s", "Population", "Peak memory"
+    )?;
+
+    let workloads: [fn(&BenchArgs, Engine) -> Report; 2] = [r_pentomino, soup];
+    for (workload, engine) in workloads
+        .into_iter()
+        .flat_map(|workload| [Engine::Sparse, Engine::Dense].map(|engine| (workload, engine)))
+    {
+        let report = workload(args, engine);
+        let peak_memory = match report.peak_memory {
+            Some(bytes) => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+            None => String::from("unknown"),
+        };
+        writeln!(
+            output,
+            "{:<24} {:>11} {:>8.2}s {:>10.0} {:>10} {:>12}",
+            report.name,
+            report.generations,
+            report.elapsed.as_secs_f64(),
+            report.generations_per_second(),
+            report.population,
+            peak_memory
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The R-pentomino from its start until it stabilizes.
+pub fn r_pentomino(_: &BenchArgs, engine: Engine) -> Report {
+    let mut game = Grid::new(R_PENTOMINO_BOARD, R_PENTOMINO_BOARD);
+    game.engine = engine;
+    let pattern = Pattern::parse_rle(R_PENTOMINO).unwrap_or_default();
+    let center = (R_PENTOMINO_BOARD / 2) as i64;
+    game.seed(pattern, (center, center));
+
+    let name = format!("R-pentomino ({})", engine);
+    measure(&name, &mut game, R_PENTOMINO_GENERATIONS)
+}
+
+/// A random soup filling a board with dead edges.
+pub fn soup(args: &BenchArgs, engine: Engine) -> Report {
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let mut game = Grid::new(SOUP_SIZE, SOUP_SIZE);
+    game.engine = engine;
+    game.boundary = Boundary::Dead;
+    game.seed(
+        Soup::random(&mut rng, SOUP_SIZE, SOUP_SIZE, SOUP_DENSITY),
+        (0, 0),
+    );
+
+    let name = format!("{}x{} soup ({})", SOUP_SIZE, SOUP_SIZE, engine);
+    measure(&name, &mut game, args.generations)
+}
+
+fn measure(name: &str, game: &mut Grid, generations: usize) -> Report {
+    let start = Instant::now();
+    for _ in 0..generations {
+        game.tick();
+    }
+
+    Report {
+        name: name.to_string(),
+        generations,
+        elapsed: start.elapsed(),
+        population: game.cells.len(),
+        peak_memory: peak_memory(),
+    }
+}
+
+/// Reads the process's peak resident set size where the platform reports it.
+fn peak_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_r_pentomino_stabilizes() {
+        let args = BenchArgs {
+            generations: 0,
+            seed: 0,
+        };
+        for engine in [Engine::Sparse, Engine::Dense] {
+            let report = r_pentomino(&args, engine);
+
+            // the well-known final population, gliders included
+            assert_eq!(report.population, 116);
+            assert_eq!(report.generations, R_PENTOMINO_GENERATIONS);
+        }
+    }
+
+    #[test]
+    fn test_soup_is_reproducible() {
+        let args = BenchArgs {
+            generations: 3,
+            seed: 7,
+        };
+
+        assert_eq!(
+            soup(&args, Engine::Sparse).population,
+            soup(&args, Engine::Dense).population
+        );
+    }
+}
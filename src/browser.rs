@@ -0,0 +1,178 @@
+use crate::{
+    grid::Cell,
+    library::Pick,
+    render::{BRAILLE_BLANK, BRAILLE_DOTS},
+};
+
+/// How many characters across a thumbnail is, each showing two columns of
+/// four cells.
+pub const THUMBNAIL_WIDTH: usize = 6;
+
+/// A seed listed in the browser, with where it is in the catalog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub category: String,
+    pub name: String,
+    pub pick: Pick,
+    /// What it looks like, from [`thumbnail`].
+    pub thumbnail: String,
+}
+
+/// A seed drawn in a single line of braille characters, shrunk to fit in
+/// [`THUMBNAIL_WIDTH`] if it's bigger, so each dot stands for a square of
+/// cells and shows if any of them is alive. Padded with spaces to line up.
+pub fn thumbnail(cells: &[Cell]) -> String {
+    let left = cells.iter().map(|cell| cell.0).min().unwrap_or_default();
+    let top = cells.iter().map(|cell| cell.1).min().unwrap_or_default();
+    let width = cells
+        .iter()
+        .map(|cell| cell.0 - left + 1)
+        .max()
+        .unwrap_or_default();
+    let height = cells
+        .iter()
+        .map(|cell| cell.1 - top + 1)
+        .max()
+        .unwrap_or_default();
+    let columns = 2 * THUMBNAIL_WIDTH as i64;
+    let scale = ((width + columns - 1) / columns)
+        .max((height + 3) / 4)
+        .max(1);
+
+    let mut dots = [0u8; THUMBNAIL_WIDTH];
+    for (x, y) in cells {
+        let (x, y) = ((x - left) / scale, (y - top) / scale);
+        dots[x as usize / 2] |= BRAILLE_DOTS[y as usize][x as usize % 2];
+    }
+    let used = ((width + scale - 1) / scale + 1) as usize / 2;
+    dots.iter()
+        .enumerate()
+        .map(|(index, dots)| match index < used {
+            true => char::from_u32(BRAILLE_BLANK + *dots as u32).unwrap_or(' '),
+            false => ' ',
+        })
+        .collect()
+}
+
+/// How well `query` fuzzy matches `text`, higher being better, or `None` if
+/// its letters don't all appear in `text` in order. Letters in a row and at
+/// the start of words count for more, so `hw` finds "HWSS" before "Beehive".
+pub fn score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last = None;
+
+    for ch in query
+        .to_lowercase()
+        .chars()
+        .filter(|ch| !ch.is_whitespace())
+    {
+        let found = from + text[from..].iter().position(|letter| *letter == ch)?;
+        score += 1;
+        if last.is_some_and(|last| last + 1 == found) {
+            score += 2;
+        }
+        if found == 0 || matches!(text[found - 1], ' ' | '-' | '_') {
+            score += 3;
+        }
+        last = Some(found);
+        from = found + 1;
+    }
+    Some(score)
+}
+
+/// The entries whose name, or failing that category, matches `query`, kept
+/// together by category in the order the categories first come up, with the
+/// position of the best match among them.
+pub fn search<'e>(entries: &'e [Entry], query: &str) -> (Vec<&'e Entry>, usize) {
+    let mut categories: Vec<&str> = Vec::new();
+    let mut matches: Vec<(&Entry, usize)> = Vec::new();
+    for entry in entries {
+        let Some(score) = score(query, &entry.name).or_else(|| score(query, &entry.category))
+        else {
+            continue;
+        };
+        if !categories.contains(&entry.category.as_str()) {
+            categories.push(&entry.category);
+        }
+        matches.push((entry, score));
+    }
+    // stable, so entries keep their order within a category
+    matches.sort_by_key(|(entry, _)| {
+        categories
+            .iter()
+            .position(|category| *category == entry.category)
+    });
+
+    let best = matches
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, (_, score))| *score)
+        .map(|(position, _)| position)
+        .unwrap_or_default();
+    (matches.into_iter().map(|(entry, _)| entry).collect(), best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(category: &str, name: &str, seed: usize) -> Entry {
+        Entry {
+            category: category.to_string(),
+            name: name.to_string(),
+            pick: Pick { category: 0, seed },
+            thumbnail: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score("", "Glider"), Some(0));
+        assert_eq!(score("gld", "Glider"), score("GLD", "glider"));
+        assert_eq!(score("dg", "Glider"), None);
+        assert!(score("hw", "HWSS") > score("hw", "Beehive"));
+    }
+
+    #[test]
+    fn test_thumbnail() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        assert_eq!(thumbnail(&glider), "⠬⠆    ");
+        // anywhere on the board
+        let moved: Vec<Cell> = glider.iter().map(|&(x, y)| (x - 10, y + 5)).collect();
+        assert_eq!(thumbnail(&moved), thumbnail(&glider));
+
+        // twice as big as fits, each dot is a square of four cells
+        let wide: Vec<Cell> = (0..24).flat_map(|x| (0..8).map(move |y| (x, y))).collect();
+        assert_eq!(thumbnail(&wide), "⣿⣿⣿⣿⣿⣿");
+        let corners = [(0, 0), (47, 7)];
+        assert_eq!(thumbnail(&corners), "⠁\u{2800}\u{2800}\u{2800}\u{2800}⠐");
+        assert_eq!(thumbnail(&[]), "      ");
+    }
+
+    #[test]
+    fn test_search() {
+        let entries = [
+            entry("Spaceships", "Glider", 1),
+            entry("Still lifes", "Block", 2),
+            entry("Spaceships", "LWSS", 3),
+            entry("Guns", "Glider gun", 4),
+            entry("Spaceships", "Long glider", 5),
+        ];
+        let indices = |query| {
+            let (found, best) = search(&entries, query);
+            let indices: Vec<usize> = found.iter().map(|entry| entry.pick.seed).collect();
+            (indices, best)
+        };
+
+        // grouped by category, the first match best when nothing's typed
+        assert_eq!(indices(""), (vec![1, 3, 5, 2, 4], 0));
+        assert_eq!(indices("glider"), (vec![1, 5, 4], 0));
+        assert_eq!(indices("gg"), (vec![4, 5], 0));
+        // by category when no name matches
+        assert_eq!(indices("still"), (vec![2], 0));
+        assert_eq!(indices("xyz"), (vec![], 0));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{grid::ALIVE_CELL, render::Renderer};
+use clap::ValueEnum;
+use crossterm::{
+    cursor,
+    style::Print,
+    terminal::{self, Clear, ClearType},
+    ExecutableCommand,
+};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::io::stdout;
+
+/// The levels of each of red, green and blue in the 6 by 6 by 6 cube of the
+/// 256 colors, from index 16.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+/// Where the 24 grays of the 256 colors start, from dark to light.
+const GRAYS: u8 = 232;
+/// Terminals that draw emoji as something else, or not at all, and don't
+/// report the mouse, by how `TERM` starts: the Linux and BSD consoles, and
+/// old or bare ones.
+const CONSOLE_TERMS: [&str; 4] = ["linux", "cons", "vt", "dumb"];
+/// The 16 colors as xterm draws them by default, in the order of their
+/// indices.
+const ANSI_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// How many colors a terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub enum ColorDepth {
+    /// Any color by its red, green and blue.
+    #[value(name = "truecolor")]
+    #[serde(rename = "truecolor")]
+    TrueColor,
+    /// The 16 colors, a 6 by 6 by 6 cube and 24 grays.
+    #[value(name = "256")]
+    #[serde(rename = "256")]
+    Ansi256,
+    /// Black, white, gray and the bright and dark of six others.
+    #[value(name = "16")]
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+impl ColorDepth {
+    /// The colors a terminal shows, by its `COLORTERM` and `TERM`: any with
+    /// `COLORTERM` set to `truecolor` or `24bit`, 256 where `TERM` says so,
+    /// like `xterm-256color`, and otherwise the 16 all of them have.
+    pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+        match (colorterm, term) {
+            (Some("truecolor" | "24bit"), _) => ColorDepth::TrueColor,
+            (_, Some(term)) if term.ends_with("-direct") => ColorDepth::TrueColor,
+            (_, Some(term)) if term.contains("256") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The nearest color to `color` among those there are, by its red,
+    /// green and blue. The 16 named ones are left as they are.
+    pub fn nearest(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::Ansi256, Color::Rgb(red, green, blue)) => {
+                Color::Indexed(nearest_indexed((red, green, blue)))
+            }
+            (ColorDepth::Ansi16, Color::Rgb(..) | Color::Indexed(_)) => {
+                let rgb = rgb(color).unwrap_or_default();
+                ANSI_COLORS
+                    .iter()
+                    .min_by_key(|(_, ansi)| distance(*ansi, rgb))
+                    .map_or(color, |(ansi, _)| *ansi)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// What the terminal the game runs in can do, as far as it can be told.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Whether emoji take the two columns each of the blocks needs.
+    pub wide_emoji: bool,
+    pub colors: ColorDepth,
+    /// Whether it reports the mouse moving and clicking.
+    pub mouse: bool,
+}
+
+impl Capabilities {
+    /// A terminal's, by its `COLORTERM` and `TERM` and how many columns it
+    /// drew an emoji across, if it was asked and answered. Consoles whose
+    /// width isn't known are taken to draw emoji narrow.
+    pub fn detect(
+        colorterm: Option<&str>,
+        term: Option<&str>,
+        emoji_width: Option<u16>,
+    ) -> Capabilities {
+        let console = term.is_some_and(is_console);
+        Capabilities {
+            wide_emoji: emoji_width.map_or(!console, |width| width == 2),
+            colors: ColorDepth::detect(colorterm, term),
+            mouse: !console,
+        }
+    }
+
+    /// The terminal the game runs in, asked how wide it draws an emoji when
+    /// `probe` is set and it isn't a console, which may not answer at all.
+    pub fn of_terminal(probe: bool) -> Capabilities {
+        let var = |name| std::env::var(name).ok();
+        let term = var("TERM");
+        let emoji_width = match probe && !term.as_deref().is_some_and(is_console) {
+            true => emoji_width(),
+            false => None,
+        };
+        Capabilities::detect(var("COLORTERM").as_deref(), term.as_deref(), emoji_width)
+    }
+
+    /// The best way to draw cells here: blocks where emoji are two columns
+    /// wide, and half blocks, just as square, where they aren't.
+    pub fn renderer(&self) -> Renderer {
+        match self.wide_emoji {
+            true => Renderer::Blocks,
+            false => Renderer::HalfBlock,
+        }
+    }
+}
+
+/// Whether `term` is one of the consoles.
+fn is_console(term: &str) -> bool {
+    CONSOLE_TERMS.iter().any(|prefix| term.starts_with(prefix))
+}
+
+/// How many columns the terminal draws an emoji across, found by drawing one
+/// at the start of the line and asking where the cursor ended up, or `None`
+/// if it doesn't say. The line is cleared after.
+fn emoji_width() -> Option<u16> {
+    terminal::enable_raw_mode().ok()?;
+    let mut stdout = stdout();
+    let width = stdout
+        .execute(Print(format!("\r{}", ALIVE_CELL)))
+        .and_then(|_| cursor::position())
+        .map(|(column, _)| column)
+        .ok();
+    let _ = stdout
+        .execute(Print('\r'))
+        .and_then(|stdout| stdout.execute(Clear(ClearType::CurrentLine)));
+    let _ = terminal::disable_raw_mode();
+    width
+}
+
+/// The red, green and blue of a color given by them or by index.
+fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Rgb(red, green, blue) => Some((red, green, blue)),
+        Color::Indexed(index @ 0..=15) => Some(ANSI_COLORS[index as usize].1),
+        Color::Indexed(index) if index >= GRAYS => {
+            let level = 8 + 10 * (index - GRAYS);
+            Some((level, level, level))
+        }
+        Color::Indexed(index) => {
+            let index = (index - 16) as usize;
+            Some((
+                CUBE_LEVELS[index / 36],
+                CUBE_LEVELS[index / 6 % 6],
+                CUBE_LEVELS[index % 6],
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The index of the nearest of the 256 colors past the first 16, in the
+/// cube or among the grays.
+fn nearest_indexed((red, green, blue): (u8, u8, u8)) -> u8 {
+    let level = |value: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&level| CUBE_LEVELS[level].abs_diff(value))
+            .unwrap_or_default() as u8
+    };
+    let cube = 16 + 36 * level(red) + 6 * level(green) + level(blue);
+    let average = (red as u16 + green as u16 + blue as u16) / 3;
+    let gray = GRAYS + (average.saturating_sub(3) / 10).min(23) as u8;
+    [cube, gray]
+        .into_iter()
+        .min_by_key(|&index| {
+            let indexed = rgb(Color::Indexed(index)).unwrap_or_default();
+            distance(indexed, (red, green, blue))
+        })
+        .unwrap_or(cube)
+}
+
+/// How far apart two colors are, as the square of the distance between
+/// their red, green and blue.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let square = |a: u8, b: u8| (a.abs_diff(b) as u32).pow(2);
+    square(a.0, b.0) + square(a.1, b.1) + square(a.2, b.2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        let detect = ColorDepth::detect;
+        assert_eq!(
+            detect(Some("truecolor"), Some("xterm-256color")),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(None, Some("xterm-direct")), ColorDepth::TrueColor);
+        assert_eq!(detect(None, Some("tmux-256color")), ColorDepth::Ansi256);
+        assert_eq!(detect(None, Some("linux")), ColorDepth::Ansi16);
+        assert_eq!(detect(None, None), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let xterm = Capabilities::detect(None, Some("xterm-256color"), None);
+        assert!(xterm.wide_emoji && xterm.mouse);
+        assert_eq!(xterm.colors, ColorDepth::Ansi256);
+        assert_eq!(xterm.renderer(), Renderer::Blocks);
+        // drawn one column wide when asked
+        let narrow = Capabilities::detect(Some("truecolor"), Some("xterm-256color"), Some(1));
+        assert_eq!(narrow.renderer(), Renderer::HalfBlock);
+        assert!(narrow.mouse);
+
+        let console = Capabilities::detect(None, Some("linux"), None);
+        assert!(!console.wide_emoji && !console.mouse);
+        assert_eq!(console.renderer(), Renderer::HalfBlock);
+        assert!(!Capabilities::detect(None, Some("vt220"), None).wide_emoji);
+        assert!(Capabilities::detect(None, None, None).wide_emoji);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let orange = Color::Rgb(0xe6, 0x9f, 0x00);
+        assert_eq!(ColorDepth::TrueColor.nearest(orange), orange);
+        // 215, 175, 0 in the cube
+        assert_eq!(ColorDepth::Ansi256.nearest(orange), Color::Indexed(178));
+        // a gray is nearer than any in the cube
+        assert_eq!(
+            ColorDepth::Ansi256.nearest(Color::Rgb(0x30, 0x30, 0x30)),
+            Color::Indexed(236)
+        );
+        assert_eq!(ColorDepth::Ansi16.nearest(orange), Color::Yellow);
+        assert_eq!(
+            ColorDepth::Ansi16.nearest(Color::Indexed(236)),
+            Color::Black
+        );
+        assert_eq!(ColorDepth::Ansi16.nearest(Color::Blue), Color::Blue);
+    }
+}
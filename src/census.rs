@@ -0,0 +1,268 @@
+use crate::{
+    grid::{Cell, Grid},
+    seed::{IsSeed, Oscillator, Seed, Spaceship, Still},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Common still lifes from Life's ash besides the built-in seeds, drawn with
+/// `O` for live cells.
+const STILL_LIFES: [(&str, &str); 8] = [
+    ("Ship", "OO.\nO.O\n.OO"),
+    ("Long boat", ".O..\nO.O.\n.O.O\n..OO"),
+    ("Pond", ".OO.\nO..O\nO..O\n.OO."),
+    ("Barge", ".O..\nO.O.\n.O.O\n..O."),
+    ("Mango", ".OO..\nO..O.\n.O..O\n..OO."),
+    ("Aircraft carrier", "OO..\nO..O\n..OO"),
+    ("Eater", "OO..\nO.O.\n..O.\n..OO"),
+    ("Snake", "OO.O\nO.OO"),
+];
+
+/// Each object's apgcode, the name Catagolue knows it by.
+const APGCODES: [(&str, &str); 22] = [
+    ("Block", "xs4_33"),
+    ("Beehive", "xs6_696"),
+    ("Loaf", "xs7_2596"),
+    ("Boat", "xs5_253"),
+    ("Tub", "xs4_252"),
+    ("Blinker", "xp2_7"),
+    ("Toad", "xp2_7e"),
+    ("Beacon", "xp2_318c"),
+    ("Pulsar", "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401"),
+    ("Penta-decathlon", "xp15_4r4z4r4"),
+    ("Glider", "xq4_153"),
+    ("LWSS", "xq4_6frc"),
+    ("MWSS", "xq4_27dee6"),
+    ("HWSS", "xq4_27deee6"),
+    ("Ship", "xs6_356"),
+    ("Long boat", "xs7_25ac"),
+    ("Pond", "xs8_6996"),
+    ("Barge", "xs6_25a4"),
+    ("Mango", "xs8_69ic"),
+    ("Aircraft carrier", "xs6_39c"),
+    ("Eater", "xs7_178c"),
+    ("Snake", "xs6_bd"),
+];
+
+/// The longest period of the objects looked for.
+const MAX_PERIOD: usize = 15;
+
+/// The cells of a shape moved to start at 0,0 and turned or flipped into
+/// whichever of its eight orientations sorts first, so any phase of an
+/// object matches however it sits on the board.
+pub fn canonical(cells: &[Cell]) -> Vec<Cell> {
+    let orientations: [fn(Cell) -> Cell; 8] = [
+        |(x, y)| (x, y),
+        |(x, y)| (-x, y),
+        |(x, y)| (x, -y),
+        |(x, y)| (-x, -y),
+        |(x, y)| (y, x),
+        |(x, y)| (-y, x),
+        |(x, y)| (y, -x),
+        |(x, y)| (-y, -x),
+    ];
+    orientations
+        .iter()
+        .map(|orient| {
+            let turned: Vec<Cell> = cells.iter().map(|cell| orient(*cell)).collect();
+            let left = turned.iter().map(|cell| cell.0).min().unwrap_or_default();
+            let top = turned.iter().map(|cell| cell.1).min().unwrap_or_default();
+            let mut shape: Vec<Cell> = turned
+                .into_iter()
+                .map(|(x, y)| (x - left, y - top))
+                .collect();
+            shape.sort_unstable();
+            shape
+        })
+        .min()
+        .unwrap_or_default()
+}
+
+/// The canonical form of every phase of each known object, with its name.
+pub fn catalog() -> HashMap<Vec<Cell>, &'static str> {
+    let seeds = [
+        Seed::Still(Still::Block),
+        Seed::Still(Still::Beehive),
+        Seed::Still(Still::Loaf),
+        Seed::Still(Still::Boat),
+        Seed::Still(Still::Tub),
+        Seed::Oscillator(Oscillator::Blinker),
+        Seed::Oscillator(Oscillator::Toad),
+        Seed::Oscillator(Oscillator::Beacon),
+        Seed::Oscillator(Oscillator::Pulsar),
+        Seed::Oscillator(Oscillator::PentaDecathlon),
+        Seed::Spaceship(Spaceship::Glider),
+        Seed::Spaceship(Spaceship::LwSpaceship),
+        Seed::Spaceship(Spaceship::MwSpaceship),
+        Seed::Spaceship(Spaceship::HwSpaceship),
+    ];
+    let still_lifes = STILL_LIFES.iter().map(|(name, art)| {
+        let cells = art.lines().enumerate().flat_map(|(y, line)| {
+            line.char_indices()
+                .filter(|(_, ch)| *ch == 'O')
+                .map(move |(x, _)| (x as i64, y as i64))
+        });
+        (*name, cells.collect::<Vec<Cell>>())
+    });
+    let objects = seeds
+        .iter()
+        .map(|seed| (seed.name(), seed.cells((0, 0))))
+        .chain(still_lifes);
+
+    let mut catalog = HashMap::new();
+    for (name, cells) in objects {
+        let mut grid = Grid::new(64, 64);
+        for cell in cells {
+            grid.add_cell(cell);
+        }
+        for _ in 0..MAX_PERIOD {
+            let shape = canonical(&grid.cells.iter().copied().collect::<Vec<_>>());
+            if catalog.insert(shape, name).is_some() {
+                break;
+            }
+            grid.tick();
+        }
+    }
+    catalog
+}
+
+/// The apgcode of an object in the [`catalog`].
+pub fn apgcode(name: &str) -> Option<&'static str> {
+    APGCODES
+        .iter()
+        .find(|(object, _)| *object == name)
+        .map(|(_, code)| *code)
+}
+
+/// Splits the cells into groups of cells touching each other, sideways or
+/// diagonally, or within `reach` cells of each other.
+pub fn components(cells: &HashSet<Cell>, reach: i64) -> Vec<Vec<Cell>> {
+    let mut unvisited = cells.clone();
+    let mut components = Vec::new();
+
+    while let Some(&start) = unvisited.iter().next() {
+        unvisited.remove(&start);
+        let mut component = vec![start];
+        let mut index = 0;
+        while let Some(&(x, y)) = component.get(index) {
+            for dy in -reach..=reach {
+                for dx in -reach..=reach {
+                    if unvisited.remove(&(x + dx, y + dy)) {
+                        component.push((x + dx, y + dy));
+                    }
+                }
+            }
+            index += 1;
+        }
+        components.push(component);
+    }
+    components
+}
+
+/// How many of each known object are on the board, most common first, with
+/// anything else counted as "Other".
+///
+/// Cells within two of each other are looked up together first, so objects
+/// in pieces, like a pulsar's bars, are found whole. Groups that aren't known
+/// are split into the pieces touching each other, to find objects that are
+/// just close together.
+pub fn census(cells: &HashSet<Cell>) -> Vec<(&'static str, usize)> {
+    count(cells, &catalog())
+}
+
+/// The census against a [`catalog`] made beforehand, to count many boards.
+pub fn count(
+    cells: &HashSet<Cell>,
+    catalog: &HashMap<Vec<Cell>, &'static str>,
+) -> Vec<(&'static str, usize)> {
+    let mut counts: HashMap<&'static str, usize> = HashMap::new();
+
+    for group in components(cells, 2) {
+        if let Some(name) = catalog.get(&canonical(&group)) {
+            *counts.entry(name).or_default() += 1;
+            continue;
+        }
+        for piece in components(&group.into_iter().collect(), 1) {
+            let name = catalog.get(&canonical(&piece)).unwrap_or(&"Other");
+            *counts.entry(name).or_default() += 1;
+        }
+    }
+
+    let mut counts: Vec<(&'static str, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::Pattern;
+
+    #[test]
+    fn test_canonical() {
+        // a glider pointing each way is the same shape
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let flipped: Vec<Cell> = glider.iter().map(|&(x, y)| (10 - x, y + 5)).collect();
+        let turned: Vec<Cell> = glider.iter().map(|&(x, y)| (y, -x)).collect();
+        assert_eq!(canonical(&glider), canonical(&flipped));
+        assert_eq!(canonical(&glider), canonical(&turned));
+        assert_ne!(canonical(&glider), canonical(&[(0, 0), (1, 0), (2, 0)]));
+    }
+
+    #[test]
+    fn test_catalog_has_every_phase() {
+        let catalog = catalog();
+        let names = |name| catalog.values().filter(|n| **n == name).count();
+        assert_eq!(names("Block"), 1);
+        assert_eq!(names("Blinker"), 1); // both phases are the same shape turned
+        assert_eq!(names("Glider"), 2); // two shapes, each flipped
+        assert_eq!(names("Pulsar"), 3);
+        assert_eq!(names("Pond"), 1);
+    }
+
+    #[test]
+    fn test_apgcodes() {
+        let catalog = catalog();
+        let mut names: Vec<&str> = catalog.values().copied().collect();
+        names.sort_unstable();
+        names.dedup();
+        for name in names {
+            let code = apgcode(name).unwrap_or_else(|| panic!("no apgcode for {}", name));
+            let pattern = Pattern::parse_apgcode(code).unwrap();
+            assert_eq!(catalog.get(&canonical(&pattern.cells)), Some(&name));
+        }
+        assert_eq!(apgcode("Other"), None);
+    }
+
+    #[test]
+    fn test_census() {
+        let mut grid = Grid::new(80, 80);
+        grid.seed(Still::Block, (0, 0));
+        grid.seed(Still::Block, (10, 0));
+        grid.seed(Oscillator::Blinker, (20, 0));
+        grid.seed(Spaceship::Glider, (30, 0));
+        grid.seed(Oscillator::Pulsar, (0, 20));
+        // a cell on its own isn't anything
+        grid.add_cell((40, 40));
+        // a pond, one of the still lifes that aren't seeds
+        for cell in [(51, 50), (52, 50), (50, 51), (53, 51), (50, 52), (53, 52)] {
+            grid.add_cell(cell);
+        }
+        grid.add_cell((51, 53));
+        grid.add_cell((52, 53));
+        // a blinker a cell away from a block is still both
+        grid.seed(Still::Block, (60, 60));
+        grid.seed(Oscillator::Blinker, (63, 60));
+
+        assert_eq!(
+            census(&grid.cells),
+            vec![
+                ("Block", 3),
+                ("Blinker", 2),
+                ("Glider", 1),
+                ("Other", 1),
+                ("Pond", 1),
+                ("Pulsar", 1),
+            ]
+        );
+    }
+}
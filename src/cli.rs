@@ -1,51 +1,289 @@
+use clap::ValueEnum;
 use crossterm::{
-    event::{self, KeyCode, KeyEvent, MouseEvent},
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        KeyCode, KeyEvent, KeyboardEnhancementFlags, MouseEvent, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement,
+        EnterAlternateScreen, LeaveAlternateScreen,
+    },
     ExecutableCommand,
 };
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     prelude::{CrosstermBackend, Stylize, Terminal},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Paragraph},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Sparkline, Wrap},
     CompletedFrame,
 };
 use std::{
-    io::stdout,
-    time::{Duration, Instant},
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    fs::File,
+    io::{stdout, BufWriter},
+    path::Path,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
-    grid::Grid,
-    seed::{Oscillator, Seed, Spaceship, Still},
+    args::{self, Args, Command},
+    browser,
+    capability::Capabilities,
+    census,
+    clipboard::{self, Copied},
+    compress, config,
+    cursor::{self, Snap},
+    cycle::{self, CycleDetector},
+    escape::EscapeTracker,
+    explorer::{self, Candidate},
+    export::{self, InstantReplay},
+    golly,
+    grid::{Boundary, BoundingBox, Cell, Grid, Guides, Shade, CELL_COLORS, ZOOM_LEVELS},
+    history::{History, Snapshot},
+    keymap::Keymap,
+    leaderboard::{Entry, Leaderboard},
+    library::{self, Category, Pick, Source, UserSeed},
+    palette,
+    pattern::Pattern,
+    patterns::{self, PatternDir},
+    recording::{Playback, Recorder, Recording},
+    render::Renderer,
+    rule::Rule,
+    savestate::Savestate,
+    scene::Scene,
+    search,
+    seed::{InPlace, IsSeed, Orientation, Oriented, Seed, Symmetry, SEEDS},
+    soup::{self, Soup},
+    stats::{PopulationHistory, StatsLog},
+    theme::{self, Theme},
+    watch::{self, Breakpoint},
+    wireworld::Electron,
 };
 
-const FRAMETIME_MILIS: u64 = 16; // 60 fps
+#[cfg(feature = "gamepad")]
+use crate::gamepad::Gamepad;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// 60 fps, however fast the game ticks
+const FRAMETIME_MILIS: u64 = 16;
+// on terminals that don't report key releases, a held key is considered
+// released once the terminal stops auto-repeating it for this long
+const HOLD_TIMEOUT_MILIS: u64 = 600;
+// the board is only rebuilt once the terminal has kept its size for this long
+const RESIZE_DEBOUNCE_MILIS: u64 = 100;
 const TITLE: &str = "Conway's Game of Life";
 const INSTRUCTIONS: &str = concat!(
-    "\n",
-    r#"Esc or Q (quit) | 0-9 A-F (select seed) | "#,
-    r#"Arrows (move seed) | Shift+Arrows (move faster) | "#,
-    r#"Space (place seed) | P (play or pause) | Enter (tick) | Delete (clear)"#
+    r#"Esc or Q (quit) | 1-9 (pick a seed in the category) | 0 (single cell) | Ctrl+Y (browse and search seeds) | "#,
+    r#"Tab/Shift+Tab (next/previous category) | PageUp/PageDown (previous/next seed in it) | Arrows (move seed) | Shift+Arrows (move faster) | Ctrl+Arrows (pan) | Home (center on live cells) | End (zoom to fit them) | +/- (zoom) | "#,
+    r#"S (snap cursor) | W (cycle edges) | \ (mirror drawing) | Shift+\ (mirror at cursor) | T (seed color) | L (load pattern or scene) | "#,
+    r#"Space (place seed) | P (play or pause) | Enter (tick) | Delete (clear) | U (undo) | Ctrl+R (redo) | "#,
+    r#"G (go to) | Ctrl+F (advance N generations) | Ctrl+P (pause at generation, population or cell) | V (watch cell) | ? (analyze) | N (census) | Ctrl+N (neighbor counts) | Z (remove escaped ships) | [/] (slower/faster) | Alt+1-9 (speed) | Alt+0 (slow motion) | Hold > (fast-forward) | "#,
+    r#"H (highlight births, deaths) | O (onion skin, trails) | Y (color by age) | C (color theme) | B (blocks, half blocks, braille, ASCII) | M (measure) | # (guide lines) | Ctrl+G (population graph) | Ctrl+T (log stats) | R (edit rule) | X (explore rules) | Ctrl+L (soup leaderboard) | "#,
+    r#"Right-drag or Ctrl+Space (select) | Ctrl+C/Ctrl+X (copy/cut as RLE) | Ctrl+V (paste) | Ctrl+B (stamp copied) | ,/. (turn) | / and ; (flip) | Alt+,/. and Alt+/ and ; (turn or flip the board) | Ctrl+D (move selection, or the whole board) | Ctrl+W (draw a new seed) | Ctrl+A (scatter copies) | Ctrl+K (crop the board) | "#,
+    r#"K (bookmark) | J (jump to bookmark) | : (type a command) | "#,
+    r#"I (save instant replay) | Ctrl+E (export) | Ctrl+S (save) | Ctrl+O (load)"#
 );
+/// The keys shown under the board, with the rest of [`INSTRUCTIONS`] a key
+/// away.
+const HINT: &str = "F1 or :keys (all keys) | Esc or Q (quit) | Space (place seed) | P (play or pause) | : (type a command)";
+
+/// How far Ctrl+Arrows move the view.
+const PAN_CELLS: i64 = 8;
+/// How many cells across and down the seed editor's canvas is.
+const EDITOR_SIZE: i64 = 16;
+/// How long names and categories typed in the seed editor can be.
+const EDITOR_NAME_LENGTH: usize = 30;
+
+/// How many rows the population graph takes at the bottom of the board.
+const POPULATION_GRAPH_HEIGHT: u16 = 8;
+
+/// Simulation speeds bound to Alt+1 through Alt+9.
+const SPEED_PRESETS: [Speed; 9] = [
+    Speed::Rate(1),
+    Speed::Rate(2),
+    Speed::Rate(5),
+    Speed::Rate(10),
+    Speed::Rate(15),
+    Speed::Rate(30),
+    Speed::Rate(60),
+    Speed::Rate(120),
+    Speed::Unlimited,
+];
+
+/// Slow-motion speeds cycled through with Alt+0.
+const SLOW_MOTION_PRESETS: [Speed; 4] = [
+    Speed::Every(2),
+    Speed::Every(3),
+    Speed::Every(5),
+    Speed::Every(10),
+];
 
 #[derive(Debug)]
 struct State {
+    mode: Mode,
     play: PlayState,
-    origin: (usize, usize),
-    seed_index: u8,
+    origin: Cell,
+    /// How far the arrow keys move the cursor.
+    snap: Snap,
+    /// Where the seed picked is in the catalog, by category.
+    pick: Pick,
+    /// The built-in seed picked, placed unless there's a pattern instead.
+    seed: Seed,
+    /// How what's placed is turned and flipped, with `,` `.` `/` and `;`.
+    orientation: Orientation,
+    /// Seeds drawn in the editor and saved, offered after the built-in ones,
+    /// followed by those in the patterns directory, then those added from
+    /// Golly's collection with --golly.
+    user_seeds: Vec<UserSeed>,
+    /// How many of the user's seeds are saved ones.
+    saved_seeds: usize,
+    /// How many of the user's seeds, after the saved ones, are from the
+    /// patterns directory.
+    pattern_seeds: usize,
+    /// The patterns directory, read again whenever a file in it changes.
+    patterns: Option<PatternDir>,
+    /// The keys set in config.toml, turned into the default ones as they're
+    /// pressed.
+    keymap: Keymap,
+    /// The built-in color themes and those in config.toml, to switch between
+    /// with C.
+    themes: Vec<Theme>,
+    /// Which of the themes the game is drawn in.
+    theme: usize,
+    /// How the board is drawn, which sets how many cells fit across it.
+    renderer: Renderer,
+    /// How many columns each cell takes where a character shows one, or
+    /// `None` for the renderer's own.
+    cell_width: Option<u16>,
+    /// A pattern loaded with L or pasted, placed instead of the built-in seeds.
+    pattern: Option<Pattern>,
+    /// The rule a pasted pattern comes with, switched to when it's placed,
+    /// unless something else is picked first.
+    pasted_rule: Option<Rule>,
+    /// The last selection copied or cut, kept to stamp again with Ctrl+B
+    /// after placing something else, until the next one is copied.
+    buffer: Option<Pattern>,
+    /// Where the selection was started from, while its other corner follows
+    /// the cursor after Ctrl+Space, or the mouse while right-dragging.
+    select_anchor: Option<Cell>,
+    /// When the latest tick was due, so ticks keep to the speed however
+    /// often the board is drawn.
     last_update: Instant,
-    target_framerate: u64,
+    /// When the board was last drawn, to draw it once per frame.
+    last_frame: Instant,
+    meter: Meter,
+    speed: Speed,
+    /// When the fast-forward key was last pressed or repeated, until released.
+    fast_forward: Option<Instant>,
+    reports_key_release: bool,
+    /// Feedback for the last action, shown in the status line.
+    message: Option<String>,
+    rng: StdRng,
+    /// What the next soup is made from, shown so it can be made again by
+    /// starting with it as --rng-seed.
+    rng_seed: u64,
+    replay: InstantReplay,
+    /// Boards from before seeds were placed, the board was cleared or loaded,
+    /// or a tick was stepped through by hand.
+    history: History,
+    /// Writes every change to the board to the file given with --record.
+    recorder: Option<Recorder<compress::Writer>>,
+    /// The recording being played back instead of running the rule.
+    playback: Option<Playback>,
+    /// Writes each generation's births, deaths and population as CSV.
+    stats: Option<StatsLog<compress::Writer>>,
+    /// The population of the latest generations, for the graph.
+    population: PopulationHistory,
+    /// Whether the population graph is shown over the bottom of the board.
+    show_population: bool,
+    /// What pauses the game when a tick gets there.
+    breakpoints: Vec<Breakpoint>,
+    /// Pauses the game once the board dies out or starts repeating.
+    cycles: CycleDetector,
+    /// Counts the spaceships flying away from the rest of the board.
+    escapes: EscapeTracker,
+    /// Boards saved under a name to jump back to, oldest first.
+    bookmarks: Vec<(String, Snapshot)>,
+    /// Whether the board keeps its size instead of following the terminal's.
+    fixed_size: bool,
+    /// When the terminal was last resized, until the board is laid out again.
+    pending_resize: Option<Instant>,
+    /// Padding around the live cells when cropping exports, if cropping.
+    crop: Option<usize>,
+    /// The cell under the mouse, described in the status line while paused.
+    hovered: Option<Cell>,
+    /// Whether seeds placed by hand over live cells wait to be placed again.
+    confirm_overlap: bool,
+    /// Where a seed over live cells was placed once, waiting to be confirmed.
+    unconfirmed: Option<Cell>,
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<Gamepad>,
     game: Grid,
 }
 
+impl State {
+    fn is_fast_forwarding(&self) -> bool {
+        match self.fast_forward {
+            Some(_) if self.reports_key_release => true,
+            Some(pressed) => pressed.elapsed() < Duration::from_millis(HOLD_TIMEOUT_MILIS),
+            None => false,
+        }
+    }
+}
+
 impl Default for State {
     fn default() -> Self {
         State {
-            seed_index: 0,
+            mode: Mode::Normal,
+            pick: Pick::default(),
+            seed: SEEDS[0],
+            orientation: Orientation::default(),
+            user_seeds: Vec::new(),
+            saved_seeds: 0,
+            pattern_seeds: 0,
+            patterns: None,
+            keymap: Keymap::default(),
+            themes: theme::built_in(),
+            theme: 0,
+            renderer: Renderer::default(),
+            cell_width: None,
+            pattern: None,
+            pasted_rule: None,
+            select_anchor: None,
+            buffer: None,
             origin: (0, 0),
-            target_framerate: 60,
+            snap: Snap::default(),
+            speed: SPEED_PRESETS[4],
+            fast_forward: None,
+            reports_key_release: false,
+            message: None,
+            rng: StdRng::from_entropy(),
+            rng_seed: 0,
+            replay: InstantReplay::default(),
+            history: History::default(),
+            recorder: None,
+            playback: None,
+            stats: None,
+            population: PopulationHistory::default(),
+            show_population: false,
+            breakpoints: Vec::new(),
+            cycles: CycleDetector::default(),
+            escapes: EscapeTracker::default(),
+            bookmarks: Vec::new(),
+            fixed_size: false,
+            pending_resize: None,
+            crop: None,
+            hovered: None,
+            confirm_overlap: false,
+            unconfirmed: None,
+            #[cfg(feature = "gamepad")]
+            gamepad: None,
             last_update: Instant::now(),
+            last_frame: Instant::now(),
+            meter: Meter::default(),
             play: PlayState::Paused,
             game: Grid::new(0, 0),
         }
@@ -59,37 +297,429 @@ enum PlayState {
     Playing,
 }
 
+/// What the keyboard is currently driving.
+#[derive(Debug, Default)]
+enum Mode {
+    #[default]
+    Normal,
+    /// A text prompt is open, holding the text typed so far.
+    Prompt(Prompt, String),
+    /// The rule editor is open with the cursor on a birth or survival count.
+    RuleEditor { survival: bool, count: usize },
+    /// The rule explorer is showing a randomly found rule.
+    Explorer(Candidate),
+    /// The bookmarks are listed with the cursor on one of them.
+    Bookmarks { selected: usize },
+    /// The objects counted on the board, most common first.
+    Census(Vec<(&'static str, usize)>),
+    /// Every key and what it does.
+    Keys,
+    /// The longest-lived soups searched, with the cursor on one of them.
+    Leaderboard {
+        entries: Vec<Entry>,
+        selected: usize,
+    },
+    /// The live cells lifted out of the selection, where they were, being
+    /// nudged by `offset` before they're dropped.
+    Moving {
+        cells: Vec<Cell>,
+        from: BoundingBox,
+        offset: Cell,
+    },
+    /// A new seed is being drawn, to be saved to the user's seeds.
+    Editor(Editor),
+    /// Everything on the board, moved by `offset` so far.
+    Shifting { offset: Cell },
+    /// The seeds are listed in the sidebar, those matching the search typed
+    /// so far, with the cursor on one of them.
+    Browser {
+        /// Every seed there is, with its thumbnail drawn once when opened.
+        entries: Vec<browser::Entry>,
+        query: String,
+        selected: usize,
+    },
+}
+
+/// A seed drawn on the editor's canvas, with what it'll be saved as.
+#[derive(Debug, Default)]
+struct Editor {
+    /// Live cells, from the top-left corner of the canvas.
+    cells: HashSet<Cell>,
+    cursor: Cell,
+    name: String,
+    category: String,
+    field: EditorField,
+}
+
+/// The part of the seed editor keys go to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum EditorField {
+    #[default]
+    Canvas,
+    Name,
+    Category,
+}
+
+/// Questions asked through the text prompt.
+#[derive(Debug, Clone, Copy)]
+enum Prompt {
+    Goto,
+    Save,
+    Load,
+    Export,
+    Pattern,
+    Bookmark,
+    Advance,
+    Breakpoint,
+    Stats,
+    Scatter,
+    /// A command from the [`palette`], listing those it could be.
+    Command,
+}
+
+impl Prompt {
+    fn title(&self) -> &'static str {
+        match self {
+            Prompt::Goto => "Go to x,y",
+            Prompt::Save => "Save to (.gz/.zst to compress)",
+            Prompt::Export => "Export to (.rle, .cells, .lif, .life, .png or .txt)",
+            Prompt::Pattern => {
+                "Load pattern (.rle, .cells, .lif, .life, .mc, apgcode) or scene (.toml, .json)"
+            }
+            Prompt::Load => "Load from",
+            Prompt::Bookmark => "Bookmark as",
+            Prompt::Advance => "Advance generations",
+            Prompt::Stats => "Log stats to (.csv, .gz/.zst to compress)",
+            Prompt::Breakpoint => "Pause at gen N, pop >N <N =N, or cell x,y (empty to clear)",
+            Prompt::Scatter => "Scatter N copies of the seed (Nr to turn each at random)",
+            Prompt::Command => "Command (Tab to complete)",
+        }
+    }
+
+    fn width(&self) -> u16 {
+        match self {
+            Prompt::Goto | Prompt::Advance => 30,
+            Prompt::Bookmark => 40,
+            Prompt::Save | Prompt::Load | Prompt::Stats => 50,
+            Prompt::Export => 60,
+            Prompt::Breakpoint | Prompt::Scatter => 66,
+            Prompt::Pattern | Prompt::Command => 80,
+        }
+    }
+
+    fn accepts(&self, ch: char) -> bool {
+        match self {
+            Prompt::Goto => ch.is_ascii_digit() || ch == ',' || ch == ' ' || ch == '-',
+            Prompt::Advance => ch.is_ascii_digit(),
+            Prompt::Breakpoint => ch.is_ascii_digit() || "<>=,- ".contains(ch),
+            Prompt::Scatter => ch.is_ascii_digit() || ch == 'r' || ch == 'R',
+            Prompt::Save
+            | Prompt::Load
+            | Prompt::Export
+            | Prompt::Pattern
+            | Prompt::Bookmark
+            | Prompt::Stats
+            | Prompt::Command => !ch.is_control(),
+        }
+    }
+}
+
+/// How fast the simulation runs while playing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Speed {
+    /// Generations per second.
+    Rate(u32),
+    /// One generation every this many seconds.
+    Every(u32),
+    /// As many generations as fit in each frame.
+    Unlimited,
+}
+
+impl Speed {
+    fn interval(&self) -> Duration {
+        match self {
+            Speed::Rate(rate) => Duration::from_secs(1) / *rate,
+            Speed::Every(seconds) => Duration::from_secs(*seconds as u64),
+            Speed::Unlimited => Duration::ZERO,
+        }
+    }
+}
+
+impl Display for Speed {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Speed::Rate(rate) => write!(f, "{} gen/s", rate),
+            Speed::Every(seconds) => write!(f, "1 gen/{}s", seconds),
+            Speed::Unlimited => write!(f, "unlimited"),
+        }
+    }
+}
+
+/// Counts the frames drawn and generations ticked, to show how fast they
+/// actually go over the last second.
+#[derive(Debug)]
+struct Meter {
+    since: Instant,
+    frames: usize,
+    ticks: usize,
+    /// Frames per second over the last whole second.
+    fps: f64,
+    /// Generations per second over the last whole second.
+    ups: f64,
+}
+
+impl Default for Meter {
+    fn default() -> Self {
+        Meter {
+            since: Instant::now(),
+            frames: 0,
+            ticks: 0,
+            fps: 0.0,
+            ups: 0.0,
+        }
+    }
+}
+
+impl Meter {
+    fn frame(&mut self) {
+        self.frames += 1;
+        let elapsed = self.since.elapsed().as_secs_f64();
+        if elapsed >= 1.0 {
+            self.fps = self.frames as f64 / elapsed;
+            self.ups = self.ticks as f64 / elapsed;
+            *self = Meter {
+                fps: self.fps,
+                ups: self.ups,
+                ..Default::default()
+            };
+        }
+    }
+}
+
 struct ExitSignal(bool);
 
-pub fn run() -> std::io::Result<()> {
-    let mut terminal = setup()?;
-    let size = terminal.size()?;
-    let width = size.width as usize;
-    let height = size.height as usize;
+pub fn run(args: Args) -> std::io::Result<()> {
+    // the emoji's width only matters to the renderer picked for it
+    let chosen = args.ascii || args.renderer.is_some();
+    let capabilities = Capabilities::of_terminal(!chosen);
+    let renderer = match args.ascii {
+        true => Renderer::Ascii,
+        false => args.renderer.unwrap_or_else(|| capabilities.renderer()),
+    };
+    let mouse = match (args.mouse, args.no_mouse) {
+        (true, _) => true,
+        (_, true) => false,
+        _ => capabilities.mouse,
+    };
+    let (width, height) = terminal::size()?;
+    let (width, height) = board_size(Rect::new(0, 0, width, height), renderer, args.cell_width);
+
+    let mut game = Grid::new(width, height);
+    let mut fixed_size = false;
+
+    if let Some(path) = &args.scene {
+        let scene = Scene::load(path).map_err(std::io::Error::other)?;
+        scene.apply(&mut game).map_err(std::io::Error::other)?;
+        fixed_size = scene.width.is_some() || scene.height.is_some();
+    }
+
+    if args.width.is_some() || args.height.is_some() {
+        game.resize(
+            args.width.unwrap_or(game.width),
+            args.height.unwrap_or(game.height),
+        );
+        fixed_size = true;
+    }
+    if let Some(rule) = args.rule {
+        game.rule = rule;
+    }
+    if let Some(boundary) = args.boundary {
+        game.boundary = boundary;
+    }
+    game.symmetry = args.symmetry.unwrap_or_default();
+    game.engine = args.engine.unwrap_or_default();
+
+    let playback = match &args.command {
+        Some(Command::Replay(replay)) => {
+            let recording = Recording::load(&replay.path)?;
+            fixed_size = true;
+            Some(Playback::start(recording, &mut game))
+        }
+        _ => None,
+    };
+
+    // show the middle of a board larger than the terminal
+    game.pan(
+        (game.width.saturating_sub(game.viewport.width) / 2) as i64,
+        (game.height.saturating_sub(game.viewport.height) / 2) as i64,
+    );
+
+    // place the cursor at the center of the view
+    let (width, height) = (game.viewport.width, game.viewport.height);
+    let origin = game.to_universe((width / 2, height / 2 - (height / 15)));
+
+    let patterns = match &args.patterns_dir {
+        Some(path) if !path.is_dir() => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("{}: no such directory", path.display()),
+            ))
+        }
+        Some(path) => Some(PatternDir::new(path.clone())),
+        None => patterns::dir().ok().map(PatternDir::new),
+    };
+    let mut user_seeds = library::dir().and_then(library::load).unwrap_or_default();
+    let saved_seeds = user_seeds.len();
+    user_seeds.extend(patterns.as_ref().map(PatternDir::load).unwrap_or_default());
+    let pattern_seeds = user_seeds.len() - saved_seeds;
+    if let Some(path) = &args.golly {
+        let collection = golly::load(path).map_err(|error| {
+            std::io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+        })?;
+        user_seeds.extend(collection);
+    }
+    let keymap = Keymap::load().map_err(std::io::Error::other)?;
+    let colors = args.colors.unwrap_or(capabilities.colors);
+    let themes: Vec<Theme> = theme::load()
+        .map_err(std::io::Error::other)?
+        .into_iter()
+        .map(|theme| theme.fit(colors))
+        .collect();
+    let theme = match &args.theme {
+        Some(name) => theme::find(&themes, name)
+            .ok_or_else(|| std::io::Error::other(theme::unknown(&themes, name)))?,
+        None => 0,
+    };
+
+    if let Some(pattern) = &args.pattern {
+        let at = args.at.unwrap_or(origin);
+        place_pattern(&mut game, pattern, at, args.count, args.spacing)
+            .map_err(std::io::Error::other)?;
+    }
+
+    let recorder = match &args.record {
+        Some(path) => Some(Recorder::create(path, &game)?),
+        None => None,
+    };
+    let stats = match &args.stats {
+        Some(path) => Some(StatsLog::create(path, &game)?),
+        None => None,
+    };
+
+    let rng_seed = args.rng_seed.unwrap_or_else(rand::random);
+
+    let mut terminal = setup(mouse)?;
 
     let mut state = State {
-        // a cell's char width is 2 chars
-        game: Grid::new(width / 2, height / 2),
-        // place the cursor at the center of the screen
-        origin: (width / 4, height / 2 - (height / 15)),
+        reports_key_release: enable_key_release_events()?,
+        game,
+        fixed_size,
+        crop: args.crop,
+        confirm_overlap: args.confirm_overlap,
+        speed: match args.fps {
+            Some(0) => Speed::Unlimited,
+            Some(fps) => Speed::Rate(fps),
+            None => SPEED_PRESETS[4],
+        },
+        play: match args.autoplay || playback.is_some() {
+            true => PlayState::Playing,
+            false => PlayState::Paused,
+        },
+        recorder,
+        playback,
+        stats,
+        escapes: EscapeTracker::new(args.remove_escaped),
+        rng: StdRng::seed_from_u64(rng_seed),
+        rng_seed,
+        #[cfg(feature = "gamepad")]
+        gamepad: Gamepad::new(),
+        origin,
+        message: args.golly.as_ref().map(|_| {
+            format!(
+                "Added {} patterns from Golly's collection, Ctrl+Y to browse them",
+                user_seeds.len() - saved_seeds - pattern_seeds
+            )
+        })
+        .or_else(|| {
+            (!chosen && !capabilities.wide_emoji).then(|| {
+                String::from("Emoji look narrow here, so cells are half blocks; --renderer blocks for emoji")
+            })
+        }),
+        user_seeds,
+        saved_seeds,
+        pattern_seeds,
+        patterns,
+        keymap,
+        themes,
+        theme,
+        renderer,
+        cell_width: args.cell_width,
         ..Default::default()
     };
 
     loop {
+        update(&mut state);
+        relayout(&mut terminal, &mut state)?;
+        state.last_frame = Instant::now();
+        state.meter.frame();
         draw(&mut terminal, &mut state)?;
         let ExitSignal(should_exit) = handle_input(&mut state)?;
+        record(&mut state);
 
         if should_exit {
             break;
         }
     }
 
-    teardown()
+    let finished = state.recorder.take().map_or(Ok(()), Recorder::finish);
+    let logged = state.stats.take().map_or(Ok(()), StatsLog::finish);
+    teardown(state.reports_key_release)?;
+    finished.and(logged)
+}
+
+/// Stamps copies of a pattern file, or of a built-in seed given by name, in a row.
+fn place_pattern(
+    game: &mut Grid,
+    name: &str,
+    at: Cell,
+    count: usize,
+    spacing: Option<Cell>,
+) -> Result<(), String> {
+    let seed: Box<dyn IsSeed> = match Seed::from_name(name) {
+        Some(seed) if !Path::new(name).exists() => Box::new(seed),
+        _ => {
+            let pattern = Pattern::load_or_decode(name)?;
+            if let Some(rule) = pattern.parse_rule() {
+                game.rule = rule;
+            }
+            Box::new(pattern)
+        }
+    };
+
+    let spacing = spacing.unwrap_or_else(|| {
+        let cells = seed.cells(at);
+        let left = cells.iter().map(|cell| cell.0).min().unwrap_or_default();
+        let right = cells.iter().map(|cell| cell.0).max().unwrap_or_default();
+        (right - left + 3, 0)
+    });
+
+    for copy in 0..count {
+        let copy = copy as i64;
+        let origin = (at.0 + copy * spacing.0, at.1 + copy * spacing.1);
+        game.seed(&*seed, origin);
+    }
+
+    Ok(())
 }
 
 #[inline]
-fn setup() -> std::io::Result<Terminal<CrosstermBackend<impl std::io::Write>>> {
+pub fn setup(mouse: bool) -> std::io::Result<Terminal<CrosstermBackend<impl std::io::Write>>> {
     stdout().execute(EnterAlternateScreen)?;
+    // pasted text comes as one event instead of keys typed
+    stdout().execute(EnableBracketedPaste)?;
+    if mouse {
+        stdout().execute(EnableMouseCapture)?;
+    }
     enable_raw_mode()?;
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
     terminal.clear()?;
@@ -97,68 +727,718 @@ fn setup() -> std::io::Result<Terminal<CrosstermBackend<impl std::io::Write>>> {
     Ok(terminal)
 }
 
+/// Asks the terminal to report key releases, returning whether it will.
+#[inline]
+fn enable_key_release_events() -> std::io::Result<bool> {
+    if !supports_keyboard_enhancement()? {
+        return Ok(false);
+    }
+
+    stdout().execute(PushKeyboardEnhancementFlags(
+        KeyboardEnhancementFlags::REPORT_EVENT_TYPES,
+    ))?;
+    Ok(true)
+}
+
+/// Advances the simulation as the play state and speed call for.
+#[inline]
+fn update(state: &mut State) {
+    match state.play {
+        // tick for a whole frame's worth of time, rendering only the result
+        _ if state.is_fast_forwarding() => {
+            let deadline = Instant::now() + Duration::from_millis(FRAMETIME_MILIS);
+            while Instant::now() < deadline {
+                if !tick(state) {
+                    break;
+                }
+            }
+        }
+        // catch up on every tick due since the last frame, as many as fit in
+        // one, so speeds past the frame rate tick several times per frame and
+        // unlimited ticks for the whole frame
+        PlayState::Playing => {
+            let interval = state.speed.interval();
+            let deadline = Instant::now() + Duration::from_millis(FRAMETIME_MILIS);
+            while state.last_update.elapsed() >= interval {
+                if !tick(state) {
+                    break;
+                }
+                state.last_update += interval;
+                // too slow to keep up, so drop the ticks still owed
+                if Instant::now() >= deadline {
+                    state.last_update = Instant::now();
+                    break;
+                }
+            }
+        }
+        // the cells being moved are previewed instead
+        PlayState::Paused if matches!(state.mode, Mode::Moving { .. } | Mode::Shifting { .. }) => {}
+        PlayState::Paused => {
+            state.game.preview(
+                selection(&state.pattern, state.seed, state.orientation),
+                state.origin,
+            );
+        }
+    }
+
+    if state.patterns.as_mut().is_some_and(PatternDir::poll) {
+        reload_patterns(state);
+    }
+}
+
+/// Reads the patterns directory again after something in it changed, in
+/// place of the seeds read from it before.
+fn reload_patterns(state: &mut State) {
+    let Some(patterns) = &state.patterns else {
+        return;
+    };
+    let seeds = patterns.load();
+    let (start, count) = (state.saved_seeds, seeds.len());
+    state
+        .user_seeds
+        .splice(start..start + state.pattern_seeds, seeds);
+    state.pattern_seeds = count;
+    if let Mode::Browser { entries, .. } = &mut state.mode {
+        *entries = seed_entries(&library::catalog(&state.user_seeds), &state.user_seeds);
+    }
+    state.message = Some(format!(
+        "Read {} patterns from {} again",
+        count,
+        patterns.path.display()
+    ));
+}
+
+/// Ticks the game once, or steps through the recording being played back,
+/// keeping the new generation for instant replay. Returns false once the game
+/// pauses, at the end of the recording, on a breakpoint, or once the board
+/// settles down.
+fn tick(state: &mut State) -> bool {
+    match &mut state.playback {
+        Some(playback) => {
+            if !playback.step(&mut state.game) {
+                state.play = PlayState::Paused;
+                state.message = Some(String::from("End of recording"));
+                return false;
+            }
+        }
+        None => {
+            state.game.tick();
+            let escaped = state.escapes.check(&mut state.game);
+            if let Some(name) = escaped.last() {
+                state.message = Some(format!(
+                    "{} escaped, {} so far",
+                    name,
+                    state.escapes.total()
+                ));
+            }
+        }
+    }
+    state.meter.ticks += 1;
+    // a new run after clearing the board gets a new graph
+    if state.game.generation == 1 {
+        state.population.clear();
+    }
+    state.population.record(state.game.cells.len());
+    state.replay.record(&state.game.cells);
+    record(state);
+    log_stats(state);
+
+    let message = match watch::hit(&state.breakpoints, &state.game) {
+        Some(breakpoint) => format!("Paused at {}", breakpoint),
+        None => match state.cycles.check(&state.game) {
+            Some(outcome) => outcome.to_string(),
+            None => return true,
+        },
+    };
+    state.play = PlayState::Paused;
+    state.fast_forward = None;
+    state.message = Some(message);
+    false
+}
+
+/// Adds the generation the game just ticked to the stats log, if there is one.
+fn log_stats(state: &mut State) {
+    let Some(stats) = &mut state.stats else {
+        return;
+    };
+    if let Err(error) = stats.record(&state.game) {
+        state.message = Some(format!("Stopped logging stats: {}", error));
+        state.stats = None;
+    }
+}
+
+/// Writes whatever changed on the board to the recording, if there is one.
+fn record(state: &mut State) {
+    let Some(recorder) = &mut state.recorder else {
+        return;
+    };
+    if let Err(error) = recorder.capture(&state.game.cells) {
+        state.message = Some(format!("Stopped recording: {}", error));
+        state.recorder = None;
+    }
+}
+
 #[inline]
+/// Splits the screen into the title, board, status line and instructions.
+fn layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(area)
+}
+
+/// How many cells fit on the board when the terminal is `area` and they're
+/// drawn by `renderer`, `cell_width` columns wide where it's set.
+fn board_size(area: Rect, renderer: Renderer, cell_width: Option<u16>) -> (usize, usize) {
+    let board = layout(area)[1];
+    let (across, down) = renderer.cells();
+    (
+        (board.width / renderer.columns(cell_width)) as usize * across,
+        board.height as usize * down,
+    )
+}
+
+/// The cell under the mouse at `column` and `row` of the terminal, if it's
+/// over the board.
+fn board_cell(
+    game: &Grid,
+    renderer: Renderer,
+    cell_width: Option<u16>,
+    column: u16,
+    row: u16,
+) -> std::io::Result<Option<Cell>> {
+    let (width, height) = terminal::size()?;
+    let board = layout(Rect::new(0, 0, width, height))[1];
+    if !board.intersects(Rect::new(column, row, 1, 1)) {
+        return Ok(None);
+    }
+    // the top left of the cells under it, where a character shows several
+    let (across, down) = renderer.cells();
+    let (x, y) = (
+        ((column - board.x) / renderer.columns(cell_width)) as usize * across,
+        (row - board.y) as usize * down,
+    );
+    Ok(Some(game.to_universe((x, y))))
+}
+
+/// Fits the board to the terminal once it has stopped being resized.
+fn relayout(
+    terminal: &mut Terminal<CrosstermBackend<impl std::io::Write>>,
+    state: &mut State,
+) -> std::io::Result<()> {
+    let settled = state
+        .pending_resize
+        .is_some_and(|resized| resized.elapsed() >= Duration::from_millis(RESIZE_DEBOUNCE_MILIS));
+    if !settled {
+        return Ok(());
+    }
+    state.pending_resize = None;
+    fit_board(state, terminal.size()?);
+
+    Ok(())
+}
+
+/// Sizes the viewport, and the board unless its size was set, to fill the
+/// terminal when it's `area`.
+fn fit_board(state: &mut State, area: Rect) {
+    let (width, height) = board_size(area, state.renderer, state.cell_width);
+    let game = &mut state.game;
+    game.resize_viewport(width, height);
+    if !state.fixed_size {
+        game.resize(width, height);
+    }
+    state.origin = game.clamp_to_viewport(state.origin);
+    game.preview(
+        selection(&state.pattern, state.seed, state.orientation),
+        state.origin,
+    );
+}
+
 fn draw<'t>(
     terminal: &'t mut Terminal<CrosstermBackend<impl std::io::Write>>,
     state: &mut State,
 ) -> std::io::Result<CompletedFrame<'t>> {
+    let fast_forward = state.is_fast_forwarding();
+    let theme = &state.themes[state.theme];
+    let (renderer, cell_width) = (state.renderer, state.cell_width);
     let game = &mut state.game;
 
     terminal.draw(|frame| {
-        let area = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(5),
-                Constraint::Percentage(90),
-                Constraint::Percentage(5),
-            ])
-            .split(frame.size());
+        let area = layout(frame.size());
 
         let block = Block::default()
             .title(TITLE)
             .borders(Borders::BOTTOM)
             .title_style(Style::default().add_modifier(Modifier::BOLD))
             .title_alignment(Alignment::Center)
-            .bg(Color::Blue)
+            .style(theme.title())
             .bold();
 
         frame.render_widget(block, area[0]);
 
-        game.resize(area[1].width as usize, area[1].height as usize);
+        frame.render_widget(
+            Paragraph::new(renderer.lines(theme, &game.shades(), cell_width)),
+            area[1],
+        );
+
+        // each guide line labeled with where it is, along the top and left
+        let board = area[1];
+        let Guides { columns, rows } = game.guides();
+        let (across, down) = renderer.cells();
+        let labels = columns
+            .into_iter()
+            .map(|(column, x)| {
+                let column = (column / across) as u16 * renderer.columns(cell_width);
+                (board.x + column, board.y, x)
+            })
+            .chain(
+                rows.into_iter()
+                    .map(|(row, y)| (board.x, board.y + (row / down) as u16, y)),
+            );
+        for (column, row, at) in labels {
+            let label = at.to_string();
+            let width = (label.len() as u16).min(board.right().saturating_sub(column));
+            frame.render_widget(
+                Paragraph::new(label).style(theme.label()),
+                Rect::new(column, row, width, 1),
+            );
+        }
 
-        match state.play {
-            PlayState::Playing => {
-                let now = Instant::now();
-                let frametime = Duration::from_secs_f64(state.target_framerate as f64 / 1000.0);
-                match frametime.checked_sub(state.last_update.elapsed()) {
-                    None => {
-                        game.tick();
-                        state.last_update = now;
-                    }
-                    Some(_) => {}
-                };
-            }
-            _ => {
-                game.preview(select_seed(state.seed_index), state.origin);
+        if state.show_population {
+            let board = area[1];
+            let height = POPULATION_GRAPH_HEIGHT.min(board.height);
+            let graph = Rect::new(board.x, board.bottom() - height, board.width, height);
+            let populations = state
+                .population
+                .latest(graph.width.saturating_sub(2) as usize);
+            let peak = populations.iter().max().copied().unwrap_or_default();
+            frame.render_widget(Clear, graph);
+            frame.render_widget(
+                Sparkline::default()
+                    .block(
+                        Block::default()
+                            .title(format!(
+                                "Population over {} gens, peak {}",
+                                populations.len(),
+                                peak
+                            ))
+                            .borders(Borders::ALL),
+                    )
+                    .data(&populations)
+                    .green(),
+                graph,
+            );
+        }
+
+        let play = match state.play {
+            _ if fast_forward => "Fast-forward",
+            PlayState::Paused => "Paused",
+            PlayState::Playing => "Playing",
+        };
+
+        let mut stats = format!(
+            "Gen: {} | Pop: {} | Rule: {} | Seed: {} | UPS: {} | FPS: {:.0}",
+            game.generation,
+            game.cells.len(),
+            rule_label(&game.rule),
+            selection(&state.pattern, state.seed, state.orientation)
+                .0
+                .seed
+                .name(),
+            match state.meter.ups {
+                ups if ups >= 100.0 => format!("{:.0}", ups),
+                ups => format!("{:.1}", ups),
+            },
+            state.meter.fps
+        );
+        let escaped = state.escapes.counts();
+        if !escaped.is_empty() {
+            let counts: Vec<String> = escaped
+                .iter()
+                .map(|(name, count)| format!("{} {}", name, count))
+                .collect();
+            stats.push_str(&format!(" | Escaped: {}", counts.join(", ")));
+        }
+        frame.render_widget(
+            Paragraph::new(stats)
+                .style(theme.stats())
+                .alignment(Alignment::Center),
+            area[2],
+        );
+
+        let mut status = format!("{} | Speed: {}", play, state.speed);
+        if let (PlayState::Playing, Speed::Every(_)) = (&state.play, state.speed) {
+            let remaining = state
+                .speed
+                .interval()
+                .saturating_sub(state.last_update.elapsed());
+            status.push_str(&format!(" | Next in {:.1}s", remaining.as_secs_f64()));
+        }
+        if state.snap != Snap::Off {
+            status.push_str(&format!(" | Snap: {}", state.snap));
+        }
+        if let Some(playback) = &state.playback {
+            status.push_str(&format!(
+                " | Frame: {}/{}",
+                playback.position(),
+                playback.len()
+            ));
+        }
+        if !state.breakpoints.is_empty() {
+            let breakpoints: Vec<String> =
+                state.breakpoints.iter().map(|b| b.to_string()).collect();
+            status.push_str(&format!(" | Break at: {}", breakpoints.join(", ")));
+        }
+        if game.boundary != Boundary::Open {
+            status.push_str(&format!(" | Edges: {}", game.boundary));
+        }
+        if state.orientation != Orientation::default() {
+            status.push_str(&format!(" | Placing: {}", state.orientation));
+        }
+        if game.symmetry != Symmetry::None {
+            status.push_str(&format!(" | Symmetry: {}", game.symmetry));
+        }
+        if game.rule.colors > 1 {
+            let color = CELL_COLORS[game.color % game.rule.colors];
+            status.push_str(&format!(" | Color: {}", color));
+        }
+        if game.zoom > 1 {
+            status.push_str(&format!(" | Zoom: 1:{}", game.zoom));
+        }
+        status.push_str(&format!(" | Cursor: {},{}", state.origin.0, state.origin.1));
+        if (game.viewport.x, game.viewport.y) != (0, 0) {
+            status.push_str(&format!(" | View: {},{}", game.viewport.x, game.viewport.y));
+        }
+        if game.show_bounds {
+            match game.bounding_box() {
+                Some(bounds) => status.push_str(&format!(
+                    " | Box: {}x{}, {} alive",
+                    bounds.width,
+                    bounds.height,
+                    game.cells.len()
+                )),
+                None => status.push_str(" | Box: empty"),
             }
         }
+        if let (PlayState::Paused, Some(cell)) = (&state.play, state.hovered) {
+            status.push_str(&format!(" | {}", inspect(game, cell)));
+        }
+        if let Some(message) = &state.message {
+            status.push_str(&format!(" | {}", message));
+        }
 
-        frame.render_widget(Paragraph::new(format!("{}", game)).white(), area[1]);
+        frame.render_widget(
+            Paragraph::new(status)
+                .style(theme.status())
+                .alignment(Alignment::Center),
+            area[3],
+        );
 
         frame.render_widget(
-            Paragraph::new(INSTRUCTIONS)
-                .black()
-                .on_gray()
+            Paragraph::new(HINT)
+                .style(theme.help())
                 .bold()
                 .alignment(Alignment::Center),
-            area[2],
+            area[4],
         );
+
+        match &state.mode {
+            Mode::Normal | Mode::Moving { .. } | Mode::Shifting { .. } => {}
+            Mode::Prompt(prompt @ Prompt::Command, input) => {
+                let lines = command_lines(input);
+                let popup = centered_rect(prompt.width(), lines.len() as u16 + 2, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(lines).block(
+                        Block::default()
+                            .title(prompt.title())
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::Prompt(prompt, input) => {
+                let popup = centered_rect(prompt.width(), 3, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(format!("{}_", input)).block(
+                        Block::default()
+                            .title(prompt.title())
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::RuleEditor { survival, count } => {
+                let popup = centered_rect(52, 6, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(rule_editor_lines(&game.rule, *survival, *count)).block(
+                        Block::default()
+                            .title(format!("Rule: {}", rule_label(&game.rule)))
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::Explorer(candidate) => {
+                let popup = centered_rect(66, 6, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(explorer_lines(candidate))
+                        .alignment(Alignment::Center)
+                        .block(
+                            Block::default()
+                                .title("Explore rules")
+                                .borders(Borders::ALL)
+                                .title_alignment(Alignment::Center),
+                        ),
+                    popup,
+                );
+            }
+            Mode::Bookmarks { selected } => {
+                let lines = bookmark_lines(&state.bookmarks, *selected);
+                let popup = centered_rect(56, lines.len() as u16 + 2, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(lines).block(
+                        Block::default()
+                            .title("Bookmarks")
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::Census(counts) => {
+                let lines = census_lines(counts);
+                let popup = centered_rect(40, lines.len() as u16 + 2, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(lines).block(
+                        Block::default()
+                            .title(format!("Census, gen {}", game.generation))
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::Keys => {
+                let width = frame.size().width.saturating_sub(4).min(120);
+                let lines = keys_lines(width.saturating_sub(2) as usize);
+                let popup = centered_rect(width, lines.len() as u16 + 2, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(lines).block(
+                        Block::default()
+                            .title("Keys")
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::Editor(editor) => {
+                let lines = editor_lines(editor, theme, renderer, cell_width);
+                let popup = centered_rect(76, lines.len() as u16 + 2, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(lines).alignment(Alignment::Center).block(
+                        Block::default()
+                            .title("New seed")
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+            Mode::Browser {
+                entries,
+                query,
+                selected,
+            } => {
+                let board = area[1];
+                let sidebar = Rect::new(
+                    board.right().saturating_sub(SIDEBAR_WIDTH),
+                    board.y,
+                    SIDEBAR_WIDTH.min(board.width),
+                    board.height,
+                );
+                let parts = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([
+                        Constraint::Min(0),
+                        Constraint::Length(SIDEBAR_DETAILS_HEIGHT),
+                    ])
+                    .split(sidebar);
+                let (list, details) = (parts[0], parts[1]);
+                let catalog = library::catalog(&state.user_seeds);
+                let (found, _) = browser::search(entries, query);
+                let (lines, line) = browser_lines(query, &found, *selected);
+                // scrolled to keep the cursor in sight
+                let scroll = (line + 1).saturating_sub(list.height.saturating_sub(2) as usize);
+                frame.render_widget(Clear, sidebar);
+                frame.render_widget(
+                    Paragraph::new(lines).scroll((scroll as u16, 0)).block(
+                        Block::default()
+                            .title("Seeds")
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    list,
+                );
+                let about = found.get(*selected).map_or_else(Vec::new, |entry| {
+                    seed_details(&catalog, &state.user_seeds, entry.pick)
+                });
+                frame.render_widget(
+                    Paragraph::new(about)
+                        .wrap(Wrap { trim: true })
+                        .block(Block::default().borders(Borders::ALL)),
+                    details,
+                );
+            }
+            Mode::Leaderboard { entries, selected } => {
+                let lines = leaderboard_lines(entries, *selected);
+                let popup = centered_rect(56, lines.len() as u16 + 2, frame.size());
+                frame.render_widget(Clear, popup);
+                frame.render_widget(
+                    Paragraph::new(lines).block(
+                        Block::default()
+                            .title("Longest-lived soups")
+                            .borders(Borders::ALL)
+                            .title_alignment(Alignment::Center),
+                    ),
+                    popup,
+                );
+            }
+        }
     })
 }
 
+/// A cell's coordinates, what's in it, how many live neighbors it has and,
+/// if it's alive, for how long, as in `12,-3: alive 5 gens, 2 neighbors`.
+fn inspect(game: &Grid, cell: Cell) -> String {
+    let contents = match cell {
+        _ if game.cells.contains(&cell) => match game.age(&cell) {
+            0 => String::from("just born"),
+            1 => String::from("alive 1 gen"),
+            age => format!("alive {} gens", age),
+        },
+        _ if game.dying.contains_key(&cell) => format!("dying, state {}", game.dying[&cell]),
+        _ if game.rule.wireworld && game.wires.contains(&cell) => String::from("wire"),
+        _ => String::from("dead"),
+    };
+    let neighbors = match game.neighbor_count(&cell) {
+        1 => String::from("1 neighbor"),
+        count => format!("{} neighbors", count),
+    };
+    format!("{},{}: {}, {}", cell.0, cell.1, contents, neighbors)
+}
+
+fn explorer_lines(candidate: &Candidate) -> Vec<Line<'static>> {
+    let score = &candidate.score;
+    let outcome = match score.exploded {
+        true => format!("Exploded after {} gens", score.lifetime),
+        false => format!(
+            "Active for {} gens, growth x{:.1}",
+            score.lifetime, score.growth
+        ),
+    };
+
+    vec![
+        Line::from(Span::raw(candidate.rule.to_string()).bold()),
+        Line::from(format!(
+            "{} | Score {:.2}",
+            outcome,
+            score.interestingness()
+        )),
+        Line::from(""),
+        Line::from(
+            Span::raw("K (keep) | N (next) | R (any rule) | Enter (try) | Esc (close)").dim(),
+        ),
+    ]
+}
+
+/// Draws each birth and survival count as a checkbox, highlighting the cursor.
+fn rule_editor_lines(rule: &Rule, survival: bool, cursor: usize) -> Vec<Line<'static>> {
+    if !rule.is_life_like() {
+        return vec![
+            Line::from(""),
+            Line::from("This rule has no counts to toggle"),
+            Line::from(""),
+            Line::from(Span::raw("Tab (next preset) | Esc (close)").dim()),
+        ];
+    }
+
+    let header = (0..9)
+        .map(|count| format!(" {} ", count))
+        .collect::<String>();
+    let row = |label: &'static str, counts: &[bool; 9], selected: bool| {
+        let mut spans = vec![Span::raw(label)];
+        for (count, enabled) in counts.iter().enumerate() {
+            let checkbox = Span::raw(if *enabled { "[x]" } else { "[ ]" });
+            spans.push(match selected && count == cursor {
+                true => checkbox.reversed(),
+                false => checkbox,
+            });
+        }
+        Line::from(spans)
+    };
+
+    vec![
+        Line::from(format!("   {}", header)),
+        row("B: ", &rule.birth, !survival),
+        row("S: ", &rule.survival, survival),
+        Line::from(Span::raw("Space (toggle) | Tab (next preset) | Esc (close)").dim()),
+    ]
+}
+
+/// The rulestring, followed by its name for presets, e.g. `B36/S23 (HighLife)`.
+fn rule_label(rule: &Rule) -> String {
+    match rule.name() {
+        Some(name) if !name.eq_ignore_ascii_case(&rule.to_string()) => {
+            format!("{} ({})", rule, name)
+        }
+        _ => rule.to_string(),
+    }
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
 #[inline]
 fn handle_input(state: &mut State) -> std::io::Result<ExitSignal> {
-    if event::poll(std::time::Duration::from_millis(FRAMETIME_MILIS))? {
+    // wait for input until the next frame is due
+    let timeout = Duration::from_millis(FRAMETIME_MILIS).saturating_sub(state.last_frame.elapsed());
+
+    #[cfg(feature = "gamepad")]
+    if let Some(gamepad) = &mut state.gamepad {
+        for key in gamepad.poll() {
+            if let ExitSignal(true) = handle_key(state, key) {
+                return Ok(ExitSignal(true));
+            }
+        }
+    }
+
+    if event::poll(timeout)? {
         let game = &mut state.game;
         match event::read()? {
             //
@@ -169,100 +1449,79 @@ fn handle_input(state: &mut State) -> std::io::Result<ExitSignal> {
                 column,
                 modifiers: _,
             }) => match kind {
+                // the mouse would place seeds over the cells being moved
+                _ if matches!(state.mode, Mode::Moving { .. } | Mode::Shifting { .. }) => {}
+                event::MouseEventKind::Down(event::MouseButton::Right) => {
+                    state.select_anchor =
+                        board_cell(game, state.renderer, state.cell_width, column, row)?;
+                    game.selection = state
+                        .select_anchor
+                        .and_then(|cell| BoundingBox::of(&[cell]));
+                }
+                event::MouseEventKind::Drag(event::MouseButton::Right) => {
+                    if let (Some(anchor), Some(cell)) = (
+                        state.select_anchor,
+                        board_cell(game, state.renderer, state.cell_width, column, row)?,
+                    ) {
+                        game.selection = BoundingBox::of(&[anchor, cell]);
+                    }
+                }
+                event::MouseEventKind::Up(event::MouseButton::Right) => {
+                    state.select_anchor = None;
+                }
                 event::MouseEventKind::Down(_) => {
-                    game.seed(
-                        select_seed(state.seed_index),
-                        (row as usize, column as usize),
-                    );
+                    if let Some(cell) =
+                        board_cell(game, state.renderer, state.cell_width, column, row)?
+                    {
+                        place_seed(state, cell);
+                    }
                 }
                 event::MouseEventKind::ScrollDown => {
-                    next_seed(state);
+                    next_seed(state, false);
                 }
                 event::MouseEventKind::ScrollUp => {
-                    previous_seed(state);
+                    next_seed(state, true);
                 }
                 event::MouseEventKind::Moved => {
-                    game.preview(
-                        select_seed(state.seed_index),
-                        (row as usize, column as usize),
-                    );
+                    state.hovered =
+                        board_cell(game, state.renderer, state.cell_width, column, row)?;
+                    if let Some(cell) = state.hovered {
+                        game.preview(
+                            selection(&state.pattern, state.seed, state.orientation),
+                            cell,
+                        );
+                    }
                 }
                 _ => {}
             },
             //
             //
-            event::Event::Key(KeyEvent {
-                code,
-                modifiers,
-                kind,
-                state: _,
-            }) => {
-                let speed = match modifiers {
-                    event::KeyModifiers::SHIFT => 5,
-                    _ => 1,
-                };
-
-                if kind == event::KeyEventKind::Press {
-                    match code {
-                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
-                            return Ok(ExitSignal(true))
-                        }
-                        KeyCode::Pause | KeyCode::Char('p') | KeyCode::Char('P') => {
-                            match state.play {
-                                PlayState::Paused => {
-                                    state.play = PlayState::Playing;
-                                }
-                                PlayState::Playing => {
-                                    state.play = PlayState::Paused;
-                                    game.preview(select_seed(state.seed_index), state.origin);
-                                }
-                            }
-                        }
-                        KeyCode::Insert | KeyCode::Char(' ') => {
-                            game.seed(select_seed(state.seed_index), state.origin);
-                        }
-                        KeyCode::Left => {
-                            state.origin.0 = state.origin.0.saturating_sub(speed);
-                            game.preview(select_seed(state.seed_index), state.origin);
-                        }
-                        KeyCode::Right => {
-                            if state.origin.0 + speed <= game.width {
-                                state.origin.0 += speed;
-                            }
-                            game.preview(select_seed(state.seed_index), state.origin);
-                        }
-                        KeyCode::Up => {
-                            state.origin.1 = state.origin.1.saturating_sub(speed);
-                            game.preview(select_seed(state.seed_index), state.origin);
-                        }
-                        KeyCode::Down => {
-                            if state.origin.1 + speed <= game.height {
-                                state.origin.1 += speed;
-                            }
-                            game.preview(select_seed(state.seed_index), state.origin);
-                        }
-                        KeyCode::Delete => {
-                            game.clear();
-                        }
-                        KeyCode::Enter => match state.play {
-                            PlayState::Paused => {
-                                game.tick();
-                            }
-                            PlayState::Playing => {
-                                state.play = PlayState::Paused;
-                                game.preview(select_seed(state.seed_index), state.origin);
-                            }
-                        },
-                        KeyCode::Char(ch) => {
-                            if ch.is_digit(16) {
-                                state.seed_index = ch.to_digit(16).unwrap() as u8;
-                            }
-                            game.preview(select_seed(state.seed_index), state.origin);
-                        }
-                        _ => {}
+            event::Event::Key(mut key) => {
+                // other modes take keys for what they are, like text typed
+                if matches!(state.mode, Mode::Normal) {
+                    match state.keymap.translate(key.code, key.modifiers) {
+                        Some(bound) => (key.code, key.modifiers) = (bound.code, bound.modifiers),
+                        None => return Ok(ExitSignal(false)),
                     }
                 }
+                let signal = handle_key(state, key);
+                if let Some(anchor) = state.select_anchor {
+                    state.game.selection = BoundingBox::of(&[anchor, state.origin]);
+                }
+                return Ok(signal);
             }
+            event::Event::Paste(text) => match &mut state.mode {
+                Mode::Prompt(prompt, input) => {
+                    input.extend(text.chars().filter(|ch| prompt.accepts(*ch)));
+                }
+                // terminals send the line breaks as carriage returns
+                Mode::Normal => {
+                    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+                    state.message = Some(paste(state, &text));
+                }
+                _ => {}
+            },
+            event::Event::Resize(..) => state.pending_resize = Some(Instant::now()),
             _ => {}
         }
     }
@@ -270,52 +1529,1957 @@ fn handle_input(state: &mut State) -> std::io::Result<ExitSignal> {
     Ok(ExitSignal(false))
 }
 
-const MAX_SEEDS: u8 = 14;
+fn handle_key(state: &mut State, key: KeyEvent) -> ExitSignal {
+    let KeyEvent {
+        code,
+        modifiers,
+        kind,
+        state: _,
+    } = key;
+    let game = &mut state.game;
 
-fn next_seed(state: &mut State) {
-    match state.seed_index {
-        MAX_SEEDS => state.seed_index = 0,
-        _ => state.seed_index += 1,
-    }
-}
+    // zoomed out, each step covers what one character shows
+    let times = match modifiers {
+        event::KeyModifiers::SHIFT => 5,
+        _ => 1,
+    } * game.zoom;
+    let (step_x, step_y) =
+        state
+            .snap
+            .steps(&selection(&state.pattern, state.seed, state.orientation));
 
-fn previous_seed(state: &mut State) {
-    match state.seed_index {
-        0 => state.seed_index = MAX_SEEDS,
-        _ => state.seed_index -= 1,
+    if code == KeyCode::Char('>') && matches!(state.mode, Mode::Normal) {
+        state.fast_forward = match kind {
+            event::KeyEventKind::Release => None,
+            _ => Some(Instant::now()),
+        };
+        return ExitSignal(false);
     }
-}
-
-fn select_seed(index: u8) -> Seed {
-    match index {
-        // Still lifes are patterns that do not change from one generation to the next.
-        1 => Seed::Still(Still::Block),
-        2 => Seed::Still(Still::Beehive),
-        3 => Seed::Still(Still::Loaf),
-        4 => Seed::Still(Still::Boat),
-        5 => Seed::Still(Still::Tub),
-
-        // Oscillators are patterns that return to their original configuration
-        6 => Seed::Oscillator(Oscillator::Blinker),
-        7 => Seed::Oscillator(Oscillator::Toad),
-        8 => Seed::Oscillator(Oscillator::Beacon),
-        9 => Seed::Oscillator(Oscillator::Pulsar),
-        10 => Seed::Oscillator(Oscillator::PentaDecathlon),
-
-        // Spaceships are patterns that translate themselves across the grid.
-        11 => Seed::Spaceship(Spaceship::Glider),
-        12 => Seed::Spaceship(Spaceship::LwSpaceship),
-        13 => Seed::Spaceship(Spaceship::MwSpaceship),
-        14 => Seed::Spaceship(Spaceship::HwSpaceship),
 
-        // A single cell.
-        _ => Seed::Cell((0, 0)),
+    // with key release reporting on, held keys arrive as repeats
+    if kind != event::KeyEventKind::Release {
+        match state.mode {
+            Mode::Normal => {}
+            Mode::Prompt(..) => {
+                handle_prompt_input(state, code);
+                return ExitSignal(false);
+            }
+            Mode::RuleEditor { .. } => {
+                handle_rule_editor_input(state, code);
+                return ExitSignal(false);
+            }
+            Mode::Explorer(_) => {
+                handle_explorer_input(state, code);
+                return ExitSignal(false);
+            }
+            Mode::Bookmarks { .. } => {
+                handle_bookmarks_input(state, code);
+                return ExitSignal(false);
+            }
+            // any key closes them
+            Mode::Census(_) | Mode::Keys => {
+                state.mode = Mode::Normal;
+                return ExitSignal(false);
+            }
+            Mode::Leaderboard { .. } => {
+                handle_leaderboard_input(state, code);
+                return ExitSignal(false);
+            }
+            Mode::Moving { .. } => {
+                handle_move_input(state, code, modifiers);
+                return ExitSignal(false);
+            }
+            Mode::Editor(_) => {
+                handle_editor_input(state, code);
+                return ExitSignal(false);
+            }
+            Mode::Shifting { .. } => {
+                handle_shift_input(state, code, modifiers);
+                return ExitSignal(false);
+            }
+            Mode::Browser { .. } => {
+                handle_browser_input(state, code, modifiers);
+                return ExitSignal(false);
+            }
+        }
+        state.message = None;
+        if !matches!(code, KeyCode::Insert | KeyCode::Char(' ')) {
+            state.unconfirmed = None;
+        }
+
+        match code {
+            KeyCode::Esc if game.selection.is_some() => {
+                game.selection = None;
+                state.select_anchor = None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => return ExitSignal(true),
+            KeyCode::Char(' ') if modifiers == event::KeyModifiers::CONTROL => {
+                match state.select_anchor.take() {
+                    Some(_) => {
+                        state.message = Some(String::from(
+                            "Selected, Ctrl+C (copy) | Ctrl+X (cut) | Esc (deselect)",
+                        ))
+                    }
+                    None => {
+                        state.select_anchor = Some(state.origin);
+                        game.selection = BoundingBox::of(&[state.origin]);
+                        state.message = Some(String::from(
+                            "Selecting, move the cursor to the other corner and press Ctrl+Space",
+                        ));
+                    }
+                }
+            }
+            KeyCode::Char('c') if modifiers == event::KeyModifiers::CONTROL => {
+                state.message = Some(copy_selection(state, false));
+            }
+            KeyCode::Char('x') if modifiers == event::KeyModifiers::CONTROL => {
+                state.message = Some(copy_selection(state, true));
+            }
+            KeyCode::Char('d') if modifiers == event::KeyModifiers::CONTROL => {
+                match game.selection {
+                    Some(from) => {
+                        state.history.record(game);
+                        state.play = PlayState::Paused;
+                        state.select_anchor = None;
+                        let cells: Vec<Cell> = game
+                            .cells
+                            .iter()
+                            .filter(|cell| from.contains(cell))
+                            .copied()
+                            .collect();
+                        for cell in &cells {
+                            game.remove_cell(cell);
+                        }
+                        game.preview = cells.iter().copied().collect();
+                        state.message = Some(moving_message((0, 0)));
+                        state.mode = Mode::Moving {
+                            cells,
+                            from,
+                            offset: (0, 0),
+                        };
+                    }
+                    // with nothing selected, the whole board moves
+                    None => {
+                        state.history.record(game);
+                        game.preview.clear();
+                        state.message = Some(shifting_message((0, 0)));
+                        state.mode = Mode::Shifting { offset: (0, 0) };
+                    }
+                }
+            }
+            KeyCode::Char('b') if modifiers == event::KeyModifiers::CONTROL => {
+                state.message = Some(match &state.buffer {
+                    Some(buffer) => {
+                        state.pattern = Some(buffer.clone());
+                        state.pasted_rule = None;
+                        game.preview(
+                            selection(&state.pattern, state.seed, state.orientation),
+                            state.origin,
+                        );
+                        format!(
+                            "Stamping {}x{}, Space to place",
+                            buffer.width, buffer.height
+                        )
+                    }
+                    None => String::from("Nothing copied yet, select and press Ctrl+C"),
+                });
+            }
+            // with Alt, the whole board instead of the seed
+            KeyCode::Char(ch @ (',' | '.' | '/' | ';'))
+                if modifiers == event::KeyModifiers::ALT =>
+            {
+                let orientation = match ch {
+                    ',' => Orientation::default().turned(3),
+                    '.' => Orientation::default().turned(1),
+                    '/' => Orientation::default().flipped(true),
+                    _ => Orientation::default().flipped(false),
+                };
+                state.history.record(game);
+                game.orient(orientation);
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+                state.message = Some(format!("Board {}", orientation));
+            }
+            KeyCode::Char(ch @ (',' | '.' | '/' | ';')) => {
+                state.orientation = match ch {
+                    ',' => state.orientation.turned(3),
+                    '.' => state.orientation.turned(1),
+                    '/' => state.orientation.flipped(true),
+                    _ => state.orientation.flipped(false),
+                };
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Char('v') if modifiers == event::KeyModifiers::CONTROL => {
+                state.message = Some(match clipboard::paste() {
+                    Ok(text) => paste(state, &text),
+                    Err(error) => format!("Could not paste: {}", error),
+                });
+            }
+            KeyCode::Char('p') if modifiers == event::KeyModifiers::CONTROL => {
+                state.mode = Mode::Prompt(Prompt::Breakpoint, String::new());
+            }
+            KeyCode::Pause | KeyCode::Char('p') | KeyCode::Char('P') => match state.play {
+                PlayState::Paused => {
+                    state.play = PlayState::Playing;
+                    state.last_update = Instant::now();
+                }
+                PlayState::Playing => {
+                    state.play = PlayState::Paused;
+                    game.preview(
+                        selection(&state.pattern, state.seed, state.orientation),
+                        state.origin,
+                    );
+                }
+            },
+            KeyCode::Insert | KeyCode::Char(' ') => place_seed(state, state.origin),
+            // the cursor moves along, as when panning
+            KeyCode::Home => match game.bounding_box() {
+                Some(bounds) => {
+                    let (x, y) = (game.viewport.x, game.viewport.y);
+                    game.look_at(bounds.center());
+                    state.origin = (
+                        state.origin.0 + game.viewport.x - x,
+                        state.origin.1 + game.viewport.y - y,
+                    );
+                    game.preview(
+                        selection(&state.pattern, state.seed, state.orientation),
+                        state.origin,
+                    );
+                }
+                None => state.message = Some(String::from("Nothing on the board to center on")),
+            },
+            KeyCode::End => match game.zoom_to_fit() {
+                true => {
+                    state.origin = game.clamp_to_viewport(state.origin);
+                    game.preview(
+                        selection(&state.pattern, state.seed, state.orientation),
+                        state.origin,
+                    );
+                }
+                false => state.message = Some(String::from("Nothing on the board to fit")),
+            },
+            KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down
+                if modifiers.contains(event::KeyModifiers::CONTROL) =>
+            {
+                let distance = PAN_CELLS * game.zoom as i64;
+                let (dx, dy) = match code {
+                    KeyCode::Left => (-distance, 0),
+                    KeyCode::Right => (distance, 0),
+                    KeyCode::Up => (0, -distance),
+                    _ => (0, distance),
+                };
+                // the cursor moves along, staying at the same spot on screen
+                game.pan(dx, dy);
+                state.origin = (state.origin.0 + dx, state.origin.1 + dy);
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Left => {
+                state.origin.0 = cursor::back(state.origin.0, step_x, times).max(game.viewport.x);
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Right => {
+                let x = cursor::forward(state.origin.0, step_x, times);
+                if x <= game.viewport.x + game.viewport.width as i64 {
+                    state.origin.0 = x;
+                }
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Up => {
+                state.origin.1 = cursor::back(state.origin.1, step_y, times).max(game.viewport.y);
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Down => {
+                let y = cursor::forward(state.origin.1, step_y, times);
+                if y <= game.viewport.y + game.viewport.height as i64 {
+                    state.origin.1 = y;
+                }
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Delete => {
+                state.history.record(game);
+                game.clear();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                let level = ZOOM_LEVELS.iter().position(|&zoom| zoom >= game.zoom);
+                game.set_zoom(ZOOM_LEVELS[level.unwrap_or_default().saturating_sub(1)]);
+                state.origin = game.clamp_to_viewport(state.origin);
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Char('-') => {
+                let level = ZOOM_LEVELS.iter().rposition(|&zoom| zoom <= game.zoom);
+                let level = (level.unwrap_or_default() + 1).min(ZOOM_LEVELS.len() - 1);
+                game.set_zoom(ZOOM_LEVELS[level]);
+                state.origin = game.clamp_to_viewport(state.origin);
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Char('[') => state.speed = step_speed(state.speed, false),
+            KeyCode::Char(']') => state.speed = step_speed(state.speed, true),
+            KeyCode::Char(ch @ '1'..='9') if modifiers == event::KeyModifiers::ALT => {
+                state.speed = SPEED_PRESETS[ch as usize - '1' as usize];
+            }
+            KeyCode::Char('0') if modifiers == event::KeyModifiers::ALT => {
+                state.speed = next_slow_motion(state.speed);
+            }
+            KeyCode::Char('e') if modifiers == event::KeyModifiers::CONTROL => {
+                state.mode = Mode::Prompt(Prompt::Export, String::new());
+            }
+            KeyCode::Char('s') if modifiers == event::KeyModifiers::CONTROL => {
+                state.mode = Mode::Prompt(Prompt::Save, String::new());
+            }
+            KeyCode::Char('o') if modifiers == event::KeyModifiers::CONTROL => {
+                state.mode = Mode::Prompt(Prompt::Load, String::new());
+            }
+            KeyCode::Char('r') if modifiers == event::KeyModifiers::CONTROL => {
+                state.message = match state.history.redo(game) {
+                    true => None,
+                    false => Some(String::from("Nothing to redo")),
+                };
+            }
+            KeyCode::Char('k') if modifiers == event::KeyModifiers::CONTROL => {
+                state.history.record(game);
+                state.message = Some(match game.crop(state.crop.unwrap_or_default()) {
+                    Some(board) => {
+                        state.fixed_size = true;
+                        state.origin = game.clamp_to_viewport(state.origin);
+                        format!("Cropped the board to {}x{}", board.width, board.height)
+                    }
+                    None => String::from("Nothing on the board to crop to"),
+                });
+            }
+            KeyCode::Char('k') | KeyCode::Char('K') => {
+                state.mode = Mode::Prompt(Prompt::Bookmark, String::new());
+            }
+            KeyCode::Char('j') | KeyCode::Char('J') => {
+                state.mode = Mode::Bookmarks { selected: 0 };
+            }
+            KeyCode::Char('u') | KeyCode::Char('U') => {
+                state.message = match state.history.undo(game) {
+                    true => None,
+                    false => Some(String::from("Nothing to undo")),
+                };
+            }
+            KeyCode::Char('h') | KeyCode::Char('H') => {
+                // off, then births, then births and deaths
+                let (births, deaths) = match (game.highlight_births, game.highlight_deaths) {
+                    (false, _) => (true, false),
+                    (true, false) => (true, true),
+                    (true, true) => (false, false),
+                };
+                game.highlight_births = births;
+                game.highlight_deaths = deaths;
+                state.message = Some(String::from(match (births, deaths) {
+                    (true, false) => "Highlighting births",
+                    (true, true) => "Highlighting births and deaths",
+                    _ => "Highlighting nothing",
+                }));
+            }
+            KeyCode::Char('o') | KeyCode::Char('O') => {
+                // off, then onion skin, then trails
+                (game.onion_skin, game.show_trails) = match (game.onion_skin, game.show_trails) {
+                    (false, false) => (true, false),
+                    (true, _) => (false, true),
+                    (false, true) => (false, false),
+                };
+                game.trails.clear();
+            }
+            KeyCode::Char('l') if modifiers == event::KeyModifiers::CONTROL => {
+                match Leaderboard::path().and_then(Leaderboard::load) {
+                    Ok(leaderboard) => {
+                        state.mode = Mode::Leaderboard {
+                            entries: leaderboard.entries().to_vec(),
+                            selected: 0,
+                        }
+                    }
+                    Err(error) => {
+                        state.message = Some(format!("Could not load the leaderboard: {}", error))
+                    }
+                }
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') => {
+                state.mode = Mode::Prompt(Prompt::Pattern, String::new());
+            }
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                state.snap = state.snap.next();
+            }
+            KeyCode::Char('a') if modifiers == event::KeyModifiers::CONTROL => {
+                state.mode = Mode::Prompt(Prompt::Scatter, String::new());
+            }
+            KeyCode::Char(':') => {
+                state.mode = Mode::Prompt(Prompt::Command, String::new());
+            }
+            // starting from the live cells selected, if they fit
+            KeyCode::Char('w') if modifiers == event::KeyModifiers::CONTROL => {
+                let cells = match game.selection {
+                    Some(bounds)
+                        if bounds.width as i64 <= EDITOR_SIZE
+                            && bounds.height as i64 <= EDITOR_SIZE =>
+                    {
+                        bounds
+                            .cells()
+                            .filter(|cell| game.cells.contains(cell))
+                            .map(|(x, y)| (x - bounds.x, y - bounds.y))
+                            .collect()
+                    }
+                    _ => HashSet::new(),
+                };
+                state.mode = Mode::Editor(Editor {
+                    cells,
+                    ..Default::default()
+                });
+            }
+            KeyCode::Char('w') | KeyCode::Char('W') => {
+                game.boundary = game.boundary.next();
+            }
+            KeyCode::Char('\\') => {
+                game.symmetry = game.symmetry.next();
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            // the axes cross at the cursor, or back in the middle of the board
+            KeyCode::Char('|') => {
+                let cursor = (state.origin.0 * 2, state.origin.1 * 2);
+                game.axes = match game.axes {
+                    Some(axes) if axes == cursor => None,
+                    _ => Some(cursor),
+                };
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+                state.message = Some(match game.axes {
+                    Some(_) => format!("Mirroring across {},{}", state.origin.0, state.origin.1),
+                    None => String::from("Mirroring across the middle of the board"),
+                });
+            }
+            KeyCode::Char('t') if modifiers == event::KeyModifiers::CONTROL => {
+                match state.stats.take() {
+                    Some(stats) => {
+                        state.message = Some(match stats.finish() {
+                            Ok(()) => String::from("Stopped logging stats"),
+                            Err(error) => format!("Could not finish stats log: {}", error),
+                        });
+                    }
+                    None => state.mode = Mode::Prompt(Prompt::Stats, String::new()),
+                }
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') if game.rule.colors > 1 => {
+                game.color = (game.color + 1) % game.rule.colors;
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => switch_theme(state, ""),
+            KeyCode::Char('b') | KeyCode::Char('B') => {
+                if let Err(error) = switch_renderer(state, "") {
+                    state.message = Some(error.to_string());
+                }
+            }
+            KeyCode::Char('m') | KeyCode::Char('M') => {
+                game.show_bounds = !game.show_bounds;
+            }
+            KeyCode::Char('#') => {
+                game.show_guides = !game.show_guides;
+            }
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                state.mode = Mode::RuleEditor {
+                    survival: false,
+                    count: 0,
+                };
+            }
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                state.mode = Mode::Explorer(explorer::search(&mut state.rng));
+            }
+            KeyCode::Char('i') | KeyCode::Char('I') => {
+                state.message = Some(save_instant_replay(state));
+            }
+            KeyCode::Char('g') if modifiers == event::KeyModifiers::CONTROL => {
+                state.show_population = !state.show_population;
+            }
+            KeyCode::Char('g') | KeyCode::Char('G') => {
+                state.mode = Mode::Prompt(Prompt::Goto, String::new());
+            }
+            KeyCode::Char('f') if modifiers == event::KeyModifiers::CONTROL => {
+                state.mode = Mode::Prompt(Prompt::Advance, String::new());
+            }
+            // the board, or the selected seed when there's nothing on it
+            KeyCode::Char('?') => {
+                let (name, analysis) = match game.cells.is_empty() {
+                    false => ("Board", cycle::analyze(game)),
+                    true => {
+                        let mut seed = Grid::new(game.width, game.height);
+                        seed.rule = game.rule;
+                        seed.seed(
+                            selection(&state.pattern, state.seed, state.orientation),
+                            state.origin,
+                        );
+                        ("Seed", cycle::analyze(&seed))
+                    }
+                };
+                state.message = Some(format!("{}: {}", name, analysis));
+            }
+            KeyCode::Char('n') if modifiers == event::KeyModifiers::CONTROL => {
+                game.show_neighbors = !game.show_neighbors;
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                state.mode = Mode::Census(census::census(&game.cells));
+            }
+            KeyCode::F(1) => state.mode = Mode::Keys,
+            // with the cursor on the seed picked
+            KeyCode::Char('y') if modifiers == event::KeyModifiers::CONTROL => {
+                let catalog = library::catalog(&state.user_seeds);
+                let entries = seed_entries(&catalog, &state.user_seeds);
+                let (found, _) = browser::search(&entries, "");
+                let selected = found
+                    .iter()
+                    .position(|entry| entry.pick == state.pick)
+                    .unwrap_or_default();
+                state.mode = Mode::Browser {
+                    entries,
+                    query: String::new(),
+                    selected,
+                };
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                game.show_age = !game.show_age;
+            }
+            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                state.escapes.remove = !state.escapes.remove;
+                state.message = Some(String::from(match state.escapes.remove {
+                    true => "Removing escaped spaceships",
+                    false => "Keeping escaped spaceships",
+                }));
+            }
+            KeyCode::Char('v') | KeyCode::Char('V') => {
+                let breakpoint = Breakpoint::Cell(state.origin);
+                match state.breakpoints.contains(&breakpoint) {
+                    true => state.breakpoints.retain(|b| *b != breakpoint),
+                    false => state.breakpoints.push(breakpoint),
+                }
+                game.watched = watch::watched(&state.breakpoints);
+            }
+            KeyCode::Enter => match state.play {
+                PlayState::Paused => {
+                    state.history.record(game);
+                    tick(state);
+                }
+                PlayState::Playing => {
+                    state.play = PlayState::Paused;
+                    game.preview(
+                        selection(&state.pattern, state.seed, state.orientation),
+                        state.origin,
+                    );
+                }
+            },
+            KeyCode::Char('0') => {
+                choose_seed(state, Pick::default());
+                state.game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            // the first nine seeds of the category
+            KeyCode::Char(ch @ '1'..='9') => {
+                let pick = Pick {
+                    seed: ch as usize - '1' as usize,
+                    ..state.pick
+                };
+                if pick.get(&library::catalog(&state.user_seeds)).is_some() {
+                    choose_seed(state, pick);
+                    describe_pick(state);
+                }
+                state.game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Char(_) => {
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::PageUp | KeyCode::PageDown => {
+                match code {
+                    KeyCode::Tab => next_category(state, false),
+                    KeyCode::BackTab => next_category(state, true),
+                    KeyCode::PageDown => next_seed(state, false),
+                    _ => next_seed(state, true),
+                }
+                state.game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    ExitSignal(false)
+}
+
+fn handle_prompt_input(state: &mut State, code: KeyCode) {
+    let Mode::Prompt(prompt, input) = &mut state.mode else {
+        return;
+    };
+
+    match code {
+        KeyCode::Esc => state.mode = Mode::Normal,
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(ch) if prompt.accepts(ch) => {
+            input.push(ch);
+        }
+        KeyCode::Tab if matches!(prompt, Prompt::Command) => {
+            if let Some(completed) = palette::complete(input) {
+                *input = completed;
+            }
+        }
+        KeyCode::Enter => {
+            let prompt = *prompt;
+            let input = std::mem::take(input);
+            state.mode = Mode::Normal;
+            submit_prompt(state, prompt, input.trim());
+        }
+        _ => {}
+    }
+}
+
+fn submit_prompt(state: &mut State, prompt: Prompt, input: &str) {
+    match prompt {
+        Prompt::Goto => {
+            if let Some(cell) = args::parse_coordinates(input) {
+                let game = &mut state.game;
+                // the plane goes on past the view, which moves to the cell
+                game.look_at(cell);
+                state.origin = cell;
+                game.preview(
+                    selection(&state.pattern, state.seed, state.orientation),
+                    state.origin,
+                );
+            }
+        }
+        Prompt::Advance => {
+            if let Ok(generations) = input.parse::<usize>() {
+                state.history.record(&state.game);
+                let started = Instant::now();
+                // tick without drawing, showing only where it ends up, unless
+                // a breakpoint stops it first
+                if (0..generations).all(|_| tick(state)) {
+                    state.message = Some(format!(
+                        "Advanced {} generations in {:.2}s",
+                        generations,
+                        started.elapsed().as_secs_f64()
+                    ));
+                }
+            }
+        }
+        Prompt::Scatter => {
+            let (count, turned) = match input.strip_suffix(['r', 'R']) {
+                Some(count) => (count, true),
+                None => (input, false),
+            };
+            if let Ok(count) = count.parse::<usize>() {
+                state.history.record(&state.game);
+                let seed = selection(&state.pattern, state.seed, state.orientation).0;
+                let orientation = (!turned).then_some(seed.orientation);
+                let size = (state.game.width, state.game.height);
+                for (orientation, origin) in
+                    soup::scatter(&mut state.rng, &seed.seed, count, size, orientation)
+                {
+                    let copy = Oriented {
+                        seed: &seed.seed,
+                        orientation,
+                    };
+                    state.game.seed(copy, origin);
+                }
+                state.message = Some(format!(
+                    "Scattered {} copies of {}",
+                    count,
+                    seed.seed.name()
+                ));
+            }
+        }
+        Prompt::Breakpoint if input.is_empty() => {
+            state.breakpoints.clear();
+            state.game.watched.clear();
+            state.message = Some(String::from("Cleared breakpoints"));
+        }
+        Prompt::Breakpoint => {
+            state.message = Some(match input.parse::<Breakpoint>() {
+                Ok(breakpoint) => {
+                    state.breakpoints.push(breakpoint);
+                    state.game.watched = watch::watched(&state.breakpoints);
+                    format!("Will pause at {}", breakpoint)
+                }
+                Err(error) => error,
+            });
+        }
+        Prompt::Stats => {
+            state.message = Some(match StatsLog::create(input, &state.game) {
+                Ok(stats) => {
+                    state.stats = Some(stats);
+                    format!("Logging stats to {}", input)
+                }
+                Err(error) => format!("Could not log stats to {}: {}", input, error),
+            });
+        }
+        Prompt::Save => {
+            state.message = Some(match Savestate::from_grid(&state.game).save(input) {
+                Ok(()) => format!("Saved to {}", input),
+                Err(error) => format!("Could not save {}: {}", input, error),
+            });
+        }
+        Prompt::Bookmark => {
+            let name = match input {
+                "" => format!("Bookmark {}", state.bookmarks.len() + 1),
+                name => name.to_string(),
+            };
+            state.message = Some(format!("Bookmarked {}", name));
+            state.bookmarks.push((name, Snapshot::of(&state.game)));
+        }
+        Prompt::Export => {
+            state.message = Some(match export::export(&state.game, input, state.crop) {
+                Ok(bounds) => format!(
+                    "Exported {}x{} cells to {}",
+                    bounds.width, bounds.height, input
+                ),
+                Err(error) => format!("Could not export {}: {}", input, error),
+            });
+        }
+        Prompt::Pattern
+            if matches!(
+                compress::format_extension(Path::new(input)),
+                Some("toml" | "json")
+            ) =>
+        {
+            state.message = Some(match Scene::load(input) {
+                Ok(scene) => {
+                    state.history.record(&state.game);
+                    state.game.clear();
+                    match scene.apply(&mut state.game) {
+                        Ok(()) => {
+                            state.fixed_size |= scene.width.is_some() || scene.height.is_some();
+                            format!("Loaded scene {}", input)
+                        }
+                        Err(error) => format!("Could not load scene: {}", error),
+                    }
+                }
+                Err(error) => format!("Could not load scene: {}", error),
+            });
+        }
+        Prompt::Pattern => {
+            state.message = Some(match Pattern::load_or_decode(input) {
+                Ok(pattern) => {
+                    if let Some(rule) = pattern.parse_rule() {
+                        state.game.rule = rule;
+                    }
+                    let name = pattern.name.clone().unwrap_or_else(|| input.to_string());
+                    let message = format!("Loaded {} ({}x{})", name, pattern.width, pattern.height);
+                    state.pattern = Some(pattern);
+                    state.pasted_rule = None;
+                    state.game.preview(
+                        selection(&state.pattern, state.seed, state.orientation),
+                        state.origin,
+                    );
+                    message
+                }
+                Err(error) => format!("Could not load pattern: {}", error),
+            });
+        }
+        Prompt::Command => run_command(state, input),
+        Prompt::Load => {
+            state.message = Some(match Savestate::load(input) {
+                Ok(savestate) => {
+                    state.history.record(&state.game);
+                    savestate.restore(&mut state.game);
+                    format!("Loaded {}", input)
+                }
+                Err(error) => format!("Could not load {}: {}", input, error),
+            });
+        }
+    }
+}
+
+/// Runs a command typed on the command line, those that ask for something
+/// through a key's prompt the same way the prompt does.
+fn run_command(state: &mut State, line: &str) {
+    let (command, argument) = match palette::parse(line) {
+        Ok(parsed) => parsed,
+        Err(error) => {
+            state.message = Some(error);
+            return;
+        }
+    };
+    let prompt = match command {
+        palette::Command::Rule => {
+            if let Ok(rule) = argument.parse() {
+                state.game.rule = rule;
+            }
+            state.message = Some(format!("Rule: {}", rule_label(&state.game.rule)));
+            return;
+        }
+        palette::Command::Speed => {
+            state.speed = match argument.parse().unwrap_or_default() {
+                0 => Speed::Unlimited,
+                rate => Speed::Rate(rate),
+            };
+            state.message = Some(format!("Speed: {}", state.speed));
+            return;
+        }
+        palette::Command::Clear => {
+            state.history.record(&state.game);
+            state.game.clear();
+            return;
+        }
+        palette::Command::Theme => {
+            switch_theme(state, argument);
+            return;
+        }
+        palette::Command::Keys => {
+            state.mode = Mode::Keys;
+            return;
+        }
+        palette::Command::Draw => {
+            if let Err(error) = switch_renderer(state, argument) {
+                state.message = Some(error.to_string());
+            }
+            return;
+        }
+        palette::Command::Step => Prompt::Advance,
+        palette::Command::Goto => Prompt::Goto,
+        palette::Command::Save => Prompt::Export,
+        palette::Command::Load => Prompt::Pattern,
+        palette::Command::SaveState => Prompt::Save,
+        palette::Command::LoadState => Prompt::Load,
+        palette::Command::Bookmark => Prompt::Bookmark,
+        palette::Command::Break => Prompt::Breakpoint,
+        palette::Command::Stats => Prompt::Stats,
+        palette::Command::Scatter => Prompt::Scatter,
+    };
+    submit_prompt(state, prompt, argument);
+}
+
+/// Switches to the color theme named, or the next one if none is.
+fn switch_theme(state: &mut State, name: &str) {
+    match name {
+        "" => state.theme = (state.theme + 1) % state.themes.len(),
+        name => match theme::find(&state.themes, name) {
+            Some(index) => state.theme = index,
+            None => {
+                state.message = Some(format!("No theme {:?}", name));
+                return;
+            }
+        },
     }
+    state.message = Some(format!("Theme: {}", state.themes[state.theme].name));
+}
+
+/// Draws the board with the renderer named, or the next one without a name,
+/// refitting it to as many cells as that shows.
+fn switch_renderer(state: &mut State, name: &str) -> std::io::Result<()> {
+    state.renderer = match name {
+        "" => state.renderer.next(),
+        name => match Renderer::from_str(name, true) {
+            Ok(renderer) => renderer,
+            Err(_) => {
+                state.message = Some(format!("No renderer {:?}", name));
+                return Ok(());
+            }
+        },
+    };
+
+    let center = state.game.viewport.center();
+    let (width, height) = terminal::size()?;
+    fit_board(state, Rect::new(0, 0, width, height));
+    if state.fixed_size {
+        // a board of its own size stays centered where it was
+        state.game.look_at(center);
+        state.origin = state.game.clamp_to_viewport(state.origin);
+        state.game.preview(
+            selection(&state.pattern, state.seed, state.orientation),
+            state.origin,
+        );
+    }
+    state.message = Some(format!("Drawing cells as {}", state.renderer));
+    Ok(())
+}
+
+/// The command line typed so far, then the commands it could be, each with
+/// how it's typed and what it does.
+fn command_lines(input: &str) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!(":{}_", input))];
+    for command in palette::matching(input) {
+        lines.push(Line::from(vec![
+            Span::raw(format!("  {:<18}", command.usage())),
+            Span::raw(command.description()).dim(),
+        ]));
+    }
+    lines
+}
+
+fn handle_rule_editor_input(state: &mut State, code: KeyCode) {
+    let Mode::RuleEditor { survival, count } = &mut state.mode else {
+        return;
+    };
+    let rule = &mut state.game.rule;
+
+    match code {
+        KeyCode::Esc | KeyCode::Enter => state.mode = Mode::Normal,
+        KeyCode::Left => *count = count.saturating_sub(1),
+        KeyCode::Right => *count = (*count + 1).min(8),
+        KeyCode::Up | KeyCode::Down => *survival = !*survival,
+        KeyCode::Tab => *rule = rule.next_preset(),
+        KeyCode::BackTab => *rule = rule.previous_preset(),
+        // Larger than Life rules have ranges instead of counts, and the others are fixed
+        _ if !rule.is_life_like() => {}
+        KeyCode::Char(' ') => match survival {
+            true => rule.survival[*count] = !rule.survival[*count],
+            false => rule.birth[*count] = !rule.birth[*count],
+        },
+        KeyCode::Char(ch @ '0'..='8') => {
+            *count = ch as usize - '0' as usize;
+            match survival {
+                true => rule.survival[*count] = !rule.survival[*count],
+                false => rule.birth[*count] = !rule.birth[*count],
+            }
+        }
+        _ => {}
+    }
+}
+
+fn handle_bookmarks_input(state: &mut State, code: KeyCode) {
+    let Mode::Bookmarks { selected } = &mut state.mode else {
+        return;
+    };
+
+    let restore = match code {
+        KeyCode::Esc => {
+            state.mode = Mode::Normal;
+            None
+        }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+            None
+        }
+        KeyCode::Down => {
+            *selected = (*selected + 1).min(state.bookmarks.len().saturating_sub(1));
+            None
+        }
+        KeyCode::Delete if *selected < state.bookmarks.len() => {
+            state.bookmarks.remove(*selected);
+            *selected = (*selected).min(state.bookmarks.len().saturating_sub(1));
+            None
+        }
+        KeyCode::Enter => Some(*selected),
+        KeyCode::Char(ch @ '1'..='9') => Some(ch as usize - '1' as usize),
+        _ => None,
+    };
+
+    if let Some((name, snapshot)) = restore.and_then(|index| state.bookmarks.get(index)) {
+        state.history.record(&state.game);
+        snapshot.restore(&mut state.game);
+        state.message = Some(format!("Jumped to {}", name));
+        state.mode = Mode::Normal;
+    }
+}
+
+fn bookmark_lines(bookmarks: &[(String, Snapshot)], selected: usize) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = bookmarks
+        .iter()
+        .enumerate()
+        .map(|(index, (name, snapshot))| {
+            let line = Span::raw(format!(
+                "{}. {} ({} cells)",
+                index + 1,
+                name,
+                snapshot.population()
+            ));
+            Line::from(match index == selected {
+                true => line.reversed(),
+                false => line,
+            })
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from("No bookmarks yet, press K to add one"));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(
+        Span::raw("1-9 or Enter (jump) | Delete (remove) | Esc (close)").dim(),
+    ));
+    lines
+}
+
+/// Puts the live cells in the selection on the clipboard as RLE, taking them
+/// off the board if cutting, and says how it went.
+fn copy_selection(state: &mut State, cut: bool) -> String {
+    let game = &mut state.game;
+    let Some(bounds) = game.selection else {
+        return String::from("Nothing selected, right-drag or press Ctrl+Space to select");
+    };
+
+    let mut pattern = Pattern::from_cells(&game.cells, bounds);
+    pattern.rule = Some(game.rule.to_string());
+    let mut rle = Vec::new();
+    if let Err(error) = pattern.write_rle(&mut rle) {
+        return format!("Could not copy: {}", error);
+    }
+    let copied = match clipboard::copy(&String::from_utf8_lossy(&rle)) {
+        Ok(Copied::Tool(tool)) => format!("with {}", tool),
+        Ok(Copied::Terminal) => String::from("through the terminal"),
+        Err(error) => return format!("Could not copy: {}", error),
+    };
+
+    // stamp it until something else is picked
+    pattern.name = Some(String::from("Selection"));
+    state.buffer = Some(pattern.clone());
+    let message = format!(
+        "{} {}x{} ({} cells) {}, Space to stamp",
+        if cut { "Cut" } else { "Copied" },
+        bounds.width,
+        bounds.height,
+        pattern.cells.len(),
+        copied
+    );
+    state.pattern = Some(pattern);
+    state.pasted_rule = None;
+
+    if cut {
+        state.history.record(game);
+        let cells: Vec<Cell> = game
+            .cells
+            .iter()
+            .filter(|cell| bounds.contains(cell))
+            .copied()
+            .collect();
+        for cell in &cells {
+            game.remove_cell(cell);
+        }
+    }
+    game.preview(
+        selection(&state.pattern, state.seed, state.orientation),
+        state.origin,
+    );
+    message
+}
+
+/// Takes a pattern pasted from elsewhere, in any format read, as the one
+/// placed, previewing it at the cursor.
+fn paste(state: &mut State, text: &str) -> String {
+    match Pattern::parse(text) {
+        Ok(pattern) => {
+            state.pasted_rule = pattern.parse_rule().filter(|&rule| rule != state.game.rule);
+            let message = format!(
+                "Pasted {}x{} ({} cells), Space to place{}",
+                pattern.width,
+                pattern.height,
+                pattern.cells.len(),
+                match &state.pasted_rule {
+                    Some(rule) => format!(" and switch to {}", rule_label(rule)),
+                    None => String::new(),
+                }
+            );
+            state.pattern = Some(pattern);
+            state.game.preview(
+                selection(&state.pattern, state.seed, state.orientation),
+                state.origin,
+            );
+            message
+        }
+        Err(error) => format!("Could not paste: {}", error),
+    }
+}
+
+/// Nudges the lifted cells with the arrow keys, five at a time with Shift,
+/// and drops them with Enter, Space or Ctrl+D, or back where they were
+/// with Esc. The selection moves with them.
+fn handle_move_input(state: &mut State, code: KeyCode, modifiers: event::KeyModifiers) {
+    let Mode::Moving { offset, .. } = &mut state.mode else {
+        return;
+    };
+
+    let step = match modifiers {
+        event::KeyModifiers::SHIFT => 5,
+        _ => 1,
+    };
+    let landing = match code {
+        KeyCode::Left => {
+            offset.0 -= step;
+            None
+        }
+        KeyCode::Right => {
+            offset.0 += step;
+            None
+        }
+        KeyCode::Up => {
+            offset.1 -= step;
+            None
+        }
+        KeyCode::Down => {
+            offset.1 += step;
+            None
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => Some(*offset),
+        KeyCode::Char('d') if modifiers == event::KeyModifiers::CONTROL => Some(*offset),
+        KeyCode::Esc => Some((0, 0)),
+        _ => None,
+    };
+
+    let game = &mut state.game;
+    if let Some((dx, dy)) = landing {
+        let Mode::Moving { cells, from, .. } = std::mem::take(&mut state.mode) else {
+            return;
+        };
+        for (x, y) in cells {
+            game.add_cell((x + dx, y + dy));
+        }
+        game.preview.clear();
+        game.selection = Some(BoundingBox {
+            x: from.x + dx,
+            y: from.y + dy,
+            ..from
+        });
+        state.message = Some(match (dx, dy) {
+            (0, 0) => String::from("Put back"),
+            (dx, dy) => format!("Moved by {},{}", dx, dy),
+        });
+        return;
+    }
+
+    let Mode::Moving {
+        cells,
+        from,
+        offset: (dx, dy),
+    } = &state.mode
+    else {
+        return;
+    };
+    game.preview = cells.iter().map(|(x, y)| (x + dx, y + dy)).collect();
+    game.selection = Some(BoundingBox {
+        x: from.x + dx,
+        y: from.y + dy,
+        ..*from
+    });
+    state.message = Some(moving_message((*dx, *dy)));
+}
+
+fn moving_message((dx, dy): Cell) -> String {
+    format!(
+        "Moving by {},{} | Arrows (nudge) | Enter (drop) | Esc (put back)",
+        dx, dy
+    )
+}
+
+fn handle_shift_input(state: &mut State, code: KeyCode, modifiers: event::KeyModifiers) {
+    let Mode::Shifting { offset } = state.mode else {
+        return;
+    };
+
+    let step = match modifiers {
+        event::KeyModifiers::SHIFT => 5,
+        _ => 1,
+    };
+    let (dx, dy) = match code {
+        KeyCode::Left => (-step, 0),
+        KeyCode::Right => (step, 0),
+        KeyCode::Up => (0, -step),
+        KeyCode::Down => (0, step),
+        KeyCode::Esc => (-offset.0, -offset.1),
+        _ => (0, 0),
+    };
+    let moved = state.game.translate(dx, dy);
+    let offset = match moved {
+        true => (offset.0 + dx, offset.1 + dy),
+        false => offset,
+    };
+    let done = match code {
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char(' ') => true,
+        KeyCode::Char('d') => modifiers == event::KeyModifiers::CONTROL,
+        _ => false,
+    };
+
+    state.mode = match done {
+        true => Mode::Normal,
+        false => Mode::Shifting { offset },
+    };
+    state.message = Some(match offset {
+        (0, 0) if done => String::from("Put back"),
+        (dx, dy) if done => format!("Moved the board by {},{}", dx, dy),
+        _ if !moved => String::from("The board's dead edges are in the way"),
+        _ => shifting_message(offset),
+    });
+}
+
+fn shifting_message((dx, dy): Cell) -> String {
+    format!(
+        "Moving the board by {},{} | Arrows (move) | Enter (done) | Esc (put back)",
+        dx, dy
+    )
+}
+
+fn handle_browser_input(state: &mut State, code: KeyCode, modifiers: event::KeyModifiers) {
+    let Mode::Browser {
+        entries,
+        query,
+        selected,
+    } = &mut state.mode
+    else {
+        return;
+    };
+    let (found, _) = browser::search(entries, query);
+
+    let chosen = match code {
+        KeyCode::Esc => {
+            state.mode = Mode::Normal;
+            None
+        }
+        KeyCode::Char('y') if modifiers == event::KeyModifiers::CONTROL => {
+            state.mode = Mode::Normal;
+            None
+        }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+            None
+        }
+        KeyCode::Down => {
+            *selected = (*selected + 1).min(found.len().saturating_sub(1));
+            None
+        }
+        KeyCode::Enter => found.get(*selected).map(|entry| entry.pick),
+        // the best match is picked as the search changes
+        KeyCode::Backspace => {
+            query.pop();
+            *selected = browser::search(entries, query).1;
+            None
+        }
+        KeyCode::Char(ch) if query.chars().count() < EDITOR_NAME_LENGTH => {
+            query.push(ch);
+            *selected = browser::search(entries, query).1;
+            None
+        }
+        _ => None,
+    };
+
+    if let Some(pick) = chosen {
+        state.mode = Mode::Normal;
+        choose_seed(state, pick);
+        let seed = selection(&state.pattern, state.seed, state.orientation);
+        state.message = Some(format!("Picked {}", seed.0.seed.name()));
+        state.game.preview(seed, state.origin);
+    }
+}
+
+/// The search typed so far, then the seeds found under their categories,
+/// with the line the cursor is on.
+fn browser_lines(
+    query: &str,
+    found: &[&browser::Entry],
+    selected: usize,
+) -> (Vec<Line<'static>>, usize) {
+    let mut lines = vec![
+        Line::from(format!("Search: {}_", query)),
+        Line::from(Span::raw("Enter (pick) | Esc (close)").dim()),
+    ];
+    let mut line = 0;
+    let mut category = None;
+    for (index, entry) in found.iter().enumerate() {
+        if category != Some(&entry.category) {
+            category = Some(&entry.category);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::raw(entry.category.clone()).bold()));
+        }
+        if index == selected {
+            line = lines.len();
+        }
+        let name = Span::raw(format!(" {} {}", entry.thumbnail, entry.name));
+        lines.push(Line::from(match index == selected {
+            true => name.reversed(),
+            false => name,
+        }));
+    }
+    if found.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from("No seeds match"));
+    }
+    (lines, line)
+}
+
+fn handle_leaderboard_input(state: &mut State, code: KeyCode) {
+    let Mode::Leaderboard { entries, selected } = &mut state.mode else {
+        return;
+    };
+
+    let chosen = match code {
+        KeyCode::Esc => {
+            state.mode = Mode::Normal;
+            None
+        }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+            None
+        }
+        KeyCode::Down => {
+            *selected = (*selected + 1).min(entries.len().saturating_sub(1));
+            None
+        }
+        KeyCode::Enter => entries.get(*selected).copied(),
+        KeyCode::Char(ch @ '1'..='9') => entries.get(ch as usize - '1' as usize).copied(),
+        _ => None,
+    };
+
+    // soups are searched in Life, so they're played back in it too
+    if let Some(entry) = chosen {
+        let game = &mut state.game;
+        state.history.record(game);
+        game.clear();
+        game.rule = Rule::default();
+        let corner = (
+            (game.viewport.width / 2).saturating_sub(search::SOUP_SIZE / 2),
+            (game.viewport.height / 2).saturating_sub(search::SOUP_SIZE / 2),
+        );
+        game.seed(search::soup(entry.seed), game.to_universe(corner));
+        state.message = Some(format!(
+            "Soup {}, settles at gen {} with {} cells",
+            entry.seed, entry.lifespan, entry.population
+        ));
+        state.mode = Mode::Normal;
+    }
+}
+
+fn leaderboard_lines(entries: &[Entry], selected: usize) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let line = Span::raw(format!(
+                "{:>2}. Soup {:<12} {:>6} gens {:>6} cells",
+                index + 1,
+                entry.seed,
+                entry.lifespan,
+                entry.population
+            ));
+            Line::from(match index == selected {
+                true => line.reversed(),
+                false => line,
+            })
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from("No soups yet, run the search subcommand"));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(
+        Span::raw("1-9 or Enter (load soup) | Esc (close)").dim(),
+    ));
+    lines
+}
+
+/// Draws on the canvas with the arrow keys and Space, Tab moving on to the
+/// name and category, and saves the seed with Enter. Esc goes back to the
+/// canvas, and from there closes the editor.
+fn handle_editor_input(state: &mut State, code: KeyCode) {
+    let Mode::Editor(editor) = &mut state.mode else {
+        return;
+    };
+
+    let text = match editor.field {
+        EditorField::Canvas => None,
+        EditorField::Name => Some(&mut editor.name),
+        EditorField::Category => Some(&mut editor.category),
+    };
+    match (code, text) {
+        (KeyCode::Esc, None) => state.mode = Mode::Normal,
+        (KeyCode::Esc, Some(_)) => editor.field = EditorField::Canvas,
+        (KeyCode::Tab, _) => {
+            editor.field = match editor.field {
+                EditorField::Canvas => EditorField::Name,
+                EditorField::Name => EditorField::Category,
+                EditorField::Category => EditorField::Canvas,
+            }
+        }
+        (KeyCode::Enter, _) => state.message = Some(save_edited_seed(state)),
+        (KeyCode::Backspace, Some(text)) => {
+            text.pop();
+        }
+        (KeyCode::Char(ch), Some(text)) if text.chars().count() < EDITOR_NAME_LENGTH => {
+            text.push(ch);
+        }
+        (KeyCode::Char(' ') | KeyCode::Insert, None) if editor.cells.contains(&editor.cursor) => {
+            editor.cells.remove(&editor.cursor);
+        }
+        (KeyCode::Char(' ') | KeyCode::Insert, None) => {
+            editor.cells.insert(editor.cursor);
+        }
+        (KeyCode::Delete, None) => editor.cells.clear(),
+        (KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down, None) => {
+            let (dx, dy) = match code {
+                KeyCode::Left => (-1, 0),
+                KeyCode::Right => (1, 0),
+                KeyCode::Up => (0, -1),
+                _ => (0, 1),
+            };
+            editor.cursor = (
+                (editor.cursor.0 + dx).clamp(0, EDITOR_SIZE - 1),
+                (editor.cursor.1 + dy).clamp(0, EDITOR_SIZE - 1),
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Saves the seed drawn in the editor to the user's seeds and picks it,
+/// closing the editor, or says what's missing.
+fn save_edited_seed(state: &mut State) -> String {
+    let Mode::Editor(editor) = &mut state.mode else {
+        return String::new();
+    };
+    let Some(bounds) = BoundingBox::of(&editor.cells) else {
+        return String::from("Draw the seed first");
+    };
+    let name = editor.name.trim();
+    if name.is_empty() {
+        editor.field = EditorField::Name;
+        return String::from("Name the seed first");
+    }
+
+    let mut pattern = Pattern::from_cells(&editor.cells, bounds);
+    pattern.name = Some(name.to_string());
+    let seed = UserSeed {
+        category: match editor.category.trim() {
+            "" => library::DEFAULT_CATEGORY.to_string(),
+            category => category.to_string(),
+        },
+        pattern,
+    };
+    let saved = library::dir().and_then(|dir| {
+        library::save(&dir, &seed)?;
+        library::load(&dir)
+    });
+    match saved {
+        Ok(seeds) => {
+            let saved_seeds = seeds.len();
+            state.user_seeds.splice(..state.saved_seeds, seeds);
+            state.saved_seeds = saved_seeds;
+            let catalog = library::catalog(&state.user_seeds);
+            let pick = Pick::find(&catalog, &seed.category, seed.name());
+            choose_seed(state, pick.unwrap_or_default());
+            state.mode = Mode::Normal;
+            state.game.preview(
+                selection(&state.pattern, state.seed, state.orientation),
+                state.origin,
+            );
+            format!("Saved {} to {}", seed.name(), seed.category)
+        }
+        Err(error) => format!("Could not save {}: {}", seed.name(), error),
+    }
+}
+
+/// The seed being drawn, as the board is, with the cursor shaded like a
+/// seed about to be placed, then the fields to name it.
+fn editor_lines(
+    editor: &Editor,
+    theme: &Theme,
+    renderer: Renderer,
+    cell_width: Option<u16>,
+) -> Vec<Line<'static>> {
+    let on_canvas = editor.field == EditorField::Canvas;
+    let shades: Vec<Vec<Shade>> = (0..EDITOR_SIZE)
+        .map(|y| {
+            (0..EDITOR_SIZE)
+                .map(|x| {
+                    let alive = editor.cells.contains(&(x, y));
+                    match (on_canvas && editor.cursor == (x, y), alive) {
+                        (true, true) => Shade::Overlap,
+                        (true, false) => Shade::Preview,
+                        (false, true) => Shade::Alive,
+                        (false, false) => Shade::Dead,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let mut lines = renderer.lines(theme, &shades, cell_width);
+
+    let field = |label: &str, text: &str, focused: bool| {
+        let text = Span::raw(format!("{}: {}", label, text));
+        Line::from(match focused {
+            true => vec![text, Span::raw("_")],
+            false => vec![text.dim()],
+        })
+    };
+    lines.push(Line::from(""));
+    lines.push(field(
+        "Name",
+        &editor.name,
+        editor.field == EditorField::Name,
+    ));
+    lines.push(field(
+        "Category",
+        &editor.category,
+        editor.field == EditorField::Category,
+    ));
+    lines.push(Line::from(""));
+    lines.push(Line::from(
+        Span::raw("Space (draw) | Delete (clear) | Tab (name) | Enter (save) | Esc (close)").dim(),
+    ));
+    lines
+}
+
+/// [`INSTRUCTIONS`] with as many keys on each line as fit in `width`.
+fn keys_lines(width: usize) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = Vec::new();
+    let mut line = String::new();
+    for key in INSTRUCTIONS.split(" | ") {
+        if !line.is_empty() && line.chars().count() + 3 + key.chars().count() > width {
+            lines.push(Line::from(std::mem::take(&mut line)));
+        }
+        if !line.is_empty() {
+            line.push_str(" | ");
+        }
+        line.push_str(key);
+    }
+    lines.push(Line::from(line));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::raw("Any key (close)").dim()));
+    lines
+}
+
+fn census_lines(counts: &[(&str, usize)]) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line> = counts
+        .iter()
+        .map(|(name, count)| Line::from(format!("{:<28}{:>8}", name, count)))
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from("Nothing on the board"));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::raw("Any key (close)").dim()));
+    lines
+}
+
+fn handle_explorer_input(state: &mut State, code: KeyCode) {
+    let Mode::Explorer(candidate) = state.mode else {
+        return;
+    };
+
+    match code {
+        KeyCode::Esc => state.mode = Mode::Normal,
+        KeyCode::Char('k') | KeyCode::Char('K') => {
+            state.message = Some(match config::save_rule(&candidate.rule) {
+                Ok(path) => format!("Saved {} to {}", candidate.rule, path.display()),
+                Err(error) => format!("Could not save {}: {}", candidate.rule, error),
+            });
+            state.mode = Mode::Explorer(explorer::search(&mut state.rng));
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Char(' ') => {
+            state.mode = Mode::Explorer(explorer::search(&mut state.rng));
+        }
+        // skip the search, for rules that die out or explode too
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            state.mode = Mode::Explorer(explorer::roll(&mut state.rng));
+        }
+        KeyCode::Enter => {
+            let game = &mut state.game;
+            let (width, height) = (game.viewport.width / 2, game.viewport.height / 2);
+            state.history.record(game);
+            game.clear();
+            game.rule = candidate.rule;
+            let seed = std::mem::replace(&mut state.rng_seed, state.rng.gen());
+            game.seed(
+                Soup::random(&mut StdRng::seed_from_u64(seed), width, height, 0.5),
+                game.to_universe((game.viewport.width / 4, game.viewport.height / 4)),
+            );
+            state.message = Some(format!("Soup {}, made again by --rng-seed {}", seed, seed));
+            state.play = PlayState::Playing;
+            state.mode = Mode::Normal;
+        }
+        _ => {}
+    }
+}
+
+/// Writes the instant replay to a timestamped GIF, describing the outcome.
+fn save_instant_replay(state: &State) -> String {
+    if state.replay.is_empty() {
+        return String::from("Nothing to replay yet");
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let path = format!("replay-{}.gif", timestamp);
+    // GIF frame delays are in hundredths of a second
+    let delay = (state.speed.interval().as_millis() / 10).clamp(2, u16::MAX as u128) as u16;
+
+    let bounds = export::region(
+        state.replay.frames().flatten(),
+        state.game.width,
+        state.game.height,
+        state.crop,
+    );
+
+    let result = File::create(&path).and_then(|file| {
+        export::write_gif(BufWriter::new(file), state.replay.frames(), bounds, delay)
+    });
+
+    match result {
+        Ok(()) => format!("Saved {} generations to {}", state.replay.len(), path),
+        Err(error) => format!("Could not save replay: {}", error),
+    }
+}
+
+fn next_slow_motion(speed: Speed) -> Speed {
+    match SLOW_MOTION_PRESETS
+        .iter()
+        .position(|preset| *preset == speed)
+    {
+        Some(index) => SLOW_MOTION_PRESETS[(index + 1) % SLOW_MOTION_PRESETS.len()],
+        None => SLOW_MOTION_PRESETS[0],
+    }
+}
+
+/// The next slower or faster speed out of the slow-motion and Alt+1-9
+/// presets, staying put past the slowest and fastest ones.
+fn step_speed(speed: Speed, faster: bool) -> Speed {
+    let mut speeds = SLOW_MOTION_PRESETS
+        .iter()
+        .rev()
+        .chain(SPEED_PRESETS.iter())
+        .copied();
+    let interval = speed.interval();
+    let next = match faster {
+        true => speeds.find(|preset| preset.interval() < interval),
+        false => speeds.rfind(|preset| preset.interval() > interval),
+    };
+    next.unwrap_or(speed)
+}
+
+/// How many columns the seed browser takes on the right of the board.
+const SIDEBAR_WIDTH: u16 = 34;
+/// How many rows at the bottom of the seed browser tell about the seed the
+/// cursor is on.
+const SIDEBAR_DETAILS_HEIGHT: u16 = 10;
+
+/// Moves on to the first seed of the next category, or going `back`, the
+/// previous one, and says which it is.
+fn next_category(state: &mut State, back: bool) {
+    let catalog = library::catalog(&state.user_seeds);
+    let pick = state.pick.next_category(&catalog, back);
+    if let Some(category) = catalog.get(pick.category) {
+        state.message = Some(format!(
+            "{}, 1-9 or PageUp/PageDown for its {} seeds",
+            category.name,
+            category.seeds.len()
+        ));
+    }
+    choose_seed(state, pick);
+}
+
+/// Moves on to the next seed in the category, or going `back`, the previous
+/// one.
+fn next_seed(state: &mut State, back: bool) {
+    let catalog = library::catalog(&state.user_seeds);
+    choose_seed(state, state.pick.next_seed(&catalog, back));
+    describe_pick(state);
+}
+
+/// Picks a seed from the catalog, or the single cell if it's not there.
+fn choose_seed(state: &mut State, pick: Pick) {
+    let catalog = library::catalog(&state.user_seeds);
+    let (pick, source) = match pick.get(&catalog) {
+        Some((_, source)) => (pick, *source),
+        None => (Pick::default(), Source::BuiltIn(SEEDS[0])),
+    };
+    state.pick = pick;
+    state.pasted_rule = None;
+    match source {
+        Source::BuiltIn(seed) => {
+            state.seed = seed;
+            state.pattern = None;
+        }
+        Source::User(index) => {
+            state.pattern = state.user_seeds.get(index).map(|seed| seed.pattern.clone());
+        }
+    }
+}
+
+/// Every seed in the catalog, to search through.
+fn seed_entries(catalog: &[Category], user_seeds: &[UserSeed]) -> Vec<browser::Entry> {
+    let mut entries = Vec::new();
+    for (index, category) in catalog.iter().enumerate() {
+        for (seed, (name, source)) in category.seeds.iter().enumerate() {
+            let cells = match *source {
+                Source::BuiltIn(seed) => seed.cells((0, 0)),
+                Source::User(index) => user_seeds
+                    .get(index)
+                    .map(|seed| seed.pattern.cells.clone())
+                    .unwrap_or_default(),
+            };
+            entries.push(browser::Entry {
+                category: category.name.clone(),
+                name: name.clone(),
+                pick: Pick {
+                    category: index,
+                    seed,
+                },
+                thumbnail: browser::thumbnail(&cells),
+            });
+        }
+    }
+    entries
+}
+
+/// A seed's size and what's known about it, for the seed browser: who found
+/// it and what it does for the built-in ones, and for the user's, whatever
+/// its file says.
+fn seed_details(catalog: &[Category], user_seeds: &[UserSeed], pick: Pick) -> Vec<Line<'static>> {
+    let Some((name, source)) = pick.get(catalog) else {
+        return Vec::new();
+    };
+    let size = |cells: &[Cell]| {
+        let bounds = BoundingBox::of(cells).unwrap_or_default();
+        Line::from(
+            Span::raw(format!(
+                "{}x{}, {} cells",
+                bounds.width,
+                bounds.height,
+                cells.len()
+            ))
+            .dim(),
+        )
+    };
+
+    let mut lines = vec![Line::from(Span::raw(name.clone()).bold())];
+    match *source {
+        Source::BuiltIn(seed) => {
+            let about = seed.about();
+            lines.push(size(&seed.cells((0, 0))));
+            let mut summary = about.summary();
+            if let Some(first) = summary.get_mut(..1) {
+                first.make_ascii_uppercase();
+                lines.push(Line::from(summary));
+            }
+            lines.push(Line::from(about.description));
+        }
+        Source::User(index) => {
+            let Some(seed) = user_seeds.get(index) else {
+                return lines;
+            };
+            lines.push(size(&seed.pattern.cells));
+            if let Some(author) = &seed.pattern.author {
+                lines.push(Line::from(format!("By {}", author)));
+            }
+            for comment in &seed.pattern.comments {
+                lines.push(Line::from(comment.clone()));
+            }
+        }
+    }
+    lines
+}
+
+/// Says who found the seed picked and what its period and speed are, if
+/// it's one of the built-in ones and any of that is known.
+fn describe_pick(state: &mut State) {
+    let catalog = library::catalog(&state.user_seeds);
+    if let Some((name, Source::BuiltIn(seed))) = state.pick.get(&catalog) {
+        let summary = seed.about().summary();
+        if !summary.is_empty() {
+            state.message = Some(format!("{}: {}", name, summary));
+        }
+    }
+}
+
+/// Places the selected seed by hand at `origin`, unless it lands on live
+/// cells with --confirm-overlap on and wasn't just placed there once already.
+fn place_seed(state: &mut State, origin: Cell) {
+    let seed = selection(&state.pattern, state.seed, state.orientation);
+    let overlap = state.game.overlap(&seed, origin);
+    if state.confirm_overlap && overlap > 0 && state.unconfirmed.replace(origin) != Some(origin) {
+        state.message = Some(format!(
+            "That would land on {} live cells, place it again to go ahead",
+            overlap
+        ));
+        return;
+    }
+    state.unconfirmed = None;
+    state.history.record(&state.game);
+    state.game.place(seed, origin);
+    if let Some(rule) = state.pasted_rule.take() {
+        state.game.rule = rule;
+        state.message = Some(format!("Rule: {}", rule_label(&rule)));
+    }
+}
+
+/// What Space places: a built-in seed, or a pattern file loaded with L.
+#[derive(Debug)]
+enum Selection<'p> {
+    Seed(Seed),
+    Pattern(&'p Pattern),
+}
+
+impl Selection<'_> {
+    fn name(&self) -> &str {
+        match self {
+            Selection::Seed(seed) => seed.name(),
+            Selection::Pattern(pattern) => pattern.name.as_deref().unwrap_or("Pattern"),
+        }
+    }
+}
+
+impl IsSeed for Selection<'_> {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        match self {
+            Selection::Seed(seed) => seed.cells(origin),
+            Selection::Pattern(pattern) => pattern.cells(origin),
+        }
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        match self {
+            Selection::Seed(seed) => seed.electrons(origin),
+            Selection::Pattern(pattern) => pattern.electrons(origin),
+        }
+    }
+}
+
+/// What Space places, turned and flipped in place.
+fn selection(
+    pattern: &Option<Pattern>,
+    seed: Seed,
+    orientation: Orientation,
+) -> InPlace<Selection<'_>> {
+    let seed = match pattern {
+        Some(pattern) => Selection::Pattern(pattern),
+        None => Selection::Seed(seed),
+    };
+    InPlace(Oriented { seed, orientation })
 }
 
 #[inline]
-fn teardown() -> std::io::Result<()> {
+pub fn teardown(reports_key_release: bool) -> std::io::Result<()> {
+    if reports_key_release {
+        stdout().execute(PopKeyboardEnhancementFlags)?;
+    }
+    stdout().execute(DisableMouseCapture)?;
+    stdout().execute(DisableBracketedPaste)?;
     stdout().execute(LeaveAlternateScreen)?;
     disable_raw_mode()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goto() {
+        let mut state = State {
+            game: Grid::new(40, 20),
+            ..Default::default()
+        };
+        assert!("-12,7".chars().all(|ch| Prompt::Goto.accepts(ch)));
+
+        submit_prompt(&mut state, Prompt::Goto, "-5000,12000");
+        assert_eq!(state.origin, (-5000, 12000));
+        assert!(state.game.viewport.contains(&(-5000, 12000)));
+    }
+
+    #[test]
+    fn test_keys_lines() {
+        let lines = keys_lines(80);
+        let keys: Vec<String> = lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        assert!(keys.iter().all(|line| line.chars().count() <= 80));
+        assert_eq!(keys[..lines.len() - 2].join(" | "), INSTRUCTIONS);
+    }
+
+    #[test]
+    fn test_paste_rule() {
+        let mut state = State {
+            game: Grid::new(40, 20),
+            origin: (10, 10),
+            ..Default::default()
+        };
+        let life = state.game.rule;
+        let rle = "x = 3, y = 1, rule = B36/S23\n3o!\n";
+
+        let message = paste(&mut state, rle);
+        assert!(
+            message.ends_with("and switch to B36/S23 (HighLife)"),
+            "{}",
+            message
+        );
+        assert_eq!(state.game.rule, life);
+        place_seed(&mut state, (10, 10));
+        assert_eq!(state.game.rule, "B36/S23".parse().unwrap());
+        assert_eq!(state.game.cells.len(), 3);
+
+        state.game.rule = life;
+        paste(&mut state, rle);
+        choose_seed(&mut state, Pick::default());
+        place_seed(&mut state, (10, 10));
+        assert_eq!(state.game.rule, life);
+    }
+
+    #[test]
+    fn test_run_command() {
+        let mut state = State {
+            game: Grid::new(40, 20),
+            speed: Speed::Rate(15),
+            ..Default::default()
+        };
+        let rule = state.game.rule;
+        for (line, message) in [
+            ("speed fast", "Usage: speed N"),
+            ("speed", "Usage: speed N"),
+            ("rule B36/S2x", "Usage: rule RULE"),
+        ] {
+            run_command(&mut state, line);
+            assert_eq!(state.message.as_deref(), Some(message), "{}", line);
+            assert_eq!(state.speed, Speed::Rate(15), "{}", line);
+            assert_eq!(state.game.rule, rule, "{}", line);
+        }
+
+        run_command(&mut state, "speed 0");
+        assert_eq!(state.speed, Speed::Unlimited);
+        run_command(&mut state, "rule highlife");
+        assert_ne!(state.game.rule, rule);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    io::{ErrorKind, Write},
+    process::{Command, Stdio},
+};
+
+/// Programs that put their input on the system clipboard, tried in turn.
+const COPY_TOOLS: [(&str, &[&str]); 5] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip.exe", &[]),
+];
+
+/// Programs that write out what's on the system clipboard, tried in turn.
+const PASTE_TOOLS: [(&str, &[&str]); 5] = [
+    ("pbpaste", &[]),
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-out"]),
+    ("xsel", &["--clipboard", "--output"]),
+    (
+        "powershell.exe",
+        &["-NoProfile", "-Command", "Get-Clipboard"],
+    ),
+];
+
+/// How text got onto the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Copied {
+    /// Through one of the clipboard programs, named.
+    Tool(&'static str),
+    /// Through the terminal, which may not support it.
+    Terminal,
+}
+
+/// Puts text on the system clipboard with the first clipboard program that
+/// works, or failing that, asks the terminal to with an OSC 52 sequence,
+/// which also works over SSH.
+pub fn copy(text: &str) -> std::io::Result<Copied> {
+    for (tool, args) in COPY_TOOLS {
+        let child = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(error) if error.kind() == ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(Copied::Tool(tool));
+        }
+    }
+
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+    stdout.flush()?;
+    Ok(Copied::Terminal)
+}
+
+/// The text on the system clipboard, read with the first clipboard program
+/// that works. Terminals can't be asked for it, but they send it themselves
+/// when pasted into.
+pub fn paste() -> std::io::Result<String> {
+    for (tool, args) in PASTE_TOOLS {
+        let output = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                return Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+            }
+            Ok(_) => continue,
+            Err(error) if error.kind() == ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Err(std::io::Error::new(
+        ErrorKind::NotFound,
+        "no clipboard program found, paste with the terminal instead",
+    ))
+}
+
+/// Encodes bytes as standard base64, padded.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |triple, (index, byte)| {
+                triple | (*byte as u32) << (16 - 8 * index)
+            });
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => {
+                    let sextet = (triple >> (18 - 6 * index)) & 0x3f;
+                    encoded.push(ALPHABET[sextet as usize] as char);
+                }
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"b"), "Yg==");
+        assert_eq!(base64(b"bo"), "Ym8=");
+        assert_eq!(base64(b"bo$"), "Ym8k");
+        assert_eq!(base64(b"bo$2bo$3o!"), "Ym8kMmJvJDNvIQ==");
+    }
+}
@@ -0,0 +1,156 @@
+use flate2::{read::GzDecoder, write::GzEncoder};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+/// Compression applied to a file, picked from its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    /// `.gz`
+    Gzip,
+    /// `.zst` or `.zstd`
+    Zstd,
+}
+
+impl Compression {
+    pub fn from_path(path: &Path) -> Compression {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") | Some("zstd") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+}
+
+/// The extension naming the file's format, looking past the compression
+/// extension, as in `gun.rle.gz`.
+pub fn format_extension(path: &Path) -> Option<&str> {
+    let stem;
+    let path = match Compression::from_path(path) {
+        Compression::None => path,
+        _ => {
+            stem = Path::new(path.file_stem()?);
+            stem
+        }
+    };
+
+    path.extension()?.to_str()
+}
+
+/// A file being written, compressed on the fly according to its extension.
+/// Call [`Writer::finish`] to flush the last compressed block.
+pub enum Writer {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl Writer {
+    pub fn finish(self) -> std::io::Result<()> {
+        match self {
+            Writer::Plain(mut file) => file.flush(),
+            Writer::Gzip(encoder) => encoder.finish()?.flush(),
+            Writer::Zstd(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Writer::Plain(file) => file.write(buf),
+            Writer::Gzip(encoder) => encoder.write(buf),
+            Writer::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Writer::Plain(file) => file.flush(),
+            Writer::Gzip(encoder) => encoder.flush(),
+            Writer::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Creates a file, compressing what's written to it if its extension asks for it.
+pub fn create<P: AsRef<Path>>(path: P) -> std::io::Result<Writer> {
+    let path = path.as_ref();
+    let file = BufWriter::new(File::create(path)?);
+
+    Ok(match Compression::from_path(path) {
+        Compression::None => Writer::Plain(file),
+        Compression::Gzip => Writer::Gzip(GzEncoder::new(file, flate2::Compression::default())),
+        Compression::Zstd => Writer::Zstd(zstd::Encoder::new(file, 0)?),
+    })
+}
+
+/// Opens a file, decompressing it while reading if its extension asks for it.
+pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let file = BufReader::new(File::open(path)?);
+
+    Ok(match Compression::from_path(path) {
+        Compression::None => Box::new(file),
+        Compression::Gzip => Box::new(BufReader::new(GzDecoder::new(file))),
+        Compression::Zstd => Box::new(BufReader::new(zstd::Decoder::with_buffer(file)?)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_compression_from_path() {
+        assert_eq!(
+            Compression::from_path(Path::new("a.rle")),
+            Compression::None
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("a.rle.gz")),
+            Compression::Gzip
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("a.rle.zst")),
+            Compression::Zstd
+        );
+        assert_eq!(
+            Compression::from_path(Path::new("a.zstd")),
+            Compression::Zstd
+        );
+    }
+
+    #[test]
+    fn test_format_extension() {
+        assert_eq!(format_extension(Path::new("a.rle")), Some("rle"));
+        assert_eq!(format_extension(Path::new("dir/a.rle.gz")), Some("rle"));
+        assert_eq!(format_extension(Path::new("a.json.zst")), Some("json"));
+        assert_eq!(format_extension(Path::new("a.gz")), None);
+        assert_eq!(format_extension(Path::new("a")), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = std::env::temp_dir();
+        let text = "x y\n".repeat(1000);
+
+        for name in ["round-trip.txt", "round-trip.txt.gz", "round-trip.txt.zst"] {
+            let path = dir.join(format!("{}-{}", std::process::id(), name));
+
+            let mut writer = create(&path).unwrap();
+            writer.write_all(text.as_bytes()).unwrap();
+            writer.finish().unwrap();
+
+            let mut read_back = String::new();
+            open(&path).unwrap().read_to_string(&mut read_back).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(read_back, text, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use crate::{
+    args::Args,
+    capability::ColorDepth,
+    grid::{Boundary, Engine},
+    render::{Renderer, CELL_WIDTHS},
+    rule::Rule,
+    seed::Symmetry,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+const APP_DIR: &str = "game-of-life-cli";
+const RULES_FILE: &str = "rules.txt";
+const CONFIG_FILE: &str = "config.toml";
+/// What environment variables for settings start with, as in `GOL_FPS`.
+const ENV_PREFIX: &str = "GOL_";
+/// The settings environment variables can set, leaving other `GOL_*`
+/// variables, like `GOL_DEBUG`, to whatever else reads them.
+const ENV_SETTINGS: [&str; 18] = [
+    "width",
+    "height",
+    "rule",
+    "boundary",
+    "symmetry",
+    "engine",
+    "fps",
+    "autoplay",
+    "confirm-overlap",
+    "remove-escaped",
+    "crop",
+    "patterns-dir",
+    "golly",
+    "theme",
+    "colors",
+    "renderer",
+    "cell-width",
+    "mouse",
+];
+
+/// Settings kept in `config.toml` in the config directory, named as the
+/// options setting them are:
+///
+/// ```toml
+/// rule = "highlife"
+/// fps = 30
+/// patterns-dir = "~/life/patterns"
+/// theme = "amber"
+///
+/// [keys]
+/// quit = "ctrl+q"
+/// stamp = "ctrl+u"
+///
+/// [themes.amber]
+/// base = "phosphor"
+/// alive = "#ffb000"
+/// ```
+///
+/// Those left out are left to the defaults, and each can be set as well by
+/// an environment variable, or an option, over the file. Paths starting with
+/// `~/` are in the home directory.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub rule: Option<String>,
+    pub boundary: Option<Boundary>,
+    pub symmetry: Option<Symmetry>,
+    pub engine: Option<Engine>,
+    pub fps: Option<u32>,
+    pub autoplay: Option<bool>,
+    pub confirm_overlap: Option<bool>,
+    pub remove_escaped: Option<bool>,
+    pub crop: Option<usize>,
+    pub patterns_dir: Option<PathBuf>,
+    pub golly: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub colors: Option<ColorDepth>,
+    pub renderer: Option<Renderer>,
+    pub cell_width: Option<u16>,
+    pub mouse: Option<bool>,
+    /// Keys for actions in place of their defaults, by the action's name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, String>,
+    /// Color themes by name, each the colors it changes from another.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, toml::Table>,
+}
+
+impl Config {
+    /// Reads `config.toml` from the config directory, or the defaults if
+    /// there isn't one.
+    pub fn load() -> Result<Config, String> {
+        match path() {
+            Some(path) => Config::load_from(path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    /// Reads a config file, or the defaults if there's no such file.
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Config, String> {
+        let path = path.as_ref();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Config::default())
+            }
+            Err(error) => return Err(format!("{}: {}", path.display(), error)),
+        };
+        toml::from_str(&text)
+            .and_then(Config::check)
+            .map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// The settings in `GOL_*` variables among `vars`, each named as in the
+    /// config file in capitals, with underscores for dashes, like
+    /// `GOL_PATTERNS_DIR`. Numbers and `true` or `false` are read as they'd
+    /// be written in the file, and anything else as text. Variables naming
+    /// no setting are skipped.
+    pub fn from_env<I: IntoIterator<Item = (String, String)>>(vars: I) -> Result<Config, String> {
+        let mut config = Config::default();
+        for (var, value) in vars {
+            let Some(name) = var.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let name = name.to_lowercase().replace('_', "-");
+            if !ENV_SETTINGS.contains(&name.as_str()) {
+                continue;
+            }
+            let value = toml::from_str::<toml::Table>(&format!("value = {}", value))
+                .ok()
+                .and_then(|mut table| table.remove("value"))
+                .unwrap_or(toml::Value::String(value));
+            let setting = toml::Table::from_iter([(name, value)]);
+            let setting = setting
+                .try_into()
+                .and_then(Config::check)
+                .map_err(|error| format!("{}: {}", var, error.to_string().trim_end()))?;
+            config = setting.or(config);
+        }
+        Ok(config)
+    }
+
+    /// The settings given on the command line.
+    pub fn from_args(args: &Args) -> Config {
+        Config {
+            width: args.width,
+            height: args.height,
+            rule: args.rule.as_ref().map(Rule::to_string),
+            boundary: args.boundary,
+            symmetry: args.symmetry,
+            engine: args.engine,
+            fps: args.fps,
+            autoplay: match (args.autoplay, args.paused) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            confirm_overlap: args.confirm_overlap.then_some(true),
+            remove_escaped: args.remove_escaped.then_some(true),
+            crop: args.crop,
+            patterns_dir: args.patterns_dir.clone(),
+            golly: args.golly.clone(),
+            theme: args.theme.clone(),
+            colors: args.colors,
+            renderer: args.renderer.or(args.ascii.then_some(Renderer::Ascii)),
+            cell_width: args.cell_width,
+            mouse: match (args.mouse, args.no_mouse) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            },
+            keys: BTreeMap::new(),
+            themes: BTreeMap::new(),
+        }
+    }
+
+    /// Fails on a rule that can't be read, which is kept as text to be
+    /// written back the way it was, or a cell width there's no drawing.
+    fn check<E: serde::de::Error>(self) -> Result<Config, E> {
+        match (
+            self.rule.as_deref().map(str::parse::<Rule>),
+            self.cell_width,
+        ) {
+            (Some(Err(error)), _) => Err(E::custom(error)),
+            (_, Some(width)) if !CELL_WIDTHS.contains(&width) => Err(E::custom(format!(
+                "cell-width is {} to {}, not {}",
+                CELL_WIDTHS.start(),
+                CELL_WIDTHS.end(),
+                width
+            ))),
+            _ => Ok(self),
+        }
+    }
+
+    /// These settings, with those left out taken from `other`.
+    pub fn or(self, other: Config) -> Config {
+        let mut keys = other.keys;
+        keys.extend(self.keys);
+        let mut themes = other.themes;
+        themes.extend(self.themes);
+        Config {
+            width: self.width.or(other.width),
+            height: self.height.or(other.height),
+            rule: self.rule.or(other.rule),
+            boundary: self.boundary.or(other.boundary),
+            symmetry: self.symmetry.or(other.symmetry),
+            engine: self.engine.or(other.engine),
+            fps: self.fps.or(other.fps),
+            autoplay: self.autoplay.or(other.autoplay),
+            confirm_overlap: self.confirm_overlap.or(other.confirm_overlap),
+            remove_escaped: self.remove_escaped.or(other.remove_escaped),
+            crop: self.crop.or(other.crop),
+            patterns_dir: self.patterns_dir.or(other.patterns_dir),
+            golly: self.golly.or(other.golly),
+            theme: self.theme.or(other.theme),
+            colors: self.colors.or(other.colors),
+            renderer: self.renderer.or(other.renderer),
+            cell_width: self.cell_width.or(other.cell_width),
+            mouse: self.mouse.or(other.mouse),
+            keys,
+            themes,
+        }
+    }
+
+    /// Sets the options to these settings, leaving those left out as they
+    /// are.
+    pub fn apply(&self, args: &mut Args) {
+        args.width = self.width.or(args.width);
+        args.height = self.height.or(args.height);
+        if args.rule.is_none() {
+            args.rule = self.rule.as_deref().and_then(|rule| rule.parse().ok());
+        }
+        args.boundary = self.boundary.or(args.boundary);
+        args.symmetry = self.symmetry.or(args.symmetry);
+        args.engine = self.engine.or(args.engine);
+        args.fps = self.fps.or(args.fps);
+        if let Some(autoplay) = self.autoplay {
+            (args.autoplay, args.paused) = (autoplay, !autoplay);
+        }
+        args.confirm_overlap = self.confirm_overlap.unwrap_or(args.confirm_overlap);
+        args.remove_escaped = self.remove_escaped.unwrap_or(args.remove_escaped);
+        args.crop = self.crop.or(args.crop);
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let expand = |path: &PathBuf| expand_home(path, home.as_deref());
+        args.patterns_dir = self
+            .patterns_dir
+            .as_ref()
+            .map(expand)
+            .or(args.patterns_dir.take());
+        args.golly = self.golly.as_ref().map(expand).or(args.golly.take());
+        args.theme = self.theme.clone().or(args.theme.take());
+        args.colors = self.colors.or(args.colors);
+        args.renderer = self.renderer.or(args.renderer);
+        args.cell_width = self.cell_width.or(args.cell_width);
+        if let Some(mouse) = self.mouse {
+            (args.mouse, args.no_mouse) = (mouse, !mouse);
+        }
+    }
+}
+
+/// Where a layer of settings comes from, in the order they're looked at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    CommandLine,
+    Environment,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Where a setting came from in this layer, as the option, variable or
+    /// file that set it.
+    fn describe(&self, name: &str) -> String {
+        match self {
+            Source::CommandLine => format!("--{}", name),
+            Source::Environment => {
+                format!("{}{}", ENV_PREFIX, name.to_uppercase().replace('-', "_"))
+            }
+            Source::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Source::CommandLine => write!(f, "the command line"),
+            Source::Environment => write!(f, "{}* variables", ENV_PREFIX),
+            Source::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The settings from the command line, `GOL_*` environment variables and
+/// the config file, in that order, each setting what those before it leave
+/// out.
+pub fn layers(args: &Args) -> Result<Vec<(Source, Config)>, String> {
+    let mut layers = vec![
+        (Source::CommandLine, Config::from_args(args)),
+        (Source::Environment, Config::from_env(std::env::vars())?),
+    ];
+    if let Some(path) = path() {
+        layers.push((Source::File(path.clone()), Config::load_from(path)?));
+    }
+    Ok(layers)
+}
+
+/// The settings in effect, from every layer in turn.
+pub fn merge(layers: &[(Source, Config)]) -> Config {
+    layers
+        .iter()
+        .fold(Config::default(), |merged, (_, config)| {
+            merged.or(config.clone())
+        })
+}
+
+/// Writes the settings in effect as the config file takes them, with where
+/// each one came from.
+pub fn print<W: Write>(layers: &[(Source, Config)], mut output: W) -> std::io::Result<()> {
+    let tables = |config: &Config| toml::Table::try_from(config).map_err(std::io::Error::other);
+    let sources: Vec<toml::Table> = layers
+        .iter()
+        .map(|(_, config)| tables(config))
+        .collect::<Result<_, _>>()?;
+    let (sections, merged): (toml::Table, toml::Table) = tables(&merge(layers))?
+        .into_iter()
+        .partition(|(_, value)| value.is_table());
+
+    let names: Vec<String> = layers
+        .iter()
+        .map(|(source, _)| source.to_string())
+        .collect();
+    writeln!(output, "# from {}, each over the next", names.join(", "))?;
+    writeln!(output, "# anything not listed is left to its default")?;
+    for (name, value) in &merged {
+        let from = layers
+            .iter()
+            .zip(&sources)
+            .find(|(_, table)| table.contains_key(name))
+            .map(|((source, _), _)| source.describe(name))
+            .unwrap_or_default();
+        writeln!(output, "{} = {} # {}", name, value, from)?;
+    }
+    if !sections.is_empty() {
+        let sections = toml::to_string(&sections).map_err(std::io::Error::other)?;
+        write!(output, "\n{}", sections)?;
+    }
+    Ok(())
+}
+
+/// The path with a leading `~` for the home directory, as a shell would read
+/// it, which the config file doesn't get.
+fn expand_home(path: &Path, home: Option<&Path>) -> PathBuf {
+    match (path.strip_prefix("~"), home) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Where the config file is kept: `config.toml` in the config directory.
+pub fn path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+/// Where user configuration lives: `$XDG_CONFIG_HOME/game-of-life-cli`,
+/// falling back to `~/.config/game-of-life-cli`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join(APP_DIR))
+}
+
+/// Appends a rulestring to the saved rules file, returning the file's path.
+pub fn save_rule(rule: &Rule) -> std::io::Result<PathBuf> {
+    let dir = config_dir()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory"))?;
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.join(RULES_FILE);
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", rule)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(var, value)| (var.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_env() {
+        let config = Config::from_env(vars(&[
+            ("GOL_FPS", "30"),
+            ("GOL_RULE", "highlife"),
+            ("GOL_PATTERNS_DIR", "/tmp/patterns"),
+            ("GOL_BOUNDARY", "torus"),
+            ("GOL_REMOVE_ESCAPED", "true"),
+            ("GOL_DEBUG", "1"),
+            ("HOME", "/home/conway"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                fps: Some(30),
+                rule: Some(String::from("highlife")),
+                patterns_dir: Some(PathBuf::from("/tmp/patterns")),
+                boundary: Some(Boundary::Torus),
+                remove_escaped: Some(true),
+                ..Default::default()
+            }
+        );
+
+        for (var, value) in [
+            ("GOL_FPS", "fast"),
+            ("GOL_RULE", "B9"),
+            ("GOL_AUTOPLAY", "1"),
+            ("GOL_CELL_WIDTH", "3"),
+        ] {
+            let error = Config::from_env(vars(&[(var, value)])).unwrap_err();
+            assert!(error.starts_with(var), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_env_settings() {
+        let config = Config {
+            width: Some(80),
+            height: Some(40),
+            rule: Some(String::from("highlife")),
+            boundary: Some(Boundary::Torus),
+            symmetry: Some(Symmetry::FourFold),
+            engine: Some(Engine::Sparse),
+            fps: Some(30),
+            autoplay: Some(true),
+            confirm_overlap: Some(true),
+            remove_escaped: Some(true),
+            crop: Some(10),
+            patterns_dir: Some(PathBuf::from("/tmp/patterns")),
+            golly: Some(PathBuf::from("/tmp/golly")),
+            theme: Some(String::from("amber")),
+            colors: Some(ColorDepth::TrueColor),
+            renderer: Some(Renderer::Blocks),
+            cell_width: Some(2),
+            mouse: Some(true),
+            keys: BTreeMap::new(),
+            themes: BTreeMap::new(),
+        };
+        let names: Vec<String> = toml::Table::try_from(config)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let mut settings = ENV_SETTINGS.map(String::from).to_vec();
+        settings.sort();
+        assert_eq!(names, settings);
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = Some(Path::new("/home/conway"));
+        assert_eq!(
+            expand_home(Path::new("~/life/patterns"), home),
+            PathBuf::from("/home/conway/life/patterns")
+        );
+        assert_eq!(
+            expand_home(Path::new("~"), home),
+            PathBuf::from("/home/conway")
+        );
+        assert_eq!(
+            expand_home(Path::new("~life"), home),
+            PathBuf::from("~life")
+        );
+        assert_eq!(
+            expand_home(Path::new("/tmp/~"), home),
+            PathBuf::from("/tmp/~")
+        );
+        assert_eq!(
+            expand_home(Path::new("~/life"), None),
+            PathBuf::from("~/life")
+        );
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut args = Args {
+            width: Some(80),
+            paused: true,
+            ..Default::default()
+        };
+        let env = Config {
+            width: Some(60),
+            fps: Some(30),
+            autoplay: Some(true),
+            ..Default::default()
+        };
+        let file: Config = toml::from_str(
+            "width = 40\nfps = 10\nrule = \"highlife\"\nsymmetry = \"four-fold\"\n\
+             [keys]\nquit = \"ctrl+q\"\n",
+        )
+        .unwrap();
+        let layers = [
+            (Source::CommandLine, Config::from_args(&args)),
+            (Source::Environment, env),
+            (Source::File(PathBuf::from("config.toml")), file),
+        ];
+
+        let merged = merge(&layers);
+        assert_eq!(merged.keys.len(), 1);
+        merged.apply(&mut args);
+        assert_eq!((args.width, args.fps), (Some(80), Some(30)));
+        assert_eq!(args.rule, Some("B36/S23".parse().unwrap()));
+        assert_eq!(args.symmetry, Some(Symmetry::FourFold));
+        assert!(args.paused && !args.autoplay);
+
+        let mut output = Vec::new();
+        print(&layers, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("\nwidth = 80 # --width\n"));
+        assert!(output.contains("\nfps = 30 # GOL_FPS\n"));
+        assert!(output.contains("\nrule = \"highlife\" # config.toml\n"));
+        assert!(output.ends_with("[keys]\nquit = \"ctrl+q\"\n"));
+    }
+}
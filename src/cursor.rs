@@ -0,0 +1,114 @@
+use crate::{grid::BoundingBox, seed::IsSeed};
+use std::fmt::{Display, Formatter};
+
+/// How far each arrow key press moves the cursor. When snapping, the cursor
+/// lands on multiples of the step, so repeated structures line up.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Snap {
+    /// One cell at a time.
+    #[default]
+    Off,
+    /// A fixed number of cells.
+    Cells(usize),
+    /// The width or height of the selected seed.
+    SeedSize,
+}
+
+impl Display for Snap {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Snap::Off => write!(f, "off"),
+            Snap::Cells(cells) => write!(f, "{} cells", cells),
+            Snap::SeedSize => write!(f, "seed size"),
+        }
+    }
+}
+
+impl Snap {
+    /// The next setting in the cycle: off, 5 cells, 10 cells, seed size.
+    pub fn next(self) -> Snap {
+        match self {
+            Snap::Off => Snap::Cells(5),
+            Snap::Cells(5) => Snap::Cells(10),
+            Snap::Cells(_) => Snap::SeedSize,
+            Snap::SeedSize => Snap::Off,
+        }
+    }
+
+    /// The horizontal and vertical steps when `seed` is selected.
+    pub fn steps(self, seed: &dyn IsSeed) -> (usize, usize) {
+        match self {
+            Snap::Off => (1, 1),
+            Snap::Cells(cells) => (cells.max(1), cells.max(1)),
+            Snap::SeedSize => BoundingBox::of(&seed.cells((0, 0)))
+                .map_or((1, 1), |bounds| (bounds.width, bounds.height)),
+        }
+    }
+}
+
+/// Moves forward `times` steps, landing on multiples of `step`.
+pub fn forward(position: i64, step: usize, times: usize) -> i64 {
+    let step = step as i64;
+    (position.div_euclid(step) + times as i64) * step
+}
+
+/// Moves back `times` steps, landing on multiples of `step`.
+pub fn back(position: i64, step: usize, times: usize) -> i64 {
+    let step = step as i64;
+    // a position between multiples first snaps down to the one below it
+    let snapped = position.div_euclid(step) + i64::from(position.rem_euclid(step) != 0);
+    (snapped - times as i64) * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::{Seed, Spaceship, Still};
+
+    #[test]
+    fn test_unsnapped_moves() {
+        assert_eq!(forward(7, 1, 1), 8);
+        assert_eq!(forward(7, 1, 5), 12);
+        assert_eq!(back(7, 1, 5), 2);
+        assert_eq!(back(3, 1, 5), -2);
+    }
+
+    #[test]
+    fn test_snapped_moves() {
+        assert_eq!(forward(0, 5, 1), 5);
+        assert_eq!(forward(7, 5, 1), 10);
+        assert_eq!(forward(7, 5, 2), 15);
+        assert_eq!(back(10, 5, 1), 5);
+        assert_eq!(back(7, 5, 1), 5);
+        assert_eq!(back(7, 5, 5), -15);
+        assert_eq!(forward(-7, 5, 1), -5);
+        assert_eq!(back(-7, 5, 1), -10);
+    }
+
+    #[test]
+    fn test_snap_cycle() {
+        let mut snap = Snap::default();
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(snap);
+            snap = snap.next();
+        }
+
+        assert_eq!(snap, Snap::Off);
+        assert_eq!(
+            seen,
+            [Snap::Off, Snap::Cells(5), Snap::Cells(10), Snap::SeedSize]
+        );
+    }
+
+    #[test]
+    fn test_seed_size_steps() {
+        let block = Seed::Still(Still::Block);
+        let lwss = Seed::Spaceship(Spaceship::LwSpaceship);
+
+        assert_eq!(Snap::SeedSize.steps(&block), (2, 2));
+        assert_eq!(Snap::SeedSize.steps(&lwss), (5, 4));
+        assert_eq!(Snap::Cells(5).steps(&lwss), (5, 5));
+        assert_eq!(Snap::Off.steps(&lwss), (1, 1));
+    }
+}
@@ -0,0 +1,407 @@
+use crate::{
+    grid::{BoundingBox, Cell, Grid},
+    history::Snapshot,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, VecDeque},
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+/// The longest cycle that is noticed, in generations.
+pub const MAX_PERIOD: usize = 64;
+
+/// How long a pattern is run for when analyzing it, in generations.
+pub const ANALYSIS_GENERATIONS: usize = 1000;
+
+/// How a run settled down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Every cell died in this generation.
+    DiedOut { generation: usize },
+    /// The board started repeating at this generation, every `period`
+    /// generations, 1 for still lifes.
+    Stabilized { generation: usize, period: usize },
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Outcome::DiedOut { generation } => write!(f, "Died out at gen {}", generation),
+            Outcome::Stabilized {
+                generation,
+                period: 1,
+            } => write!(f, "Stabilized at gen {}", generation),
+            Outcome::Stabilized { generation, period } => {
+                write!(f, "Stabilized at gen {} with period {}", generation, period)
+            }
+        }
+    }
+}
+
+/// The fingerprint of an empty board.
+const EMPTY: u64 = 0;
+
+/// A hash of the board's live and fading cells that doesn't depend on the
+/// order they're stored in.
+pub fn fingerprint(grid: &Grid) -> u64 {
+    let cells = grid.cells.iter().map(hash);
+    let dying = grid.dying.iter().map(hash);
+    cells.chain(dying).fold(0, u64::wrapping_add)
+}
+
+/// The fingerprint of the board's shape, wherever it is, along with the
+/// top-left corner of the live cells, or of the fading ones once all are dead.
+fn shape(grid: &Grid) -> Option<(u64, Cell)> {
+    let bounds = BoundingBox::of(&grid.cells).or_else(|| BoundingBox::of(grid.dying.keys()))?;
+    let relative = |(x, y): &Cell| (x - bounds.x, y - bounds.y);
+    let cells = grid.cells.iter().map(|cell| hash(relative(cell)));
+    let dying = grid
+        .dying
+        .iter()
+        .map(|(cell, state)| hash((relative(cell), state)));
+    Some((
+        cells.chain(dying).fold(0, u64::wrapping_add),
+        (bounds.x, bounds.y),
+    ))
+}
+
+fn hash<T: Hash>(value: T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Remembers the last few generations of a run to tell when it died out or
+/// started repeating.
+#[derive(Debug, Default)]
+pub struct CycleDetector {
+    /// Fingerprints of the latest generations, newest last.
+    recent: VecDeque<u64>,
+    /// The generation the latest fingerprint is from.
+    generation: usize,
+    /// Whether the run was already reported as settled, so it isn't again
+    /// until something changes.
+    settled: bool,
+}
+
+impl CycleDetector {
+    /// Looks at the generation the grid just ticked to, returning how the run
+    /// settled the first time it does. Edits that jump to another generation,
+    /// like clearing or undoing, start over.
+    pub fn check(&mut self, grid: &Grid) -> Option<Outcome> {
+        // an empty board that stays empty, as before placing anything, didn't die out
+        let was_empty = match self.recent.back() {
+            Some(last) if grid.generation == self.generation + 1 => *last == EMPTY,
+            _ => grid.previous.is_empty(),
+        };
+        if grid.generation != self.generation + 1 {
+            self.recent.clear();
+            self.settled = false;
+        }
+        self.generation = grid.generation;
+
+        let fingerprint = fingerprint(grid);
+        let period = self
+            .recent
+            .iter()
+            .rev()
+            .position(|recent| *recent == fingerprint)
+            .map(|index| index + 1);
+
+        if self.recent.len() == MAX_PERIOD {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(fingerprint);
+
+        let outcome = match period {
+            _ if fingerprint == EMPTY && was_empty => return None,
+            _ if fingerprint == EMPTY => Outcome::DiedOut {
+                generation: grid.generation,
+            },
+            Some(period) => Outcome::Stabilized {
+                generation: grid.generation - period,
+                period,
+            },
+            None => {
+                self.settled = false;
+                return None;
+            }
+        };
+        match std::mem::replace(&mut self.settled, true) {
+            true => None,
+            false => Some(outcome),
+        }
+    }
+}
+
+/// What a pattern turns out to be when run on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Analysis {
+    /// Comes back to the same shape every `period` generations, moved by
+    /// `displacement`, from generation `from` on.
+    Repeats {
+        from: usize,
+        period: usize,
+        displacement: (i64, i64),
+    },
+    /// Every cell is dead by this generation.
+    DiesOut { generation: usize },
+    /// Still changing after [`ANALYSIS_GENERATIONS`].
+    Unsettled,
+}
+
+/// Runs a copy of the grid, with its rule and edges, until its shape repeats
+/// or it dies out.
+pub fn analyze(grid: &Grid) -> Analysis {
+    let mut copy = Grid::new(grid.width, grid.height);
+    copy.rule = grid.rule;
+    copy.boundary = grid.boundary;
+    copy.engine = grid.engine;
+    Snapshot::of(grid).restore(&mut copy);
+    copy.generation = 0;
+
+    let mut seen: HashMap<u64, (usize, Cell)> = HashMap::new();
+    for generation in 0..=ANALYSIS_GENERATIONS {
+        if generation > 0 {
+            copy.tick();
+        }
+        let Some((fingerprint, corner)) = shape(&copy) else {
+            return Analysis::DiesOut { generation };
+        };
+        if let Some(&(from, before)) = seen.get(&fingerprint) {
+            return Analysis::Repeats {
+                from,
+                period: generation - from,
+                displacement: (corner.0 - before.0, corner.1 - before.1),
+            };
+        }
+        seen.insert(fingerprint, (generation, corner));
+    }
+    Analysis::Unsettled
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+/// A speed as a fraction of the speed of light, reduced, as in `c/4` or `2c/5`.
+fn speed(cells: usize, period: usize) -> String {
+    let divisor = gcd(cells, period);
+    match (cells / divisor, period / divisor) {
+        (1, 1) => String::from("c"),
+        (1, period) => format!("c/{}", period),
+        (cells, 1) => format!("{}c", cells),
+        (cells, period) => format!("{}c/{}", cells, period),
+    }
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let (from, period, (dx, dy)) = match *self {
+            Analysis::DiesOut { generation } => {
+                return write!(f, "Dies out by gen {}", generation);
+            }
+            Analysis::Unsettled => {
+                return write!(f, "No period within {} gens", ANALYSIS_GENERATIONS);
+            }
+            Analysis::Repeats {
+                from,
+                period,
+                displacement,
+            } => (from, period, displacement),
+        };
+
+        let (long, short) = (dx.abs().max(dy.abs()), dx.abs().min(dy.abs()));
+        match (period, long, short) {
+            (1, 0, _) => write!(f, "Still life")?,
+            (_, 0, _) => write!(f, "Period-{} oscillator", period)?,
+            (_, _, 0) => write!(
+                f,
+                "{} orthogonal spaceship, period {}",
+                speed(long as usize, period),
+                period
+            )?,
+            _ if long == short => write!(
+                f,
+                "{} diagonal spaceship, period {}",
+                speed(long as usize, period),
+                period
+            )?,
+            _ => write!(f, "({},{})c/{} oblique spaceship", long, short, period)?,
+        }
+        if long > 0 {
+            write!(f, ", moving {},{}", dx, dy)?;
+        }
+        if from > 0 {
+            write!(f, ", from gen {}", from)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::{Oscillator, Spaceship, Still};
+
+    fn run(grid: &mut Grid, detector: &mut CycleDetector, ticks: usize) -> Vec<Outcome> {
+        (0..ticks)
+            .filter_map(|_| {
+                grid.tick();
+                detector.check(grid)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stabilizes() {
+        let mut grid = Grid::new(20, 20);
+        let mut detector = CycleDetector::default();
+        grid.seed(Oscillator::Blinker, (4, 4));
+
+        // generation 2 is the same as generation 0, which wasn't seen
+        assert_eq!(
+            run(&mut grid, &mut detector, 10),
+            vec![Outcome::Stabilized {
+                generation: 1,
+                period: 2
+            }]
+        );
+    }
+
+    #[test]
+    fn test_still_life() {
+        // a pre-block becomes a block
+        let mut grid = Grid::new(20, 20);
+        let mut detector = CycleDetector::default();
+        for cell in [(4, 4), (5, 4), (4, 5)] {
+            grid.add_cell(cell);
+        }
+
+        let outcomes = run(&mut grid, &mut detector, 10);
+        assert_eq!(
+            outcomes,
+            vec![Outcome::Stabilized {
+                generation: 1,
+                period: 1
+            }]
+        );
+        assert_eq!(outcomes[0].to_string(), "Stabilized at gen 1");
+    }
+
+    #[test]
+    fn test_dies_out() {
+        let mut grid = Grid::new(20, 20);
+        let mut detector = CycleDetector::default();
+        grid.add_cell((4, 4));
+        grid.add_cell((5, 4));
+
+        let outcomes = run(&mut grid, &mut detector, 10);
+        assert_eq!(outcomes, vec![Outcome::DiedOut { generation: 1 }]);
+        assert_eq!(outcomes[0].to_string(), "Died out at gen 1");
+
+        // nothing to report for a board that was empty all along
+        grid.clear();
+        assert!(run(&mut grid, &mut detector, 10).is_empty());
+    }
+
+    #[test]
+    fn test_fading_cells_die_out_last() {
+        let mut grid = Grid::new(20, 20);
+        let mut detector = CycleDetector::default();
+        grid.rule = "B2/S/C3".parse().unwrap();
+        grid.add_cell((4, 4));
+
+        // the cell dies, then fades out the tick after
+        assert_eq!(
+            run(&mut grid, &mut detector, 10),
+            vec![Outcome::DiedOut { generation: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_starts_over_after_edits() {
+        let mut grid = Grid::new(20, 20);
+        let mut detector = CycleDetector::default();
+        grid.seed(Oscillator::Blinker, (4, 4));
+        assert_eq!(run(&mut grid, &mut detector, 10).len(), 1);
+
+        // clearing goes back to generation 0, so the new run is watched anew
+        grid.clear();
+        grid.seed(Oscillator::Blinker, (10, 10));
+        assert_eq!(run(&mut grid, &mut detector, 10).len(), 1);
+    }
+
+    fn analyze_seed(seed: impl crate::seed::IsSeed) -> Analysis {
+        let mut grid = Grid::new(40, 40);
+        grid.seed(seed, (10, 10));
+        analyze(&grid)
+    }
+
+    #[test]
+    fn test_analyze() {
+        assert_eq!(analyze_seed(Still::Beehive).to_string(), "Still life");
+        assert_eq!(
+            analyze_seed(Oscillator::Pulsar).to_string(),
+            "Period-3 oscillator"
+        );
+        assert_eq!(
+            analyze_seed(Oscillator::PentaDecathlon).to_string(),
+            "Period-15 oscillator"
+        );
+
+        let glider = analyze_seed(Spaceship::Glider);
+        assert!(matches!(glider, Analysis::Repeats { period: 4, .. }));
+        assert!(glider
+            .to_string()
+            .starts_with("c/4 diagonal spaceship, period 4, moving"));
+        assert!(analyze_seed(Spaceship::LwSpaceship)
+            .to_string()
+            .starts_with("c/2 orthogonal spaceship, period 4"));
+    }
+
+    #[test]
+    fn test_analyze_settling() {
+        // a pre-block becomes a block after a generation
+        let mut grid = Grid::new(20, 20);
+        for cell in [(4, 4), (5, 4), (4, 5)] {
+            grid.add_cell(cell);
+        }
+        assert_eq!(analyze(&grid).to_string(), "Still life, from gen 1");
+
+        let mut grid = Grid::new(20, 20);
+        grid.add_cell((4, 4));
+        assert_eq!(analyze(&grid), Analysis::DiesOut { generation: 1 });
+
+        // the grid itself is left alone
+        assert_eq!(grid.generation, 0);
+        assert_eq!(grid.cells.len(), 1);
+    }
+
+    #[test]
+    fn test_speed() {
+        assert_eq!(speed(1, 4), "c/4");
+        assert_eq!(speed(2, 4), "c/2");
+        assert_eq!(speed(2, 5), "2c/5");
+        assert_eq!(speed(3, 3), "c");
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_order() {
+        let mut a = Grid::new(10, 10);
+        let mut b = Grid::new(10, 10);
+        for cell in [(1, 1), (2, 2), (3, 3)] {
+            a.add_cell(cell);
+        }
+        for cell in [(3, 3), (1, 1), (2, 2)] {
+            b.add_cell(cell);
+        }
+        assert_eq!(fingerprint(&a), fingerprint(&b));
+        b.add_cell((4, 4));
+        assert_ne!(fingerprint(&a), fingerprint(&b));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{
+    grid::{BoundingBox, Cell},
+    rule::Rule,
+};
+use std::collections::HashSet;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A rectangle of cells packed into rows of `u64` words, one bit per cell,
+/// with the lowest bit of each word being its leftmost cell.
+#[derive(Debug)]
+struct Bitboard {
+    bounds: BoundingBox,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl Bitboard {
+    fn new(bounds: BoundingBox) -> Bitboard {
+        let words_per_row = bounds.width.div_ceil(WORD_BITS);
+        Bitboard {
+            bounds,
+            words_per_row,
+            words: vec![0; words_per_row * bounds.height],
+        }
+    }
+
+    fn from_cells(cells: &HashSet<Cell>, bounds: BoundingBox) -> Bitboard {
+        let mut board = Bitboard::new(bounds);
+        for cell in cells.iter().filter(|cell| bounds.contains(cell)) {
+            let (x, y) = ((cell.0 - bounds.x) as usize, (cell.1 - bounds.y) as usize);
+            board.words[y * board.words_per_row + x / WORD_BITS] |= 1 << (x % WORD_BITS);
+        }
+        board
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Marks the columns that are part of the rectangle, leaving out the
+    /// padding at the end of the last word.
+    fn column_mask(&self, word: usize) -> u64 {
+        let columns = self.bounds.width - word * WORD_BITS;
+        match columns >= WORD_BITS {
+            true => u64::MAX,
+            false => (1 << columns) - 1,
+        }
+    }
+
+    fn cells(&self) -> Vec<Cell> {
+        let mut cells = Vec::new();
+        for y in 0..self.bounds.height {
+            for (index, &word) in self.row(y).iter().enumerate() {
+                let mut word = word;
+                while word != 0 {
+                    let bit = word.trailing_zeros() as usize;
+                    cells.push((
+                        self.bounds.x + (index * WORD_BITS + bit) as i64,
+                        self.bounds.y + y as i64,
+                    ));
+                    word &= word - 1;
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Adds a one-bit plane to a four-bit counter stored as planes, lowest bit
+/// first, with a ripple of half adders.
+#[inline]
+fn add(counter: &mut [u64; 4], plane: u64) {
+    let mut carry = plane;
+    for bit in counter.iter_mut() {
+        let next = *bit & carry;
+        *bit ^= carry;
+        carry = next;
+    }
+}
+
+/// The cells whose neighbor count is exactly `count`.
+#[inline]
+fn equals(counter: &[u64; 4], count: usize) -> u64 {
+    (0..4).fold(u64::MAX, |matches, bit| match count >> bit & 1 {
+        1 => matches & counter[bit],
+        _ => matches & !counter[bit],
+    })
+}
+
+/// Computes the next generation of `cells` with bitwise arithmetic, 64 cells
+/// at a time. Only the live cells' bounding box and a one-cell margin are
+/// looked at, except for rules with B0, whose births far from any live cell
+/// are limited to the `board`, like the sparse engine.
+pub fn tick(cells: &HashSet<Cell>, rule: &Rule, board: BoundingBox) -> Vec<Cell> {
+    let bounds = match (BoundingBox::of(cells), rule.is_born(0)) {
+        (Some(live), true) => union(live.pad(1), board),
+        (Some(live), false) => live.pad(1),
+        (None, true) => board,
+        (None, false) => return Vec::new(),
+    };
+
+    let current = Bitboard::from_cells(cells, bounds);
+    let mut next = Bitboard::new(bounds);
+
+    let words_per_row = current.words_per_row;
+    let empty = vec![0; words_per_row];
+
+    for y in 0..bounds.height {
+        let above = match y {
+            0 => &empty[..],
+            _ => current.row(y - 1),
+        };
+        let below = match y + 1 < bounds.height {
+            true => current.row(y + 1),
+            false => &empty[..],
+        };
+        let middle = current.row(y);
+        let board_row = board.contains(&(board.x, bounds.y + y as i64));
+
+        for word in 0..words_per_row {
+            let mut counter = [0; 4];
+            for (row, center) in [(above, true), (middle, false), (below, true)] {
+                let previous = match word {
+                    0 => 0,
+                    _ => row[word - 1],
+                };
+                let following = row.get(word + 1).copied().unwrap_or_default();
+                // shifting towards the high bits moves each cell's left neighbor onto it
+                add(&mut counter, row[word] << 1 | previous >> (WORD_BITS - 1));
+                add(&mut counter, row[word] >> 1 | following << (WORD_BITS - 1));
+                if center {
+                    add(&mut counter, row[word]);
+                }
+            }
+
+            let alive = middle[word];
+            let mut result = 0;
+            for count in 0..9 {
+                if rule.is_born(count) {
+                    let mut births = !alive & equals(&counter, count);
+                    if count == 0 {
+                        births &= match board_row {
+                            true => board_columns(bounds, board, word),
+                            false => 0,
+                        };
+                    }
+                    result |= births;
+                }
+                if rule.survives(count) {
+                    result |= alive & equals(&counter, count);
+                }
+            }
+
+            next.words[y * words_per_row + word] = result & current.column_mask(word);
+        }
+    }
+
+    next.cells()
+}
+
+/// The columns of `word` in `bounds` that fall inside the `board`.
+fn board_columns(bounds: BoundingBox, board: BoundingBox, word: usize) -> u64 {
+    let start = bounds.x + (word * WORD_BITS) as i64;
+    (0..WORD_BITS)
+        .filter(|&bit| board.contains(&(start + bit as i64, board.y)))
+        .fold(0, |mask, bit| mask | 1 << bit)
+}
+
+/// The smallest box holding both.
+fn union(a: BoundingBox, b: BoundingBox) -> BoundingBox {
+    let (x, y) = (a.x.min(b.x), a.y.min(b.y));
+    BoundingBox {
+        x,
+        y,
+        width: ((a.x + a.width as i64).max(b.x + b.width as i64) - x) as usize,
+        height: ((a.y + a.height as i64).max(b.y + b.height as i64) - y) as usize,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        grid::{Boundary, Engine, Grid},
+        soup::Soup,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Runs the same soup with both engines and checks every generation matches.
+    fn assert_engines_agree(rule: &str, boundary: Boundary) {
+        let mut rng = StdRng::seed_from_u64(1);
+        let soup = Soup::random(&mut rng, 70, 20, 0.4);
+        let mut grids = [Engine::Sparse, Engine::Dense].map(|engine| {
+            let mut grid = Grid::new(80, 30);
+            grid.engine = engine;
+            grid.boundary = boundary;
+            grid.rule = rule.parse().unwrap();
+            grid.seed(soup.clone(), (0, 5));
+            grid
+        });
+
+        for generation in 0..30 {
+            for grid in grids.iter_mut() {
+                grid.tick();
+            }
+            assert_eq!(
+                grids[0].cells, grids[1].cells,
+                "{} differs at generation {}",
+                rule, generation
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_sparse_engine() {
+        assert_engines_agree("B3/S23", Boundary::Open);
+        assert_engines_agree("B3/S23", Boundary::Dead);
+        assert_engines_agree("B36/S23", Boundary::Open);
+        assert_engines_agree("B2/S", Boundary::Open);
+        assert_engines_agree("B0123478/S34678", Boundary::Dead);
+        assert_engines_agree("B2/S/C3", Boundary::Open);
+    }
+
+    #[test]
+    fn test_blinker_across_word_boundary() {
+        let cells = HashSet::from([(63, 10), (64, 10), (65, 10)]);
+        let board = BoundingBox {
+            x: 0,
+            y: 0,
+            width: 100,
+            height: 100,
+        };
+
+        let mut next = tick(&cells, &Rule::default(), board);
+        next.sort();
+        assert_eq!(next, vec![(64, 9), (64, 10), (64, 11)]);
+    }
+
+    #[test]
+    fn test_empty() {
+        let board = BoundingBox {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 10,
+        };
+        assert!(tick(&HashSet::new(), &Rule::default(), board).is_empty());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::grid::{Boundary, BoundingBox, Cell};
+use std::collections::HashSet;
+
+/// Computes the next generation of the elementary automaton with this
+/// Wolfram code. The lowest row with live cells is the latest generation,
+/// and the next one is written on the row below, scrolling every row up
+/// once the board's bottom is reached, so the top one is dropped.
+///
+/// The row reaches one cell past its live cells, except for odd codes, whose
+/// births far from any live cell are limited to the `board`, like B0 rules.
+/// When the `boundary` wraps, the row's ends meet instead.
+pub fn tick(
+    cells: &HashSet<Cell>,
+    number: u8,
+    board: BoundingBox,
+    boundary: Boundary,
+) -> Vec<Cell> {
+    let Some(row) = cells.iter().map(|cell| cell.1).max() else {
+        return Vec::new();
+    };
+    let alive: HashSet<i64> = cells
+        .iter()
+        .filter(|cell| cell.1 == row)
+        .map(|cell| cell.0)
+        .collect();
+
+    let (board_left, board_right) = (board.x, board.x + board.width as i64 - 1);
+    let (left, right) = match boundary.wraps() {
+        true => (board_left, board_right),
+        false => {
+            let left = alive.iter().min().copied().unwrap_or(board_left) - 1;
+            let right = alive.iter().max().copied().unwrap_or(board_right) + 1;
+            match number & 1 {
+                1 => (left.min(board_left), right.max(board_right)),
+                _ => (left, right),
+            }
+        }
+    };
+    let wrap = |x: i64| match boundary.wraps() {
+        true => board_left + (x - board_left).rem_euclid(board.width as i64),
+        false => x,
+    };
+
+    // the left neighbor is the highest bit of the three
+    let next = (left..=right).filter(|&x| {
+        let neighborhood = (alive.contains(&wrap(x - 1)) as u8) << 2
+            | (alive.contains(&x) as u8) << 1
+            | alive.contains(&wrap(x + 1)) as u8;
+        number >> neighborhood & 1 == 1
+    });
+
+    let bottom = board.y + board.height as i64 - 1;
+    let scroll = (row + 1 - bottom).max(0);
+    cells
+        .iter()
+        .map(|&(x, y)| (x, y - scroll))
+        .filter(|cell| scroll == 0 || cell.1 >= board.y)
+        .chain(next.map(|x| (x, row + 1 - scroll)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn board(width: usize, height: usize) -> BoundingBox {
+        BoundingBox {
+            x: 0,
+            y: 0,
+            width,
+            height,
+        }
+    }
+
+    fn sorted(mut cells: Vec<Cell>) -> Vec<Cell> {
+        cells.sort_by_key(|&(x, y)| (y, x));
+        cells
+    }
+
+    #[test]
+    fn test_rule_90_draws_a_sierpinski_triangle() {
+        let mut cells = HashSet::from([(5, 0)]);
+        for _ in 0..3 {
+            cells = tick(&cells, 90, board(11, 10), Boundary::Open)
+                .into_iter()
+                .collect();
+        }
+
+        let rows: Vec<String> = (0..4)
+            .map(|y| {
+                (0..11)
+                    .map(|x| match cells.contains(&(x, y)) {
+                        true => 'O',
+                        false => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![".....O.....", "....O.O....", "...O...O...", "..O.O.O.O.."]
+        );
+    }
+
+    #[test]
+    fn test_rule_30() {
+        let cells = HashSet::from([(5, 0)]);
+        let next = tick(&cells, 30, board(11, 10), Boundary::Open);
+        assert_eq!(sorted(next), vec![(5, 0), (4, 1), (5, 1), (6, 1)]);
+    }
+
+    #[test]
+    fn test_scrolls_at_the_bottom() {
+        let cells = HashSet::from([(2, 0), (2, 1), (2, 2)]);
+        // rule 204 copies each cell down
+        let next = tick(&cells, 204, board(5, 3), Boundary::Open);
+        assert_eq!(sorted(next), vec![(2, 0), (2, 1), (2, 2)]);
+
+        let cells = HashSet::from([(1, 0), (2, 2)]);
+        let next = tick(&cells, 204, board(5, 3), Boundary::Open);
+        assert_eq!(sorted(next), vec![(2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_odd_codes_fill_the_board() {
+        // rule 1 brings a cell to life only when all three above are dead
+        let cells = HashSet::from([(2, 0)]);
+        let next = tick(&cells, 1, board(5, 3), Boundary::Open);
+        assert_eq!(sorted(next), vec![(2, 0), (0, 1), (4, 1)]);
+    }
+
+    #[test]
+    fn test_wrapping_row() {
+        // rule 2 moves each cell one to the left
+        let cells = HashSet::from([(0, 0)]);
+        let next = tick(&cells, 2, board(5, 3), Boundary::Torus);
+        assert_eq!(sorted(next), vec![(0, 0), (4, 1)]);
+
+        let next = tick(&cells, 2, board(5, 3), Boundary::Open);
+        assert_eq!(sorted(next), vec![(0, 0), (-1, 1)]);
+    }
+
+    #[test]
+    fn test_grid_runs_wolfram_rules() {
+        let mut grid = Grid::new(9, 4);
+        grid.rule = "wolfram:110".parse().unwrap();
+        grid.add_cell((7, 0));
+        for _ in 0..5 {
+            grid.tick();
+        }
+
+        // generations 2 to 5, after the first two scrolled away
+        assert_eq!(grid.cells.len(), 3 + 3 + 5 + 3);
+        assert!(grid.cells.iter().all(|cell| (0..4).contains(&cell.1)));
+        // rule 110 grows to the left
+        assert_eq!(
+            sorted(
+                grid.cells
+                    .iter()
+                    .filter(|cell| cell.1 == 3)
+                    .copied()
+                    .collect()
+            ),
+            vec![(2, 3), (3, 3), (7, 3)]
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{
+    census,
+    cycle::{self, Analysis},
+    grid::{BoundingBox, Cell, Grid},
+    seed::{Seed, Spaceship},
+};
+use std::collections::{HashMap, HashSet};
+
+/// The spaceships looked for, the ones guns and soups send out.
+const SPACESHIPS: [Spaceship; 4] = [
+    Spaceship::Glider,
+    Spaceship::LwSpaceship,
+    Spaceship::MwSpaceship,
+    Spaceship::HwSpaceship,
+];
+
+/// How far a spaceship has to be from every other live cell to have escaped.
+pub const ESCAPE_DISTANCE: usize = 10;
+
+/// The period of every spaceship looked for. They're only looked for every
+/// this many generations, so each one is seen in the same phase every time.
+const PERIOD: usize = 4;
+
+/// Counts the spaceships that fly away from the rest of the board, like the
+/// output of a gun or the gliders a soup throws off, and optionally takes
+/// them off the board before they wrap around or reach the edges.
+#[derive(Debug)]
+pub struct EscapeTracker {
+    /// The canonical shape of every phase of the spaceships looked for.
+    catalog: HashMap<Vec<Cell>, &'static str>,
+    /// The path of each spaceship counted, as where it would have been at
+    /// generation 0 and how far it moves every period, to count it once.
+    seen: HashSet<(Cell, Cell)>,
+    counts: HashMap<&'static str, usize>,
+    /// The generation the grid was last at.
+    generation: usize,
+    /// Whether escaped spaceships are removed from the board.
+    pub remove: bool,
+}
+
+impl Default for EscapeTracker {
+    fn default() -> Self {
+        EscapeTracker::new(false)
+    }
+}
+
+impl EscapeTracker {
+    pub fn new(remove: bool) -> EscapeTracker {
+        let names: Vec<&str> = SPACESHIPS
+            .iter()
+            .map(|ship| Seed::Spaceship(*ship).name())
+            .collect();
+        EscapeTracker {
+            catalog: census::catalog()
+                .into_iter()
+                .filter(|(_, name)| names.contains(name))
+                .collect(),
+            seen: HashSet::new(),
+            counts: HashMap::new(),
+            generation: 0,
+            remove,
+        }
+    }
+
+    /// Looks at the generation the grid just ticked to, returning the names
+    /// of the spaceships that escaped since the last time, and removing them
+    /// if asked to. Edits that jump to another generation, like clearing or
+    /// undoing, start counting over.
+    pub fn check(&mut self, grid: &mut Grid) -> Vec<&'static str> {
+        if grid.generation != self.generation + 1 {
+            self.seen.clear();
+            self.counts.clear();
+        }
+        self.generation = grid.generation;
+        if !grid.generation.is_multiple_of(PERIOD) {
+            return Vec::new();
+        }
+
+        let periods = (grid.generation / PERIOD) as i64;
+        let mut escaped = Vec::new();
+        for (name, ship, (dx, dy)) in escapees(grid, &self.catalog) {
+            let bounds = BoundingBox::of(&ship).unwrap();
+            let start = (bounds.x - dx * periods, bounds.y - dy * periods);
+            if self.remove {
+                for cell in &ship {
+                    grid.remove_cell(cell);
+                }
+            }
+            if self.seen.insert((start, (dx, dy))) {
+                *self.counts.entry(name).or_default() += 1;
+                escaped.push(name);
+            }
+        }
+        escaped
+    }
+
+    /// How many spaceships escaped in all.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// How many of each spaceship escaped, most first.
+    pub fn counts(&self) -> Vec<(&'static str, usize)> {
+        let mut counts: Vec<(&'static str, usize)> = self
+            .counts
+            .iter()
+            .map(|(name, count)| (*name, *count))
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+}
+
+/// The spaceships far from every other live cell and heading away from the
+/// middle of them, with their names, cells and how far they move every
+/// period. Spaceships flying the same way, like a gun's stream of gliders,
+/// don't hold each other back.
+fn escapees(
+    grid: &Grid,
+    catalog: &HashMap<Vec<Cell>, &'static str>,
+) -> Vec<(&'static str, Vec<Cell>, Cell)> {
+    let mut ships = Vec::new();
+    for group in census::components(&grid.cells, 2) {
+        let Some(name) = catalog.get(&census::canonical(&group)) else {
+            continue;
+        };
+        // run on its own to tell which way it's going
+        let mut alone = Grid::new(grid.width, grid.height);
+        alone.rule = grid.rule;
+        for cell in &group {
+            alone.add_cell(*cell);
+        }
+        if let Analysis::Repeats {
+            period: PERIOD,
+            displacement,
+            ..
+        } = cycle::analyze(&alone)
+        {
+            ships.push((*name, group, displacement));
+        }
+    }
+
+    let sum = |cells: &mut dyn Iterator<Item = &Cell>| {
+        cells.fold((0, 0), |(x, y), cell| (x + cell.0, y + cell.1))
+    };
+    let mut escapees = Vec::new();
+    for (name, group, (dx, dy)) in &ships {
+        let fleet: HashSet<Cell> = ships
+            .iter()
+            .filter(|(_, _, displacement)| *displacement == (*dx, *dy))
+            .flat_map(|(_, cells, _)| cells.iter().copied())
+            .collect();
+        let others: Vec<Cell> = grid.cells.difference(&fleet).copied().collect();
+
+        let surroundings = BoundingBox::of(group).unwrap().pad(ESCAPE_DISTANCE);
+        if others.iter().any(|cell| surroundings.contains(cell)) {
+            continue;
+        }
+
+        // from the middle of the others towards the ship, scaled up by both
+        // populations to stay in whole numbers
+        let (size, rest) = (group.len() as i64, others.len() as i64);
+        let (ship, rest_sum) = (sum(&mut group.iter()), sum(&mut others.iter()));
+        let away = (
+            ship.0 * rest - rest_sum.0 * size,
+            ship.1 * rest - rest_sum.1 * size,
+        );
+        if rest > 0 && away.0 * dx + away.1 * dy <= 0 {
+            continue;
+        }
+        escapees.push((*name, group.clone(), (*dx, *dy)));
+    }
+    escapees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::Still;
+
+    fn run(grid: &mut Grid, tracker: &mut EscapeTracker, ticks: usize) -> Vec<&'static str> {
+        (0..ticks)
+            .flat_map(|_| {
+                grid.tick();
+                tracker.check(grid)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_counts_once() {
+        // a glider flying down and left, away from a block
+        let mut grid = Grid::new(80, 80);
+        let mut tracker = EscapeTracker::default();
+        grid.seed(Still::Block, (40, 0));
+        grid.seed(Spaceship::Glider, (20, 20));
+
+        assert_eq!(run(&mut grid, &mut tracker, 40), vec!["Glider"]);
+        assert_eq!(tracker.counts(), vec![("Glider", 1)]);
+        assert_eq!(grid.cells.len(), 9);
+
+        // clearing starts over
+        grid.clear();
+        assert!(run(&mut grid, &mut tracker, 4).is_empty());
+        assert_eq!(tracker.total(), 0);
+    }
+
+    #[test]
+    fn test_incoming() {
+        // the same glider with a block in its way isn't escaping
+        let mut grid = Grid::new(80, 80);
+        let mut tracker = EscapeTracker::default();
+        grid.seed(Still::Block, (0, 40));
+        grid.seed(Spaceship::Glider, (20, 20));
+
+        assert!(run(&mut grid, &mut tracker, 40).is_empty());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut grid = Grid::new(80, 80);
+        let mut tracker = EscapeTracker::new(true);
+        // an LWSS flying left, away from a block
+        grid.seed(Still::Block, (40, 20));
+        grid.seed(Spaceship::LwSpaceship, (20, 20));
+
+        assert_eq!(run(&mut grid, &mut tracker, 8), vec!["LWSS"]);
+        assert_eq!(grid.cells.len(), 4);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{grid::Grid, rule::Rule, soup::Soup};
+use rand::Rng;
+
+const BOARD_SIZE: usize = 48;
+const SOUP_SIZE: usize = 12;
+const SOUP_DENSITY: f64 = 0.5;
+/// How long each candidate rule is run for.
+const GENERATIONS: usize = 100;
+/// Populations past this are considered explosive and end the run early.
+const MAX_POPULATION: usize = 400;
+/// Random rules tried per search before settling for the best one seen.
+const ATTEMPTS: usize = 20;
+/// Scores at or above this end a search right away.
+const THRESHOLD: f64 = 0.4;
+
+/// How a soup behaved under a rule.
+#[derive(Debug, Clone, Copy)]
+pub struct Score {
+    /// Generations the soup kept changing before it died out or settled.
+    pub lifetime: usize,
+    /// Final population relative to the initial one.
+    pub growth: f64,
+    pub exploded: bool,
+}
+
+impl Score {
+    /// Rates the run from 0 to 1, favoring long activity at a stable population.
+    pub fn interestingness(&self) -> f64 {
+        if self.exploded {
+            return 0.0;
+        }
+
+        let activity = self.lifetime as f64 / GENERATIONS as f64;
+        let balance = 1.0 / (1.0 + self.growth.ln().abs());
+        activity * balance
+    }
+}
+
+/// A rule picked by [`search`], along with how it scored.
+#[derive(Debug, Clone, Copy)]
+pub struct Candidate {
+    pub rule: Rule,
+    pub score: Score,
+}
+
+/// Generates a random rule. B0 and B1 are left out since they fill the board.
+pub fn random_rule<R: Rng>(rng: &mut R) -> Rule {
+    loop {
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+            states: 2,
+            larger: None,
+            wireworld: false,
+            wolfram: None,
+            colors: 1,
+        };
+        for count in 2..9 {
+            rule.birth[count] = rng.gen_bool(0.35);
+        }
+        for count in 0..9 {
+            rule.survival[count] = rng.gen_bool(0.35);
+        }
+
+        if rule.birth.contains(&true) {
+            return rule;
+        }
+    }
+}
+
+/// Runs a random soup under the rule on a small off-screen board.
+pub fn score<R: Rng>(rule: Rule, rng: &mut R) -> Score {
+    let mut grid = Grid::new(BOARD_SIZE, BOARD_SIZE);
+    grid.rule = rule;
+
+    let corner = ((BOARD_SIZE - SOUP_SIZE) / 2) as i64;
+    grid.seed(
+        Soup::random(rng, SOUP_SIZE, SOUP_SIZE, SOUP_DENSITY),
+        (corner, corner),
+    );
+
+    let initial = grid.cells.len().max(1) as f64;
+    let mut lifetime = 0;
+
+    for generation in 1..=GENERATIONS {
+        let two_back = std::mem::take(&mut grid.previous);
+        grid.tick();
+
+        if grid.cells.len() > MAX_POPULATION {
+            return Score {
+                lifetime: generation,
+                growth: grid.cells.len() as f64 / initial,
+                exploded: true,
+            };
+        }
+
+        // extinct, still, or blinking with period 2
+        if grid.cells.is_empty() || grid.cells == grid.previous || grid.cells == two_back {
+            break;
+        }
+
+        lifetime = generation;
+    }
+
+    Score {
+        lifetime,
+        growth: grid.cells.len() as f64 / initial,
+        exploded: false,
+    }
+}
+
+/// Picks any random rule, however it scores.
+pub fn roll<R: Rng>(rng: &mut R) -> Candidate {
+    let rule = random_rule(rng);
+    Candidate {
+        rule,
+        score: score(rule, rng),
+    }
+}
+
+/// Tries random rules until one scores above the threshold, or returns the best seen.
+pub fn search<R: Rng>(rng: &mut R) -> Candidate {
+    let mut best: Option<Candidate> = None;
+
+    for _ in 0..ATTEMPTS {
+        let candidate = roll(rng);
+
+        if candidate.score.interestingness() >= THRESHOLD {
+            return candidate;
+        }
+
+        match &best {
+            Some(best) if best.score.interestingness() >= candidate.score.interestingness() => {}
+            _ => best = Some(candidate),
+        }
+    }
+
+    best.expect("at least one attempt is made")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_random_rule_can_grow() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..100 {
+            let rule = random_rule(&mut rng);
+            assert!(!rule.birth[0] && !rule.birth[1]);
+            assert!(rule.birth.contains(&true));
+        }
+    }
+
+    #[test]
+    fn test_score_conway() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let score = score(Rule::default(), &mut rng);
+
+        assert!(!score.exploded);
+        assert!(score.lifetime > 0);
+        assert!(score.interestingness() > 0.0);
+    }
+
+    #[test]
+    fn test_score_explosive_rule() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut rule = Rule::default();
+        rule.birth[2] = true; // B23/S2345678 fills the board quickly
+        rule.survival = [false, false, true, true, true, true, true, true, true];
+
+        let score = score(rule, &mut rng);
+        assert!(score.exploded);
+        assert_eq!(score.interestingness(), 0.0);
+    }
+
+    #[test]
+    fn test_score_dead_rule() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let rule = Rule {
+            birth: [false, false, false, false, false, false, false, false, true],
+            survival: [false; 9],
+            states: 2,
+            larger: None,
+            wireworld: false,
+            wolfram: None,
+            colors: 1,
+        };
+
+        let score = score(rule, &mut rng);
+        assert_eq!(score.growth, 0.0);
+        assert_eq!(score.interestingness(), 0.0);
+    }
+}
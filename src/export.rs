@@ -0,0 +1,365 @@
+use crate::{
+    compress,
+    grid::{BoundingBox, Cell, Grid, ALIVE_CELL, DEAD_CELL},
+    pattern::Pattern,
+};
+use gif::{Encoder, Frame, Repeat};
+use std::{
+    collections::{HashSet, VecDeque},
+    io::Write,
+    path::Path,
+};
+
+/// How many generations the instant replay keeps.
+pub const INSTANT_REPLAY_LENGTH: usize = 300;
+/// Side of each cell in exported images, in pixels.
+const CELL_PIXELS: usize = 4;
+/// Dead cells are white and live cells are black, like the board.
+const PALETTE: [u8; 6] = [0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00];
+
+/// The most recent generations, oldest first, so a run can be exported
+/// after something interesting has already happened.
+#[derive(Debug)]
+pub struct InstantReplay {
+    frames: VecDeque<HashSet<Cell>>,
+    capacity: usize,
+}
+
+impl Default for InstantReplay {
+    fn default() -> Self {
+        InstantReplay::new(INSTANT_REPLAY_LENGTH)
+    }
+}
+
+impl InstantReplay {
+    pub fn new(capacity: usize) -> InstantReplay {
+        InstantReplay {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Keeps a copy of a generation, dropping the oldest one when full.
+    pub fn record(&mut self, cells: &HashSet<Cell>) {
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(cells.clone());
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = &HashSet<Cell>> {
+        self.frames.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+/// File formats the board can be exported to, besides the instant replay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// `.rle`, readable by Golly and by `--pattern`.
+    Rle,
+    /// `.cells`, plaintext that's easy to edit by hand.
+    Cells,
+    /// `.lif`, Life 1.05 blocks of rows.
+    Life105,
+    /// `.life`, Life 1.06 coordinate lists.
+    Life106,
+    /// `.png`, a still image.
+    Png,
+    /// `.txt`, the board as it is drawn in the terminal.
+    Text,
+}
+
+impl Format {
+    /// Picks the format from the extension, looking past `.gz` and `.zst`.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match compress::format_extension(path)? {
+            "rle" => Some(Format::Rle),
+            "cells" => Some(Format::Cells),
+            "lif" => Some(Format::Life105),
+            "life" => Some(Format::Life106),
+            "png" => Some(Format::Png),
+            "txt" => Some(Format::Text),
+            _ => None,
+        }
+    }
+}
+
+/// The part of a `width` by `height` board to export: all of it, or with
+/// `crop`, the bounding box of the live cells grown by that much padding.
+pub fn region<'c>(
+    cells: impl IntoIterator<Item = &'c Cell>,
+    width: usize,
+    height: usize,
+    crop: Option<usize>,
+) -> BoundingBox {
+    let board = BoundingBox {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    };
+
+    match crop {
+        // an empty board has nothing to crop to
+        Some(padding) => BoundingBox::of(cells).map_or(board, |bounds| bounds.pad(padding)),
+        None => board,
+    }
+}
+
+/// Exports the board to `path` in the format given by its extension,
+/// returning the region that was written.
+pub fn export<P: AsRef<Path>>(
+    grid: &Grid,
+    path: P,
+    crop: Option<usize>,
+) -> std::io::Result<BoundingBox> {
+    let path = path.as_ref();
+    let format = Format::from_path(path).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "unknown format, use .rle, .cells, .lif, .life, .png or .txt",
+        )
+    })?;
+    let bounds = region(&grid.cells, grid.width, grid.height, crop);
+
+    let mut writer = compress::create(path)?;
+    let mut pattern = Pattern::from_cells(&grid.cells, bounds);
+    pattern.rule = Some(grid.rule.to_string());
+    match format {
+        Format::Rle => pattern.write_rle(&mut writer)?,
+        Format::Cells => pattern.write_cells(&mut writer)?,
+        Format::Life105 => pattern.write_life_105(&mut writer)?,
+        Format::Life106 => pattern.write_life_106(&mut writer)?,
+        Format::Png => write_png(&mut writer, &grid.cells, bounds)?,
+        Format::Text => write_text(&mut writer, &grid.cells, bounds)?,
+    }
+    writer.finish()?;
+
+    Ok(bounds)
+}
+
+/// Draws the live cells inside `bounds`, one [`CELL_PIXELS`] square per cell.
+fn render(cells: &HashSet<Cell>, bounds: BoundingBox) -> Vec<u8> {
+    let pixel_width = bounds.width * CELL_PIXELS;
+    let mut pixels = vec![0; pixel_width * bounds.height * CELL_PIXELS];
+
+    for &(x, y) in cells.iter().filter(|cell| bounds.contains(cell)) {
+        let (x, y) = ((x - bounds.x) as usize, (y - bounds.y) as usize);
+        for row in y * CELL_PIXELS..(y + 1) * CELL_PIXELS {
+            let start = row * pixel_width + x * CELL_PIXELS;
+            pixels[start..start + CELL_PIXELS].fill(1);
+        }
+    }
+
+    pixels
+}
+
+/// Writes the live cells inside `bounds` as a black and white PNG.
+pub fn write_png<W: Write>(
+    writer: W,
+    cells: &HashSet<Cell>,
+    bounds: BoundingBox,
+) -> std::io::Result<()> {
+    let too_large = || std::io::Error::other("board is too large for a PNG");
+    let png_width = u32::try_from(bounds.width * CELL_PIXELS).map_err(|_| too_large())?;
+    let png_height = u32::try_from(bounds.height * CELL_PIXELS).map_err(|_| too_large())?;
+
+    let mut encoder = png::Encoder::new(writer, png_width, png_height);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(&PALETTE[..]);
+
+    let mut writer = encoder.write_header().map_err(std::io::Error::other)?;
+    writer
+        .write_image_data(&render(cells, bounds))
+        .map_err(std::io::Error::other)?;
+    writer.finish().map_err(std::io::Error::other)
+}
+
+/// Writes the live cells inside `bounds` with the same glyphs as the board.
+pub fn write_text<W: Write>(
+    mut writer: W,
+    cells: &HashSet<Cell>,
+    bounds: BoundingBox,
+) -> std::io::Result<()> {
+    for y in bounds.y..bounds.y + bounds.height as i64 {
+        for x in bounds.x..bounds.x + bounds.width as i64 {
+            let glyph = match cells.contains(&(x, y)) {
+                true => ALIVE_CELL,
+                false => DEAD_CELL,
+            };
+            write!(writer, "{}", glyph)?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Writes generations as a looping animated GIF of the cells inside `bounds`.
+/// `delay` is the time each frame is shown, in hundredths of a second.
+pub fn write_gif<'c, W: Write>(
+    writer: W,
+    frames: impl Iterator<Item = &'c HashSet<Cell>>,
+    bounds: BoundingBox,
+    delay: u16,
+) -> std::io::Result<()> {
+    let (pixel_width, pixel_height) = (bounds.width * CELL_PIXELS, bounds.height * CELL_PIXELS);
+    let too_large = || std::io::Error::other("board is too large for a GIF");
+    let gif_width = u16::try_from(pixel_width).map_err(|_| too_large())?;
+    let gif_height = u16::try_from(pixel_height).map_err(|_| too_large())?;
+
+    let mut encoder =
+        Encoder::new(writer, gif_width, gif_height, &PALETTE).map_err(std::io::Error::other)?;
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(std::io::Error::other)?;
+
+    for cells in frames {
+        let pixels = render(cells, bounds);
+        let mut frame = Frame::from_indexed_pixels(gif_width, gif_height, pixels, None);
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(std::io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instant_replay_keeps_latest() {
+        let mut replay = InstantReplay::new(2);
+        replay.record(&HashSet::from([(0, 0)]));
+        replay.record(&HashSet::from([(1, 1)]));
+        replay.record(&HashSet::from([(2, 2)]));
+
+        let frames: Vec<_> = replay.frames().collect();
+        assert_eq!(replay.len(), 2);
+        assert_eq!(*frames[0], HashSet::from([(1, 1)]));
+        assert_eq!(*frames[1], HashSet::from([(2, 2)]));
+    }
+
+    #[test]
+    fn test_write_gif() {
+        let frames = [HashSet::from([(0, 0)]), HashSet::from([(1, 0), (5, 5)])];
+        let mut bytes = Vec::new();
+        let bounds = region(frames.iter().flatten(), 2, 2, None);
+        write_gif(&mut bytes, frames.iter(), bounds, 10).unwrap();
+
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = decoder.read_info(bytes.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (8, 8));
+
+        let first = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(first.delay, 10);
+        assert_eq!(first.buffer[0], 1); // Top-left pixel is alive
+        assert_eq!(first.buffer[4], 0);
+
+        let second = decoder.read_next_frame().unwrap().unwrap();
+        assert_eq!(second.buffer[0], 0);
+        assert_eq!(second.buffer[4], 1); // The cell at (1, 0) starts at x = 4
+
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_region_crop() {
+        let cells = HashSet::from([(3, 4), (6, 5)]);
+
+        assert_eq!(
+            region(&cells, 20, 10, None),
+            BoundingBox {
+                x: 0,
+                y: 0,
+                width: 20,
+                height: 10
+            }
+        );
+        assert_eq!(
+            region(&cells, 20, 10, Some(0)),
+            BoundingBox {
+                x: 3,
+                y: 4,
+                width: 4,
+                height: 2
+            }
+        );
+        assert_eq!(
+            region(&cells, 20, 10, Some(1)),
+            BoundingBox {
+                x: 2,
+                y: 3,
+                width: 6,
+                height: 4
+            }
+        );
+        // nothing alive, so the whole board is kept
+        assert_eq!(region(&HashSet::new(), 20, 10, Some(1)).width, 20);
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("out.rle")), Some(Format::Rle));
+        assert_eq!(
+            Format::from_path(Path::new("out.rle.gz")),
+            Some(Format::Rle)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("out.cells")),
+            Some(Format::Cells)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("out.lif")),
+            Some(Format::Life105)
+        );
+        assert_eq!(
+            Format::from_path(Path::new("out.life")),
+            Some(Format::Life106)
+        );
+        assert_eq!(Format::from_path(Path::new("out.png")), Some(Format::Png));
+        assert_eq!(Format::from_path(Path::new("out.txt")), Some(Format::Text));
+        assert_eq!(Format::from_path(Path::new("out.gif")), None);
+    }
+
+    #[test]
+    fn test_write_text_cropped() {
+        let cells = HashSet::from([(5, 5), (6, 6)]);
+        let mut text = Vec::new();
+        write_text(&mut text, &cells, region(&cells, 20, 20, Some(0))).unwrap();
+
+        assert_eq!(String::from_utf8(text).unwrap(), "⬛⬜\n⬜⬛\n");
+    }
+
+    #[test]
+    fn test_write_png_cropped() {
+        let cells = HashSet::from([(10, 10)]);
+        let mut bytes = Vec::new();
+        write_png(&mut bytes, &cells, region(&cells, 50, 50, Some(1))).unwrap();
+
+        let decoder = png::Decoder::new(bytes.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+
+        let width = 3 * CELL_PIXELS;
+        assert_eq!(reader.info().width as usize, width);
+        assert_eq!(pixels[0], 0);
+        // the live cell is in the middle of the padding
+        assert_eq!(pixels[CELL_PIXELS * width + CELL_PIXELS], 1);
+    }
+}
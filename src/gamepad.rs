@@ -0,0 +1,87 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+use gilrs::{
+    ev::filter::{Filter, Repeat},
+    Button, EventType, Gilrs,
+};
+
+/// A connected controller, read alongside the keyboard. Buttons are turned
+/// into the key presses they stand for, so both share the same bindings.
+#[derive(Debug)]
+pub struct Gamepad {
+    gilrs: Gilrs,
+    repeat: Repeat,
+}
+
+impl Gamepad {
+    /// Starts listening for controllers, or `None` if the platform has no support.
+    pub fn new() -> Option<Gamepad> {
+        Some(Gamepad {
+            gilrs: Gilrs::new().ok()?,
+            repeat: Repeat::new(),
+        })
+    }
+
+    /// Drains pending controller events as key events.
+    pub fn poll(&mut self) -> Vec<KeyEvent> {
+        let mut keys = Vec::new();
+
+        while let Some(event) = self
+            .gilrs
+            .next_event()
+            .filter_ev(&self.repeat, &mut self.gilrs)
+        {
+            // repeats are made up by the filter, so the state has to learn about them
+            self.gilrs.update(&event);
+
+            let (button, kind) = match event.event {
+                EventType::ButtonPressed(button, _) => (button, KeyEventKind::Press),
+                EventType::ButtonRepeated(button, _) => (button, KeyEventKind::Repeat),
+                EventType::ButtonReleased(button, _) => (button, KeyEventKind::Release),
+                _ => continue,
+            };
+
+            if let Some(code) = key_for(button) {
+                keys.push(KeyEvent {
+                    code,
+                    modifiers: KeyModifiers::NONE,
+                    kind,
+                    state: KeyEventState::NONE,
+                });
+            }
+        }
+        self.gilrs.inc();
+
+        keys
+    }
+}
+
+/// The key each button presses.
+fn key_for(button: Button) -> Option<KeyCode> {
+    match button {
+        Button::DPadUp => Some(KeyCode::Up),
+        Button::DPadDown => Some(KeyCode::Down),
+        Button::DPadLeft => Some(KeyCode::Left),
+        Button::DPadRight => Some(KeyCode::Right),
+        Button::South => Some(KeyCode::Char(' ')),
+        Button::East => Some(KeyCode::Enter),
+        Button::Start | Button::West => Some(KeyCode::Char('p')),
+        Button::LeftTrigger => Some(KeyCode::BackTab),
+        Button::RightTrigger => Some(KeyCode::Tab),
+        Button::North => Some(KeyCode::PageDown),
+        Button::RightTrigger2 => Some(KeyCode::Char('>')),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_for() {
+        assert_eq!(key_for(Button::DPadLeft), Some(KeyCode::Left));
+        assert_eq!(key_for(Button::South), Some(KeyCode::Char(' ')));
+        assert_eq!(key_for(Button::RightTrigger), Some(KeyCode::Tab));
+        assert_eq!(key_for(Button::Mode), None);
+    }
+}
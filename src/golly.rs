@@ -0,0 +1,310 @@
+use crate::{compress, library::UserSeed, pattern::Pattern, rule::Rule};
+use flate2::read::DeflateDecoder;
+use std::{
+    io::{ErrorKind, Read},
+    path::{Component, Path},
+};
+
+/// The extensions of the pattern files worth reading in a collection, which
+/// also has scripts, rules and help pages.
+const PATTERN_EXTENSIONS: [&str; 5] = ["rle", "mc", "lif", "life", "cells"];
+/// The directory Golly keeps its patterns in, left out of the categories.
+const PATTERNS_DIR: &str = "Patterns";
+/// Where patterns at the top of a collection go.
+pub const DEFAULT_CATEGORY: &str = "Golly";
+/// The most live cells a Macrocell pattern in a collection can have to be
+/// among the seeds. Golly's own has some with millions, which would all be
+/// expanded into memory at once.
+const MACROCELL_MAX_POPULATION: usize = 100_000;
+
+/// Reads every pattern in a collection like the one that comes with Golly,
+/// from a directory or a zip of it, into seeds categorized by the folders
+/// they're in, as in `Life/Guns`. Patterns that can't be read, are for a
+/// rule this game can't run, or are Macrocell ones too large to keep them
+/// all, are skipped.
+pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Vec<UserSeed>> {
+    let path = path.as_ref();
+    match path.is_dir() {
+        true => load_dir(path, DEFAULT_CATEGORY),
+        false => Ok(seeds(read_zip(&std::fs::read(path)?)?, DEFAULT_CATEGORY)),
+    }
+}
+
+/// Reads every pattern under `dir` the same way, with those at the top in
+/// `category`.
+pub fn load_dir<P: AsRef<Path>>(dir: P, category: &str) -> std::io::Result<Vec<UserSeed>> {
+    Ok(seeds(read_dir(dir.as_ref(), Path::new(""))?, category))
+}
+
+/// The seeds in the files read, by category and then by name.
+fn seeds(files: Vec<(String, String)>, top: &str) -> Vec<UserSeed> {
+    let mut seeds: Vec<UserSeed> = files
+        .iter()
+        .filter_map(|(name, text)| seed(Path::new(name), text, top))
+        .collect();
+    seeds.sort_by(|a, b| (&a.category, a.name()).cmp(&(&b.category, b.name())));
+    seeds
+}
+
+/// Whether a file is one of the patterns in a collection, by its extension.
+fn is_pattern(path: &Path) -> bool {
+    compress::format_extension(path)
+        .is_some_and(|extension| PATTERN_EXTENSIONS.contains(&extension))
+}
+
+/// The seed in a file at `path` within a collection, if it's readable.
+fn seed(path: &Path, text: &str, top: &str) -> Option<UserSeed> {
+    let mut pattern = match compress::format_extension(path) {
+        Some("mc") => Pattern::parse_macrocell_within(text, MACROCELL_MAX_POPULATION),
+        _ => Pattern::parse_as(path, text),
+    }
+    .ok()?;
+    // without the bounded grid Golly may add after a colon
+    let runs = pattern.rule.as_deref().is_none_or(|rule| {
+        let rule = rule.split(':').next().unwrap_or_default();
+        rule.parse::<Rule>().is_ok()
+    });
+    if pattern.cells.is_empty() || !runs {
+        return None;
+    }
+    if pattern.name.is_none() {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        pattern.name = Some(stem.into_owned());
+    }
+    Some(UserSeed {
+        category: category(path, top),
+        pattern,
+    })
+}
+
+/// The category for a file in a collection: the folders it's in, after
+/// Golly's `Patterns` folder if it's in one, or `top` if it's in none.
+fn category(path: &Path, top: &str) -> String {
+    let folders: Vec<String> = path
+        .parent()
+        .unwrap_or(Path::new(""))
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(folder) => Some(folder.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let start = folders
+        .iter()
+        .rposition(|folder| folder == PATTERNS_DIR)
+        .map_or(0, |position| position + 1);
+    match folders[start..].join("/") {
+        category if category.is_empty() => top.to_string(),
+        category => category,
+    }
+}
+
+/// The pattern files under `dir`, as their paths from `root` and text.
+fn read_dir(dir: &Path, root: &Path) -> std::io::Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = root.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            files.extend(read_dir(&path, &name)?);
+        } else if is_pattern(&path) {
+            let mut text = String::new();
+            let read = compress::open(&path).and_then(|mut file| file.read_to_string(&mut text));
+            if read.is_ok() {
+                files.push((name.to_string_lossy().into_owned(), text));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// The pattern files in a zip archive, as their paths in it and text. Only
+/// stored and deflated files are read, which is all zip tools write.
+fn read_zip(bytes: &[u8]) -> std::io::Result<Vec<(String, String)>> {
+    let invalid = || std::io::Error::new(ErrorKind::InvalidData, "not a zip file");
+    let u16_at = |at: usize| -> std::io::Result<usize> {
+        let bytes = bytes.get(at..at + 2).ok_or_else(invalid)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    let u32_at = |at: usize| -> std::io::Result<usize> {
+        let bytes = bytes.get(at..at + 4).ok_or_else(invalid)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+    };
+
+    // the end of central directory record is last, before a comment of up
+    // to 64K
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .take(0x10000 + 22)
+        .find(|&at| bytes[at..].starts_with(&ZIP_END_SIGNATURE))
+        .ok_or_else(invalid)?;
+    let count = u16_at(end + 10)?;
+    let mut at = u32_at(end + 16)?;
+
+    let mut files = Vec::new();
+    for _ in 0..count {
+        if !bytes[at.min(bytes.len())..].starts_with(&ZIP_ENTRY_SIGNATURE) {
+            return Err(invalid());
+        }
+        let method = u16_at(at + 10)?;
+        let size = u32_at(at + 20)?;
+        let length = u32_at(at + 24)?;
+        let name_length = u16_at(at + 28)?;
+        let skip = u16_at(at + 30)? + u16_at(at + 32)?;
+        let header = u32_at(at + 42)?;
+        let name = bytes
+            .get(at + 46..at + 46 + name_length)
+            .ok_or_else(invalid)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        at += 46 + name_length + skip;
+
+        if !is_pattern(Path::new(&name)) {
+            continue;
+        }
+        let start = header + 30 + u16_at(header + 26)? + u16_at(header + 28)?;
+        let data = bytes.get(start..start + size).ok_or_else(invalid)?;
+        let mut text = String::new();
+        let read = match method {
+            0 => data.take(length as u64).read_to_string(&mut text),
+            8 => DeflateDecoder::new(data)
+                .take(length as u64)
+                .read_to_string(&mut text),
+            _ => continue,
+        };
+        if read.is_ok() {
+            files.push((name, text));
+        }
+    }
+    Ok(files)
+}
+
+const ZIP_END_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x05, 0x06];
+const ZIP_ENTRY_SIGNATURE: [u8; 4] = [0x50, 0x4b, 0x01, 0x02];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::DeflateEncoder, Compression};
+    use std::io::Write;
+
+    const GLIDER: &str = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    const BLINKER: &str = "x = 3, y = 1\n3o!\n";
+    const WIREWORLD: &str = "x = 1, y = 1, rule = WireWorld\nA!\n";
+    const BRAIN: &str = "x = 1, y = 1, rule = Brain-Melt\no!\n";
+
+    /// A zip with each file deflated, as zip tools write them.
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut directory = Vec::new();
+        for (name, text) in files {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(text.as_bytes()).unwrap();
+            let data = encoder.finish().unwrap();
+            let sizes = [data.len() as u32, text.len() as u32];
+
+            let header = bytes.len() as u32;
+            bytes.extend([0x50, 0x4b, 0x03, 0x04, 20, 0, 0, 0, 8, 0]);
+            bytes.extend([0; 8]);
+            bytes.extend(sizes.iter().flat_map(|size| size.to_le_bytes()));
+            bytes.extend((name.len() as u16).to_le_bytes());
+            bytes.extend([0, 0]);
+            bytes.extend(name.as_bytes());
+            bytes.extend(&data);
+
+            directory.extend(ZIP_ENTRY_SIGNATURE);
+            directory.extend([20, 0, 20, 0, 0, 0, 8, 0]);
+            directory.extend([0; 8]);
+            directory.extend(sizes.iter().flat_map(|size| size.to_le_bytes()));
+            directory.extend((name.len() as u16).to_le_bytes());
+            directory.extend([0; 12]);
+            directory.extend(header.to_le_bytes());
+            directory.extend(name.as_bytes());
+        }
+        let offset = bytes.len() as u32;
+        bytes.extend(&directory);
+        bytes.extend(ZIP_END_SIGNATURE);
+        bytes.extend([0; 4]);
+        bytes.extend((files.len() as u16).to_le_bytes());
+        bytes.extend((files.len() as u16).to_le_bytes());
+        bytes.extend((directory.len() as u32).to_le_bytes());
+        bytes.extend(offset.to_le_bytes());
+        bytes.extend([0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_category() {
+        let category = |path| category(Path::new(path), DEFAULT_CATEGORY);
+        assert_eq!(category("Patterns/Life/Guns/gun.rle"), "Life/Guns");
+        assert_eq!(category("golly-4.3/Patterns/Life/x.mc"), "Life");
+        assert_eq!(category("Spaceships/glider.rle"), "Spaceships");
+        assert_eq!(category("glider.rle"), DEFAULT_CATEGORY);
+    }
+
+    #[test]
+    fn test_read_zip() {
+        let files = [
+            ("Patterns/Life/Oscillators/blinker.rle", BLINKER),
+            ("Patterns/Life/Spaceships/glider.rle", GLIDER),
+            ("Patterns/Help/about.html", "<html>"),
+        ];
+        let read = read_zip(&zip(&files)).unwrap();
+        let expected: Vec<(String, String)> = files[..2]
+            .iter()
+            .map(|(name, text)| (name.to_string(), text.to_string()))
+            .collect();
+        assert_eq!(read, expected);
+        assert!(read_zip(b"not a zip").is_err());
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = std::env::temp_dir().join(format!("{}-golly", std::process::id()));
+        let spaceships = dir.join("Patterns").join("Life").join("Spaceships");
+        std::fs::create_dir_all(&spaceships).unwrap();
+        std::fs::write(spaceships.join("glider.rle"), GLIDER).unwrap();
+        std::fs::write(dir.join("blinker.rle"), BLINKER).unwrap();
+        std::fs::write(dir.join("wireworld.rle"), WIREWORLD).unwrap();
+        // a rule that can't be run here, and files that aren't patterns
+        std::fs::write(dir.join("brain.rle"), BRAIN).unwrap();
+        std::fs::write(dir.join("empty.rle"), "x = 0, y = 0\n!\n").unwrap();
+        // 64 cells in a full leaf, then four of each square up to 262144
+        let nodes: String = (4..=9)
+            .map(|level| format!("{} {1} {1} {1} {1}\n", level, level - 3))
+            .collect();
+        let crowded = format!("[M2]\n{}\n{}", "********$".repeat(8), nodes);
+        assert!(Pattern::parse_macrocell(&crowded).is_ok());
+        std::fs::write(dir.join("crowded.mc"), crowded).unwrap();
+        std::fs::write(dir.join("script.lua"), "g.show('hello')").unwrap();
+        let archive = std::env::temp_dir().join(format!("{}-golly.zip", std::process::id()));
+        std::fs::write(
+            &archive,
+            zip(&[
+                ("Patterns/Life/Spaceships/glider.rle", GLIDER),
+                ("blinker.rle", BLINKER),
+                ("wireworld.rle", WIREWORLD),
+            ]),
+        )
+        .unwrap();
+
+        let seeds = load(&dir).unwrap();
+        let zipped = load(&archive).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_file(&archive).unwrap();
+
+        let names: Vec<(&str, &str)> = seeds
+            .iter()
+            .map(|seed| (seed.category.as_str(), seed.name()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("Golly", "blinker"),
+                ("Golly", "wireworld"),
+                ("Life/Spaceships", "Glider")
+            ]
+        );
+        assert_eq!(zipped, seeds);
+    }
+}
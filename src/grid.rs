@@ -1,143 +1,1249 @@
-use crate::seed::IsSeed;
-use std::collections::HashSet;
+use crate::rule::{Neighborhood, Rule};
+use crate::seed::{IsSeed, Mirrored, Orientation, Symmetry};
+use crate::wireworld::Electron;
+use crate::{dense, elementary};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
-const ALIVE_CELL: &str = "⬛";
-const DEAD_CELL: &str = "⬜";
-const ALIVE_CELL_PREVIEW: &str = "🟩";
+pub const ALIVE_CELL: &str = "⬛";
+pub const DEAD_CELL: &str = "⬜";
 const DEAD_CELL_PREVIEW: &str = "🟦";
+/// A preview cell over a live one, warning that placing the seed there would
+/// run into what's on the board.
+const OVERLAP_CELL_PREVIEW: &str = "🟧";
+const BORN_CELL: &str = "🟨";
+const DIED_CELL: &str = "🟥";
+const ONION_CELL: &str = "🔳";
+const BOUNDS_CELL: &str = "🟫";
+const SELECTED_CELL: &str = "🔲";
+const WATCHED_ALIVE_CELL: &str = "🔴";
+const WATCHED_DEAD_CELL: &str = "⭕";
+/// Cells fading out under Generations rules, from just died to nearly gone.
+const DYING_CELLS: [&str; 3] = ["🟥", "🟧", "🟪"];
+/// Where cells died, from just now to [`TRAIL_LENGTH`] generations ago.
+const TRAIL_CELLS: [&str; 3] = ["▓▓", "▒▒", "░░"];
+/// How many generations the trails of dead cells last.
+pub const TRAIL_LENGTH: usize = 12;
+/// Live cells by how long they've been alive, from just born to long settled.
+const AGE_CELLS: [&str; 5] = ["🟨", "🟧", "🟥", "🟪", "🟫"];
+/// The age, in generations, each of [`AGE_CELLS`] starts at.
+const AGE_STEPS: [usize; 5] = [0, 2, 8, 32, 128];
+/// Wireworld's electron heads and bare wire. Tails are the first dying cell.
+const ELECTRON_HEAD: &str = "🟦";
+/// Live cells of each color, for rules with colors.
+pub const CELL_COLORS: [&str; 4] = ["🟥", "🟦", "🟨", "🟩"];
+const WIRE: &str = "🟨";
+/// Zoomed out, each character shows a square of cells shaded by how many of
+/// them are alive, from none to all.
+const DENSITY_SHADES: [&str; 5] = [DEAD_CELL, "░░", "▒▒", "▓▓", ALIVE_CELL];
+/// Zoom levels, as the side of the square of cells shown by each character.
+pub const ZOOM_LEVELS: [usize; 5] = [1, 2, 4, 8, 16];
+/// How many cells apart guide lines are, at multiples of it, when zoomed in
+/// all the way. Zoomed out, they're as far apart on screen.
+pub const GUIDE_SPACING: i64 = 10;
+const GUIDE_VERTICAL: &str = "│ ";
+const GUIDE_HORIZONTAL: &str = "──";
+const GUIDE_CROSSING: &str = "┼─";
 
-pub type Cell = (usize, usize);
+pub type Cell = (i64, i64);
+
+/// What happens to cells that move past the edges of the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Boundary {
+    /// Cells keep living past every edge, out of sight.
+    #[default]
+    Open,
+    /// Cells past the edges die.
+    Dead,
+    /// The edges wrap around, so what leaves on the right comes back on the left.
+    Torus,
+    /// Like a torus, but what leaves at the top or bottom comes back mirrored
+    /// left to right.
+    Klein,
+    /// A cross-surface, or projective plane: every edge wraps around mirrored,
+    /// so what leaves on the right comes back upside down on the left.
+    Cross,
+}
+
+impl Display for Boundary {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Boundary::Open => write!(f, "open"),
+            Boundary::Dead => write!(f, "dead"),
+            Boundary::Torus => write!(f, "torus"),
+            Boundary::Klein => write!(f, "klein"),
+            Boundary::Cross => write!(f, "cross"),
+        }
+    }
+}
+
+impl Boundary {
+    /// The next one in the cycle: open, dead, torus, klein, cross.
+    pub fn next(self) -> Boundary {
+        match self {
+            Boundary::Open => Boundary::Dead,
+            Boundary::Dead => Boundary::Torus,
+            Boundary::Torus => Boundary::Klein,
+            Boundary::Klein => Boundary::Cross,
+            Boundary::Cross => Boundary::Open,
+        }
+    }
+
+    /// Whether cells past one edge come back in through another.
+    pub fn wraps(self) -> bool {
+        matches!(self, Boundary::Torus | Boundary::Klein | Boundary::Cross)
+    }
+
+    /// Where `cell` is on a `width` by `height` board with these edges, for
+    /// cells that have stepped past one of them.
+    pub fn wrap(self, cell: Cell, width: usize, height: usize) -> Cell {
+        let (width, height) = (width as i64, height as i64);
+        if !self.wraps() || width == 0 || height == 0 {
+            return cell;
+        }
+
+        let (mut x, mut y) = cell;
+        // crossing the left or right edge an odd number of times mirrors the row
+        if self == Boundary::Cross && x.div_euclid(width) % 2 != 0 {
+            y = height - 1 - y;
+        }
+        // and crossing the top or bottom one mirrors the column
+        if self != Boundary::Torus && y.div_euclid(height) % 2 != 0 {
+            x = width - 1 - x;
+        }
+        (x.rem_euclid(width), y.rem_euclid(height))
+    }
+}
+
+/// Which cells are neighbors: the rule's neighborhood and how the board's
+/// edges connect, which is all that neighbor counts depend on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Topology {
+    neighborhood: Neighborhood,
+    boundary: Boundary,
+    /// Only wrapping edges care about the board's size, so it's zero otherwise.
+    width: usize,
+    height: usize,
+}
+
+impl Topology {
+    fn wrap(self, cell: Cell) -> Cell {
+        self.boundary.wrap(cell, self.width, self.height)
+    }
+}
+
+/// How many live neighbors each cell has, for a given topology.
+#[derive(Debug, Default)]
+struct NeighborCounts {
+    topology: Topology,
+    counts: HashMap<Cell, usize>,
+}
+
+/// How the next generation is computed. Both give the same result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Engine {
+    /// Keeps a running neighbor count around the live cells, which is quick for
+    /// a few cells.
+    #[default]
+    Sparse,
+    /// Packs rows into bitmasks and counts 64 cells at a time, which is quick
+    /// for crowded boards.
+    Dense,
+}
+
+impl Display for Engine {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Engine::Sparse => write!(f, "sparse"),
+            Engine::Dense => write!(f, "dense"),
+        }
+    }
+}
+
+/// A rectangle of cells, such as the smallest one holding every live cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub x: i64,
+    pub y: i64,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl BoundingBox {
+    /// The smallest box containing all `cells`, or `None` if there are none.
+    pub fn of<'c>(cells: impl IntoIterator<Item = &'c Cell>) -> Option<BoundingBox> {
+        let mut cells = cells.into_iter();
+        let &(x, y) = cells.next()?;
+        let (min, max) = cells.fold(((x, y), (x, y)), |(min, max), &(x, y)| {
+            ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+        });
+
+        Some(BoundingBox {
+            x: min.0,
+            y: min.1,
+            width: (max.0 - min.0) as usize + 1,
+            height: (max.1 - min.1) as usize + 1,
+        })
+    }
+
+    /// Grows the box by `padding` cells on every side.
+    pub fn pad(self, padding: usize) -> BoundingBox {
+        BoundingBox {
+            x: self.x - padding as i64,
+            y: self.y - padding as i64,
+            width: self.width + 2 * padding,
+            height: self.height + 2 * padding,
+        }
+    }
+
+    /// The cell in the middle, or just up and left of it.
+    pub fn center(&self) -> Cell {
+        (
+            self.x + (self.width as i64 - 1) / 2,
+            self.y + (self.height as i64 - 1) / 2,
+        )
+    }
+
+    pub fn contains(&self, cell: &Cell) -> bool {
+        (self.x..self.x + self.width as i64).contains(&cell.0)
+            && (self.y..self.y + self.height as i64).contains(&cell.1)
+    }
+
+    /// The cells in the box, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = Cell> {
+        let BoundingBox {
+            x,
+            y,
+            width,
+            height,
+        } = *self;
+        (y..y + height as i64).flat_map(move |y| (x..x + width as i64).map(move |x| (x, y)))
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct Grid {
     pub preview: HashSet<Cell>,
     pub cells: HashSet<Cell>,
+    /// How many ticks since the board was cleared.
+    pub generation: usize,
+    /// Cells that came alive in the most recent tick.
+    pub born: HashSet<Cell>,
+    /// Cells that died in the most recent tick.
+    pub died: HashSet<Cell>,
+    /// Cells that were alive before the most recent tick.
+    pub previous: HashSet<Cell>,
+    pub highlight_births: bool,
+    pub highlight_deaths: bool,
+    pub onion_skin: bool,
+    /// Show how many live neighbors each dead cell next to a live one has.
+    pub show_neighbors: bool,
+    /// Leave trails where cells died, fading over [`TRAIL_LENGTH`] generations.
+    pub show_trails: bool,
+    /// The generation each recently dead cell died in, while trails are shown.
+    pub trails: HashMap<Cell, usize>,
+    /// Color live cells by their age.
+    pub show_age: bool,
+    /// The generation each live cell was born in. Cells left out have been
+    /// alive since generation 0.
+    pub births: HashMap<Cell, usize>,
+    /// Frame the live cells with their bounding box.
+    pub show_bounds: bool,
+    /// Draw guide lines across the dead cells, [`GUIDE_SPACING`] apart.
+    pub show_guides: bool,
+    /// Cells marked to pause the game when they change.
+    pub watched: HashSet<Cell>,
+    /// The area selected to copy or cut, shaded.
+    pub selection: Option<BoundingBox>,
+    /// How seeds placed by hand, and their previews, are mirrored.
+    pub symmetry: Symmetry,
+    /// Where the axes of symmetry cross, in half cells, or `None` for the
+    /// middle of the board.
+    pub axes: Option<Cell>,
+    pub rule: Rule,
+    /// Cells fading out under a Generations rule, by state, from 2 up to one
+    /// less than the rule's states.
+    pub dying: HashMap<Cell, usize>,
+    /// Wireworld's wire, which stays put while electrons move along it.
+    pub wires: HashSet<Cell>,
+    /// The color of each live cell under a rule with colors, as an index
+    /// into [`CELL_COLORS`]. Cells left out have the first one.
+    pub colors: HashMap<Cell, usize>,
+    /// The color seeds are placed in.
+    pub color: usize,
+    pub boundary: Boundary,
+    pub engine: Engine,
     pub width: usize,
     pub height: usize,
-    cells_list: Vec<Cell>,
+    /// The part of the universe that is drawn, which can be smaller than the
+    /// board and can be panned past its edges.
+    pub viewport: BoundingBox,
+    /// How many cells across each character shows, one of [`ZOOM_LEVELS`].
+    pub zoom: usize,
+    /// How many live neighbors each cell has, kept up to date as cells are
+    /// born and die. Cells without live neighbors are left out. The dense
+    /// engine doesn't need it, and changing the edges makes it wrong, so then
+    /// it's dropped until the sparse one runs again.
+    neighbors: Option<NeighborCounts>,
 }
 
-impl Display for Grid {
+/// The guide lines across the viewport.
+#[derive(Debug, Default, PartialEq)]
+pub struct Guides {
+    /// The column of each up and down line, with the x it's at.
+    pub columns: Vec<(usize, i64)>,
+    /// The row of each side to side line, with the y it's at.
+    pub rows: Vec<(usize, i64)>,
+}
+
+/// The guide lines among `count` characters from `start`, each showing
+/// `zoom` cells: the index of each character a line runs through, with the
+/// coordinate of the line.
+fn guide_lines(start: i64, count: usize, zoom: usize) -> Vec<(usize, i64)> {
+    let spacing = GUIDE_SPACING * zoom as i64;
+    (0..count)
+        .filter_map(|index| {
+            let from = start + (index * zoom) as i64;
+            let line = (from + zoom as i64 - 1).div_euclid(spacing) * spacing;
+            (line >= from).then_some((index, line))
+        })
+        .collect()
+}
+
+/// What a character on the board shows, drawn as emoji by default or in a
+/// theme's colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shade {
+    Dead,
+    Alive,
+    Preview,
+    /// A preview cell over a live one, warning that placing the seed there
+    /// would run into what's on the board.
+    Overlap,
+    Born,
+    Died,
+    /// Where a cell was a generation ago.
+    Onion,
+    /// The frame around the live cells.
+    Bounds,
+    Selected,
+    WatchedAlive,
+    WatchedDead,
+    /// Fading out under a Generations rule, as an index into [`DYING_CELLS`].
+    Dying(usize),
+    /// Where a cell died, as an index into [`TRAIL_CELLS`].
+    Trail(usize),
+    /// How long a cell has been alive, as an index into [`AGE_CELLS`].
+    Age(usize),
+    ElectronHead,
+    Wire,
+    /// A live cell of a color, as an index into [`CELL_COLORS`].
+    Color(usize),
+    /// Zoomed out, a square of cells from one to three quarters alive, by
+    /// quarters.
+    Density(usize),
+    /// A dead cell on an up and down guide line, a side to side one or both.
+    Guide {
+        column: bool,
+        row: bool,
+    },
+    /// A dead cell with this many live neighbors.
+    Neighbors(usize),
+}
+
+impl Shade {
+    /// A dead cell, or a guide line if it's on one.
+    fn dead(on_column: bool, on_row: bool) -> Shade {
+        match (on_column, on_row) {
+            (false, false) => Shade::Dead,
+            (column, row) => Shade::Guide { column, row },
+        }
+    }
+}
+
+impl Display for Shade {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                match (self.cells.contains(&(x, y)), self.preview.contains(&(x, y))) {
-                    (true, true) => write!(f, "{}", ALIVE_CELL_PREVIEW)?,
-                    (true, false) => write!(f, "{}", ALIVE_CELL)?,
-                    (false, true) => write!(f, "{}", DEAD_CELL_PREVIEW)?,
-                    (false, false) => write!(f, "{}", DEAD_CELL)?,
+        match *self {
+            Shade::Dead => write!(f, "{}", DEAD_CELL),
+            Shade::Alive => write!(f, "{}", ALIVE_CELL),
+            Shade::Preview => write!(f, "{}", DEAD_CELL_PREVIEW),
+            Shade::Overlap => write!(f, "{}", OVERLAP_CELL_PREVIEW),
+            Shade::Born => write!(f, "{}", BORN_CELL),
+            Shade::Died => write!(f, "{}", DIED_CELL),
+            Shade::Onion => write!(f, "{}", ONION_CELL),
+            Shade::Bounds => write!(f, "{}", BOUNDS_CELL),
+            Shade::Selected => write!(f, "{}", SELECTED_CELL),
+            Shade::WatchedAlive => write!(f, "{}", WATCHED_ALIVE_CELL),
+            Shade::WatchedDead => write!(f, "{}", WATCHED_DEAD_CELL),
+            Shade::Dying(index) => write!(f, "{}", DYING_CELLS[index]),
+            Shade::Trail(index) => write!(f, "{}", TRAIL_CELLS[index]),
+            Shade::Age(index) => write!(f, "{}", AGE_CELLS[index]),
+            Shade::ElectronHead => write!(f, "{}", ELECTRON_HEAD),
+            Shade::Wire => write!(f, "{}", WIRE),
+            Shade::Color(index) => write!(f, "{}", CELL_COLORS[index]),
+            Shade::Density(quarters) => write!(f, "{}", DENSITY_SHADES[quarters]),
+            Shade::Guide { column, row } => write!(
+                f,
+                "{}",
+                match (column, row) {
+                    (true, true) => GUIDE_CROSSING,
+                    (true, false) => GUIDE_VERTICAL,
+                    _ => GUIDE_HORIZONTAL,
                 }
+            ),
+            Shade::Neighbors(count) => write!(f, "{}", neighbor_count(count)),
+        }
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        for row in self.shades() {
+            for shade in row {
+                write!(f, "{}", shade)?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
+/// A neighbor count as wide as a cell, in full-width digits up to 9.
+fn neighbor_count(count: usize) -> String {
+    match char::from_digit(count as u32, 10) {
+        Some(digit) => char::from_u32(digit as u32 - '0' as u32 + '０' as u32)
+            .unwrap_or(digit)
+            .to_string(),
+        None => format!("{:>2}", count),
+    }
+}
+
 impl Grid {
     pub fn new(width: usize, height: usize) -> Grid {
         let preview = HashSet::new();
         let cells = HashSet::new();
-        let cells_list = Vec::new();
         Grid {
             preview,
-            cells_list,
             cells,
+            generation: 0,
+            born: HashSet::new(),
+            died: HashSet::new(),
+            previous: HashSet::new(),
+            highlight_births: false,
+            highlight_deaths: false,
+            onion_skin: false,
+            show_neighbors: false,
+            show_trails: false,
+            trails: HashMap::new(),
+            show_age: false,
+            births: HashMap::new(),
+            show_bounds: false,
+            show_guides: false,
+            watched: HashSet::new(),
+            selection: None,
+            symmetry: Symmetry::default(),
+            axes: None,
+            rule: Rule::default(),
+            dying: HashMap::new(),
+            wires: HashSet::new(),
+            colors: HashMap::new(),
+            color: 0,
+            boundary: Boundary::default(),
+            engine: Engine::default(),
             width,
             height,
+            viewport: BoundingBox {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            },
+            zoom: 1,
+            neighbors: Some(NeighborCounts::default()),
         }
     }
 
     pub fn seed<S: IsSeed>(&mut self, seed: S, origin: Cell) {
+        if self.rule.wireworld {
+            return self.seed_circuit(seed, origin);
+        }
         for cell in seed.cells(origin) {
             self.add_cell(cell);
+            if self.rule.colors > 1 {
+                let cell = self.topology().wrap(cell);
+                self.colors.insert(cell, self.color % self.rule.colors);
+            }
         }
     }
 
     pub fn add_cell(&mut self, cell: Cell) {
+        let topology = self.topology();
+        let cell = topology.wrap(cell);
         if self.cells.insert(cell) {
+            self.dying.remove(&cell);
+            self.births.insert(cell, self.generation);
             self.preview.clear();
-            self.cells_list.push(cell);
+            if let Some(neighbors) = self.neighbor_counts(topology) {
+                Self::for_each_neighbor_of(&cell, topology, |neighbor| {
+                    *neighbors.entry(*neighbor).or_default() += 1;
+                });
+            }
+        }
+    }
+
+    pub fn remove_cell(&mut self, cell: &Cell) {
+        let topology = self.topology();
+        if self.cells.remove(cell) {
+            self.births.remove(cell);
+            if let Some(neighbors) = self.neighbor_counts(topology) {
+                Self::for_each_neighbor_of(cell, topology, |neighbor| {
+                    if let Some(count) = neighbors.get_mut(neighbor) {
+                        *count -= 1;
+                        if *count == 0 {
+                            neighbors.remove(neighbor);
+                        }
+                    }
+                });
+            }
         }
     }
 
+    fn topology(&self) -> Topology {
+        let (width, height) = match self.boundary.wraps() {
+            true => (self.width, self.height),
+            false => (0, 0),
+        };
+        Topology {
+            neighborhood: self.rule.neighborhood(),
+            boundary: self.boundary,
+            width,
+            height,
+        }
+    }
+
+    /// The neighbor counts, unless they were made for other neighbors.
+    fn neighbor_counts(&mut self, topology: Topology) -> Option<&mut HashMap<Cell, usize>> {
+        self.neighbors = self
+            .neighbors
+            .take()
+            .filter(|neighbors| neighbors.topology == topology);
+        self.neighbors
+            .as_mut()
+            .map(|neighbors| &mut neighbors.counts)
+    }
+
+    /// Seeds the grid like [`Grid::seed`], along with the seed's reflections
+    /// under the symmetry.
+    pub fn place<S: IsSeed>(&mut self, seed: S, origin: Cell) {
+        self.seed(self.mirrored(seed), origin);
+    }
+
+    /// How many live cells placing `seed` by hand at `origin` would land on.
+    pub fn overlap<S: IsSeed>(&self, seed: S, origin: Cell) -> usize {
+        self.mirrored(seed)
+            .cells(origin)
+            .iter()
+            .filter(|cell| self.cells.contains(cell))
+            .count()
+    }
+
     pub fn preview<S: IsSeed>(&mut self, preview: S, origin: Cell) {
         self.preview.clear();
-        for cell in preview.cells(origin) {
+        for cell in self.mirrored(preview).cells(origin) {
             self.preview.insert(cell);
         }
     }
 
+    /// Where the axes of symmetry cross, in half cells.
+    pub fn symmetry_center(&self) -> Cell {
+        self.axes
+            .unwrap_or((self.width as i64 - 1, self.height as i64 - 1))
+    }
+
+    fn mirrored<S: IsSeed>(&self, seed: S) -> Mirrored<S> {
+        Mirrored {
+            seed,
+            symmetry: self.symmetry,
+            center: self.symmetry_center(),
+        }
+    }
+
     pub fn resize(&mut self, width: usize, height: usize) {
         if width == self.width && height == self.height {
             return;
         }
 
-        let mut next_grid = Self::new(width, height);
+        self.width = width;
+        self.height = height;
+        // open boards keep what's past the edges, it's just out of sight
+        if self.boundary == Boundary::Dead {
+            self.remove_outside_board();
+            let board = self.board();
+            self.born.retain(|cell| board.contains(cell));
+            self.died.retain(|cell| board.contains(cell));
+            self.previous.retain(|cell| board.contains(cell));
+        }
+        self.preview.clear();
+    }
 
-        self.cells_list
+    /// Moves the viewport by `dx` and `dy` cells.
+    pub fn pan(&mut self, dx: i64, dy: i64) {
+        self.viewport.x += dx;
+        self.viewport.y += dy;
+    }
+
+    /// The `width` by `height` rectangle starting at the universe's origin.
+    pub fn board(&self) -> BoundingBox {
+        BoundingBox {
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The cell shown at `column` and `row` of the board, or the top-left
+    /// one of those shown there when zoomed out.
+    pub fn to_universe(&self, (column, row): (usize, usize)) -> Cell {
+        (
+            self.viewport.x + (column * self.zoom) as i64,
+            self.viewport.y + (row * self.zoom) as i64,
+        )
+    }
+
+    /// Fits `columns` by `rows` characters of the board at the current zoom.
+    pub fn resize_viewport(&mut self, columns: usize, rows: usize) {
+        self.viewport.width = columns * self.zoom;
+        self.viewport.height = rows * self.zoom;
+    }
+
+    /// Changes the zoom, keeping the middle of the viewport where it is.
+    pub fn set_zoom(&mut self, zoom: usize) {
+        let zoom = zoom.max(1);
+        let (columns, rows) = (
+            self.viewport.width / self.zoom,
+            self.viewport.height / self.zoom,
+        );
+        let center = (
+            self.viewport.x + (self.viewport.width / 2) as i64,
+            self.viewport.y + (self.viewport.height / 2) as i64,
+        );
+
+        self.zoom = zoom;
+        self.resize_viewport(columns, rows);
+        self.look_at(center);
+    }
+
+    /// Zooms in as far as the live cells still fit in the viewport, or out as
+    /// far as it goes if they don't, and centers it on them. Returns false,
+    /// changing nothing, if there are none.
+    pub fn zoom_to_fit(&mut self) -> bool {
+        let Some(bounds) = self.bounding_box() else {
+            return false;
+        };
+        let (columns, rows) = (
+            self.viewport.width / self.zoom,
+            self.viewport.height / self.zoom,
+        );
+        let zoom = ZOOM_LEVELS
             .iter()
-            .filter(|cell| cell.0 < width && cell.1 < height)
-            .for_each(|cell| {
-                next_grid.add_cell(*cell);
-            });
+            .copied()
+            .find(|zoom| bounds.width <= columns * zoom && bounds.height <= rows * zoom)
+            .unwrap_or(ZOOM_LEVELS[ZOOM_LEVELS.len() - 1]);
+
+        self.set_zoom(zoom);
+        self.look_at(bounds.center());
+        true
+    }
+
+    /// Where guide lines run across the viewport, if they're shown.
+    pub fn guides(&self) -> Guides {
+        if !self.show_guides {
+            return Guides::default();
+        }
+        let (columns, rows) = (
+            self.viewport.width / self.zoom,
+            self.viewport.height / self.zoom,
+        );
+        Guides {
+            columns: guide_lines(self.viewport.x, columns, self.zoom),
+            rows: guide_lines(self.viewport.y, rows, self.zoom),
+        }
+    }
+
+    fn guide_indices(&self) -> (HashSet<usize>, HashSet<usize>) {
+        let Guides { columns, rows } = self.guides();
+        (
+            columns.into_iter().map(|(column, _)| column).collect(),
+            rows.into_iter().map(|(row, _)| row).collect(),
+        )
+    }
+
+    /// Moves the viewport so `center` is in the middle of it.
+    pub fn look_at(&mut self, center: Cell) {
+        self.viewport.x = center.0 - (self.viewport.width / 2) as i64;
+        self.viewport.y = center.1 - (self.viewport.height / 2) as i64;
+    }
+
+    /// What each character in the viewport shows, row by row.
+    pub fn shades(&self) -> Vec<Vec<Shade>> {
+        if self.zoom > 1 {
+            return self.zoomed_shades();
+        }
+
+        let bounds = match self.show_bounds {
+            true => self.bounding_box(),
+            false => None,
+        };
+        // the frame is the ring of cells just outside the box
+        let on_frame = |cell: &Cell| {
+            bounds.is_some_and(|bounds| !bounds.contains(cell) && bounds.pad(1).contains(cell))
+        };
+        let neighbors = match self.show_neighbors {
+            true => self.count_all_neighbors(self.topology()),
+            false => HashMap::new(),
+        };
+        let (guide_columns, guide_rows) = self.guide_indices();
+
+        let BoundingBox {
+            x: left,
+            y: top,
+            width,
+            height,
+        } = self.viewport;
+        let shade =
+            |x: i64, y: i64| match (self.cells.contains(&(x, y)), self.preview.contains(&(x, y))) {
+                (true, true) => Shade::Overlap,
+                (true, false) if self.watched.contains(&(x, y)) => Shade::WatchedAlive,
+                (true, false) if self.rule.wireworld => Shade::ElectronHead,
+                (true, false) if self.rule.colors > 1 => {
+                    Shade::Color(self.colors.get(&(x, y)).copied().unwrap_or_default())
+                }
+                (true, false) if self.show_age => {
+                    let age = self.age(&(x, y));
+                    let step = AGE_STEPS.iter().rposition(|&step| age >= step);
+                    Shade::Age(step.unwrap_or_default())
+                }
+                (true, false) if self.highlight_births && self.born.contains(&(x, y)) => {
+                    Shade::Born
+                }
+                (true, false) => Shade::Alive,
+                (false, true) => Shade::Preview,
+                (false, false) if self.watched.contains(&(x, y)) => Shade::WatchedDead,
+                (false, false) if self.dying.contains_key(&(x, y)) => {
+                    Shade::Dying(self.dying_cell(self.dying[&(x, y)]))
+                }
+                (false, false) if self.rule.wireworld && self.wires.contains(&(x, y)) => {
+                    Shade::Wire
+                }
+                (false, false) if self.highlight_deaths && self.died.contains(&(x, y)) => {
+                    Shade::Died
+                }
+                (false, false) if neighbors.contains_key(&(x, y)) => {
+                    Shade::Neighbors(neighbors[&(x, y)])
+                }
+                (false, false) if self.show_trails && self.trails.contains_key(&(x, y)) => {
+                    let age = self.generation - self.trails[&(x, y)];
+                    Shade::Trail(age * TRAIL_CELLS.len() / TRAIL_LENGTH)
+                }
+                (false, false) if self.onion_skin && self.previous.contains(&(x, y)) => {
+                    Shade::Onion
+                }
+                (false, false)
+                    if self
+                        .selection
+                        .is_some_and(|selection| selection.contains(&(x, y))) =>
+                {
+                    Shade::Selected
+                }
+                (false, false) if on_frame(&(x, y)) => Shade::Bounds,
+                (false, false) => Shade::dead(
+                    guide_columns.contains(&((x - left) as usize)),
+                    guide_rows.contains(&((y - top) as usize)),
+                ),
+            };
+        (top..top + height as i64)
+            .map(|y| (left..left + width as i64).map(|x| shade(x, y)).collect())
+            .collect()
+    }
+
+    /// Each `zoom` by `zoom` square of cells as one shaded character.
+    fn zoomed_shades(&self) -> Vec<Vec<Shade>> {
+        let zoom = self.zoom as i64;
+        let (columns, rows) = (
+            self.viewport.width / self.zoom,
+            self.viewport.height / self.zoom,
+        );
+        let square_of = |&(x, y): &Cell| {
+            (
+                (x - self.viewport.x).div_euclid(zoom),
+                (y - self.viewport.y).div_euclid(zoom),
+            )
+        };
+
+        let mut alive: HashMap<Cell, usize> = HashMap::new();
+        for cell in self
+            .cells
+            .iter()
+            .filter(|cell| self.viewport.contains(cell))
+        {
+            *alive.entry(square_of(cell)).or_default() += 1;
+        }
+        let previewed: HashSet<Cell> = self.preview.iter().map(square_of).collect();
+        let (guide_columns, guide_rows) = self.guide_indices();
+
+        let area = (zoom * zoom) as usize;
+        let shade = |column: i64, row: i64| {
+            let count = alive.get(&(column, row)).copied().unwrap_or_default();
+            match (count, previewed.contains(&(column, row))) {
+                (0, true) => Shade::Preview,
+                (_, true) => Shade::Overlap,
+                (0, false) => Shade::dead(
+                    guide_columns.contains(&(column as usize)),
+                    guide_rows.contains(&(row as usize)),
+                ),
+                (count, false) if count == area => Shade::Alive,
+                // any live cell shows, however sparse
+                (count, false) => Shade::Density((count * 3).div_ceil(area)),
+            }
+        };
+        (0..rows as i64)
+            .map(|row| {
+                (0..columns as i64)
+                    .map(|column| shade(column, row))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The nearest cell to `cell` that is shown on the board.
+    pub fn clamp_to_viewport(&self, cell: Cell) -> Cell {
+        let BoundingBox {
+            x: left,
+            y: top,
+            width,
+            height,
+        } = self.viewport;
+        let right = left + width.saturating_sub(1) as i64;
+        let bottom = top + height.saturating_sub(1) as i64;
+        (cell.0.clamp(left, right), cell.1.clamp(top, bottom))
+    }
+
+    /// Brings cells left past the edges, from before they wrapped, back in.
+    fn wrap_into_board(&mut self) {
+        let board = self.board();
+        let outside: Vec<Cell> = self
+            .cells
+            .iter()
+            .filter(|cell| !board.contains(cell))
+            .copied()
+            .collect();
+        for cell in outside {
+            self.remove_cell(&cell);
+            self.add_cell(cell);
+        }
+    }
+
+    fn remove_outside_board(&mut self) {
+        let board = self.board();
+        let outside: Vec<Cell> = self
+            .cells
+            .iter()
+            .filter(|cell| !board.contains(cell))
+            .copied()
+            .collect();
+        for cell in &outside {
+            self.remove_cell(cell);
+        }
+        self.dying.retain(|cell, _| board.contains(cell));
+        self.wires.retain(|cell| board.contains(cell));
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::of(&self.cells)
+    }
+
+    /// Every cell with something on it: the live ones, wire and fading cells.
+    fn contents(&self) -> impl Iterator<Item = &Cell> {
+        self.cells
+            .iter()
+            .chain(&self.wires)
+            .chain(self.dying.keys())
+    }
+
+    /// Turns and flips everything on the board about the middle of its
+    /// bounding box, so it stays about where it was.
+    pub fn orient(&mut self, orientation: Orientation) {
+        let Some(from) = BoundingBox::of(self.contents()) else {
+            return;
+        };
+        let far_corner = (
+            from.x + from.width as i64 - 1,
+            from.y + from.height as i64 - 1,
+        );
+        let Some(turned) = BoundingBox::of(&[
+            orientation.apply((from.x, from.y)),
+            orientation.apply(far_corner),
+        ]) else {
+            return;
+        };
+        let dx = from.x + (from.width as i64 - turned.width as i64) / 2 - turned.x;
+        let dy = from.y + (from.height as i64 - turned.height as i64) / 2 - turned.y;
+        self.remap(|cell| {
+            let (x, y) = orientation.apply(cell);
+            (x + dx, y + dy)
+        });
+    }
+
+    /// Moves everything on the board by `dx` and `dy`, around wrapping edges.
+    /// Nothing is pushed past dead edges: the move doesn't happen, returning
+    /// false, if it would.
+    pub fn translate(&mut self, dx: i64, dy: i64) -> bool {
+        let board = self.board();
+        let blocked = self.boundary == Boundary::Dead
+            && self
+                .contents()
+                .any(|&(x, y)| !board.contains(&(x + dx, y + dy)));
+        if !blocked {
+            self.remap(|(x, y)| (x + dx, y + dy));
+        }
+        !blocked
+    }
+
+    /// Shrinks the board to just fit everything on it, with `padding` cells
+    /// around it, moved to start at the board's top-left corner, and centers
+    /// the view on it. An empty board is left alone, returning `None`.
+    pub fn crop(&mut self, padding: usize) -> Option<BoundingBox> {
+        let bounds = BoundingBox::of(self.contents())?.pad(padding);
+        // the cells only move onto the smaller board, so nothing wraps
+        self.width = bounds.width;
+        self.height = bounds.height;
+        self.remap(|(x, y)| (x - bounds.x, y - bounds.y));
+        self.look_at(self.board().center());
+        Some(self.board())
+    }
+
+    /// Moves every cell, with its age, color or fading state, to wherever
+    /// `to` takes it. What changed in the last tick is forgotten, as it no
+    /// longer lines up with the board.
+    fn remap(&mut self, to: impl Fn(Cell) -> Cell) {
+        let topology = self.topology();
+        let to = |cell: Cell| topology.wrap(to(cell));
 
-        *self = next_grid;
+        self.cells = self.cells.iter().map(|&cell| to(cell)).collect();
+        self.wires = self.wires.iter().map(|&cell| to(cell)).collect();
+        self.dying = self
+            .dying
+            .drain()
+            .map(|(cell, state)| (to(cell), state))
+            .collect();
+        self.colors = self
+            .colors
+            .drain()
+            .map(|(cell, color)| (to(cell), color))
+            .collect();
+        self.births = self
+            .births
+            .drain()
+            .map(|(cell, born)| (to(cell), born))
+            .collect();
+        self.born.clear();
+        self.died.clear();
+        self.previous.clear();
+        self.trails.clear();
+        self.preview.clear();
+        self.neighbors = None;
+
+        if self.boundary == Boundary::Dead {
+            self.remove_outside_board();
+        }
     }
 
     pub fn clear(&mut self) {
+        self.generation = 0;
         self.cells.clear();
+        self.dying.clear();
+        self.wires.clear();
+        self.colors.clear();
+        self.births.clear();
+        self.trails.clear();
+        self.born.clear();
+        self.died.clear();
+        self.previous.clear();
         self.preview.clear();
-        self.cells_list.clear();
+        self.neighbors = Some(NeighborCounts {
+            topology: self.topology(),
+            counts: HashMap::new(),
+        });
     }
 
     pub fn tick(&mut self) {
-        let mut next_grid = Self::new(self.width, self.height);
+        if self.boundary.wraps() {
+            self.wrap_into_board();
+        }
+        let dying = std::mem::take(&mut self.dying);
 
-        for cell in &self.cells_list {
-            let count = self.count_neighbors(&cell);
-            if count == 2 || count == 3 {
-                next_grid.add_cell(*cell);
+        match (self.engine, self.rule.wolfram) {
+            (_, Some(number)) => {
+                let next = elementary::tick(&self.cells, number, self.board(), self.boundary);
+                self.neighbors = None;
+                self.previous = std::mem::replace(&mut self.cells, next.into_iter().collect());
+            }
+            // the dense engine only knows the eight nearest neighbors, without wrapping
+            (Engine::Sparse, None) => self.sparse_tick(),
+            (Engine::Dense, None)
+                if self.boundary.wraps() || self.rule.neighborhood() != Neighborhood::default() =>
+            {
+                self.sparse_tick()
             }
+            (Engine::Dense, None) => {
+                let next = dense::tick(&self.cells, &self.rule, self.board());
+                self.neighbors = None;
+                self.previous = std::mem::replace(&mut self.cells, next.into_iter().collect());
+            }
+        }
+
+        if self.rule.wireworld {
+            self.remove_off_wire();
+        }
+        if self.rule.states > 2 {
+            self.decay(dying);
+        }
+        if self.boundary == Boundary::Dead {
+            self.remove_outside_board();
+        }
+
+        self.born = self.cells.difference(&self.previous).copied().collect();
+        self.died = self.previous.difference(&self.cells).copied().collect();
+        if self.rule.colors > 1 {
+            self.inherit_colors();
+        }
+        self.preview.clear();
+        self.generation += 1;
+        self.age_cells();
+        if self.show_trails {
+            self.fade_trails();
+        }
+    }
+
+    /// Leaves trails where cells just died and forgets the ones that faded
+    /// out or came back to life.
+    fn fade_trails(&mut self) {
+        let (cells, generation) = (&self.cells, self.generation);
+        self.trails
+            .retain(|cell, died| !cells.contains(cell) && generation - *died < TRAIL_LENGTH);
+        for cell in &self.died {
+            self.trails.insert(*cell, generation);
+        }
+    }
+
+    /// How many generations a live cell has been alive for.
+    pub fn age(&self, cell: &Cell) -> usize {
+        let born = self.births.get(cell).copied().unwrap_or_default();
+        self.generation.saturating_sub(born)
+    }
 
-            self.for_each_neighbor_of(cell, |neighbor| {
-                if self.count_neighbors(neighbor) == 3 {
-                    next_grid.add_cell(*neighbor);
+    /// Notes when the newborns were born and forgets the cells that died.
+    fn age_cells(&mut self) {
+        let cells = &self.cells;
+        self.births.retain(|cell, _| cells.contains(cell));
+        for cell in &self.born {
+            self.births.insert(*cell, self.generation);
+        }
+    }
+
+    /// Colors the newborns after their parents, the live cells around them
+    /// before the tick, and forgets the colors of cells that died.
+    fn inherit_colors(&mut self) {
+        let topology = self.topology();
+        // after switching to a rule with fewer colors, the extra ones wrap around
+        let color_of = |cell: &Cell| self.colors.get(cell).map_or(0, |c| c % self.rule.colors);
+        let colors = self
+            .cells
+            .iter()
+            .map(|cell| {
+                if !self.born.contains(cell) {
+                    return (*cell, color_of(cell));
                 }
-            });
+
+                let mut parents = vec![0; self.rule.colors];
+                Self::for_each_neighbor_of(cell, topology, |neighbor| {
+                    if self.previous.contains(neighbor) {
+                        parents[color_of(neighbor)] += 1;
+                    }
+                });
+                let missing: Vec<usize> = (0..parents.len()).filter(|&c| parents[c] == 0).collect();
+                // three parents of different colors in QuadLife give the fourth
+                let color = match missing[..] {
+                    [color] if parents.iter().all(|&count| count <= 1) => color,
+                    _ => (0..parents.len())
+                        .rev()
+                        .max_by_key(|&c| parents[c])
+                        .unwrap_or_default(),
+                };
+                (*cell, color)
+            })
+            .collect();
+        self.colors = colors;
+    }
+
+    /// Lays the seed as wire carrying its electrons. A seed without electrons
+    /// that lands on nothing but wire sparks it instead, which is how a
+    /// single cell starts a signal.
+    fn seed_circuit<S: IsSeed>(&mut self, seed: S, origin: Cell) {
+        let cells = seed.cells(origin);
+        let electrons = seed.electrons(origin);
+        if electrons.is_empty() && cells.iter().all(|cell| self.wires.contains(cell)) {
+            for cell in cells {
+                self.add_cell(cell);
+            }
+            return;
         }
 
-        *self = next_grid
+        self.wires.extend(cells);
+        for (cell, electron) in electrons {
+            match electron {
+                Electron::Head => self.add_cell(cell),
+                Electron::Tail => {
+                    self.dying.insert(cell, 2);
+                }
+            }
+        }
+        self.preview.clear();
     }
 
-    fn count_neighbors(&self, cell: &Cell) -> usize {
-        let mut count = 0;
+    /// Undoes births off the wire, since electrons can't leave it.
+    fn remove_off_wire(&mut self) {
+        let off_wire: Vec<Cell> = self
+            .cells
+            .iter()
+            .filter(|cell| !self.wires.contains(cell))
+            .copied()
+            .collect();
+        for cell in &off_wire {
+            self.remove_cell(cell);
+        }
+    }
 
-        self.for_each_neighbor_of(cell, |neighbor| {
-            if self.cells.get(neighbor).is_some() {
+    /// Moves dying cells one state closer to dead and starts the ones that
+    /// just died, after undoing any births on cells that were still dying.
+    fn decay(&mut self, dying: HashMap<Cell, usize>) {
+        let reborn: Vec<Cell> = self
+            .cells
+            .iter()
+            .filter(|cell| dying.contains_key(cell))
+            .copied()
+            .collect();
+        for cell in &reborn {
+            self.remove_cell(cell);
+        }
+
+        let states = self.rule.states;
+        self.dying = dying
+            .into_iter()
+            .map(|(cell, state)| (cell, state + 1))
+            .filter(|(_, state)| *state < states)
+            .collect();
+        for cell in self.previous.difference(&self.cells) {
+            self.dying.insert(*cell, 2);
+        }
+    }
+
+    /// Which of [`DYING_CELLS`] a dying cell is shaded as, by how far along it
+    /// is.
+    fn dying_cell(&self, state: usize) -> usize {
+        let fading = self.rule.states.saturating_sub(2).max(1);
+        ((state - 2) * DYING_CELLS.len() / fading).min(DYING_CELLS.len() - 1)
+    }
+
+    /// Works out which cells are born and which die from the neighbor counts,
+    /// then only updates the counts around those.
+    fn sparse_tick(&mut self) {
+        let topology = self.topology();
+        let neighbors = match self.neighbor_counts(topology) {
+            Some(neighbors) => std::mem::take(neighbors),
+            None => self.count_all_neighbors(topology),
+        };
+
+        let deaths: Vec<Cell> = self
+            .cells
+            .iter()
+            .filter(|cell| {
+                let count = neighbors.get(cell).copied().unwrap_or_default();
+                !self.rule.survives(count)
+            })
+            .copied()
+            .collect();
+        let mut births: Vec<Cell> = neighbors
+            .iter()
+            .filter(|(cell, &count)| !self.cells.contains(cell) && self.rule.is_born(count))
+            .map(|(&cell, _)| cell)
+            .collect();
+
+        // cells far from any live cell are only reached by scanning the board
+        if self.rule.is_born(0) {
+            births.extend(
+                self.board()
+                    .cells()
+                    .filter(|cell| !self.cells.contains(cell) && !neighbors.contains_key(cell)),
+            );
+        }
+
+        self.neighbors = Some(NeighborCounts {
+            topology,
+            counts: neighbors,
+        });
+        self.previous = self.cells.clone();
+        for cell in &deaths {
+            self.remove_cell(cell);
+        }
+        for cell in births {
+            self.add_cell(cell);
+        }
+    }
+
+    /// How many live neighbors a cell has.
+    pub fn neighbor_count(&self, cell: &Cell) -> usize {
+        let mut count = 0;
+        Self::for_each_neighbor_of(cell, self.topology(), |neighbor| {
+            if self.cells.contains(neighbor) {
                 count += 1;
             }
         });
-
         count
     }
 
-    fn for_each_neighbor_of<F>(&self, cell: &Cell, mut callback: F)
+    fn count_all_neighbors(&self, topology: Topology) -> HashMap<Cell, usize> {
+        let mut neighbors = HashMap::new();
+        for cell in &self.cells {
+            Self::for_each_neighbor_of(cell, topology, |neighbor| {
+                *neighbors.entry(*neighbor).or_default() += 1;
+            });
+        }
+        neighbors
+    }
+
+    fn for_each_neighbor_of<F>(cell: &Cell, topology: Topology, mut callback: F)
     where
         F: FnMut(&Cell),
     {
-        let (x_min, x_max) = (cell.0.saturating_sub(1), cell.0.saturating_add(2));
-        let (y_min, y_max) = (cell.1.saturating_sub(1), cell.1.saturating_add(2));
-
-        for x_offset in x_min..x_max {
-            for y_offset in y_min..y_max {
-                if x_offset == cell.0 && y_offset == cell.1 {
+        let neighborhood = topology.neighborhood;
+        let radius = neighborhood.radius as i64;
+        for x_offset in cell.0 - radius..=cell.0 + radius {
+            for y_offset in cell.1 - radius..=cell.1 + radius {
+                if !neighborhood.contains(x_offset - cell.0, y_offset - cell.1) {
                     continue;
                 }
 
-                let neighbor = (x_offset, y_offset);
-                callback(&neighbor);
+                let neighbor = topology.wrap((x_offset, y_offset));
+                // where a cross-surface's corners meet, a cell can wrap back onto itself
+                if neighbor != *cell {
+                    callback(&neighbor);
+                }
             }
         }
     }
@@ -145,7 +1251,10 @@ impl Grid {
 
 #[cfg(test)]
 mod tests {
-    use crate::grid::Grid;
+    use crate::grid::{Boundary, BoundingBox, Grid, Guides, TRAIL_CELLS, TRAIL_LENGTH};
+    use crate::rule::Rule;
+    use crate::seed::{Orientation, Oscillator, Spaceship, Still};
+    use std::collections::{HashMap, HashSet};
 
     #[test]
     fn test_underpopulation() {
@@ -199,6 +1308,154 @@ mod tests {
         assert!(grid.cells.contains(&(1, 1))); // Cell should become alive
     }
 
+    #[test]
+    fn test_born_cells() {
+        let mut grid = Grid::new(3, 3);
+        grid.add_cell((0, 1)); // Blinker: the ends die, (1,0) and (1,2) are born
+        grid.add_cell((1, 1));
+        grid.add_cell((2, 1));
+        grid.highlight_births = true;
+
+        grid.tick();
+        assert_eq!(grid.born.len(), 2);
+        assert!(grid.born.contains(&(1, 0)));
+        assert!(grid.born.contains(&(1, 2)));
+        assert!(grid.highlight_births); // Settings should survive the tick
+    }
+
+    #[test]
+    fn test_trails() {
+        let mut grid = Grid::new(20, 5);
+        grid.show_trails = true;
+        grid.seed(Spaceship::LwSpaceship, (15, 1));
+        for _ in 0..TRAIL_LENGTH {
+            grid.tick();
+        }
+
+        let shades = |grid: &Grid| TRAIL_CELLS.map(|shade| grid.to_string().matches(shade).count());
+        assert!(shades(&grid).iter().all(|&count| count > 0));
+        // nothing is left behind once the ship flies off
+        for _ in 0..100 {
+            grid.tick();
+        }
+        assert!(grid
+            .trails
+            .values()
+            .all(|died| grid.generation - died < TRAIL_LENGTH));
+        grid.clear();
+        assert_eq!(shades(&grid), [0; 3]);
+    }
+
+    #[test]
+    fn test_neighbor_counts_overlay() {
+        let mut grid = Grid::new(5, 3);
+        grid.add_cell((1, 1));
+        grid.add_cell((2, 1));
+        grid.add_cell((3, 1));
+        grid.show_neighbors = true;
+
+        assert_eq!(grid.to_string(), "１２３２１\n１⬛⬛⬛１\n１２３２１\n");
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut grid = Grid::new(4, 3);
+        grid.add_cell((1, 1));
+        grid.selection = BoundingBox::of(&[(1, 0), (2, 1)]);
+
+        assert_eq!(grid.to_string(), "⬜🔲🔲⬜\n⬜⬛🔲⬜\n⬜⬜⬜⬜\n");
+    }
+
+    #[test]
+    fn test_neighbor_count() {
+        let mut grid = Grid::new(3, 3);
+        grid.add_cell((0, 0));
+        grid.add_cell((2, 2));
+        assert_eq!(grid.neighbor_count(&(1, 1)), 2);
+        assert_eq!(grid.neighbor_count(&(0, 0)), 0); // A cell isn't its own neighbor
+
+        grid.boundary = Boundary::Torus;
+        assert_eq!(grid.neighbor_count(&(0, 0)), 1); // (2,2) wraps around to touch it
+    }
+
+    #[test]
+    fn test_died_cells() {
+        let mut grid = Grid::new(3, 3);
+        grid.add_cell((0, 1)); // Blinker: the ends die, (1,0) and (1,2) are born
+        grid.add_cell((1, 1));
+        grid.add_cell((2, 1));
+        grid.highlight_births = true;
+        grid.highlight_deaths = true;
+
+        grid.tick();
+        assert_eq!(grid.died, HashSet::from([(0, 1), (2, 1)]));
+        assert_eq!(grid.to_string(), "⬜🟨⬜\n🟥⬛🟥\n⬜🟨⬜\n");
+    }
+
+    #[test]
+    fn test_cell_ages() {
+        let mut grid = Grid::new(5, 5);
+        grid.add_cell((1, 2)); // Blinker: the middle stays, the ends come and go
+        grid.add_cell((2, 2));
+        grid.add_cell((3, 2));
+        grid.tick();
+        grid.tick();
+        grid.tick();
+
+        assert_eq!(grid.age(&(2, 2)), 3);
+        assert_eq!(grid.age(&(2, 1)), 0);
+        assert_eq!(grid.births.len(), 3); // The dead are forgotten
+
+        grid.show_age = true;
+        let rows: Vec<String> = grid.to_string().lines().map(String::from).collect();
+        assert_eq!(rows[1], "⬜⬜🟨⬜⬜");
+        assert_eq!(rows[2], "⬜⬜🟧⬜⬜");
+    }
+
+    #[test]
+    fn test_previous_generation() {
+        let mut grid = Grid::new(3, 3);
+        grid.add_cell((0, 1));
+        grid.add_cell((1, 1));
+        grid.add_cell((2, 1));
+
+        grid.tick();
+        assert_eq!(grid.previous, HashSet::from([(0, 1), (1, 1), (2, 1)]));
+        grid.tick();
+        assert_eq!(grid.previous, HashSet::from([(1, 0), (1, 1), (1, 2)]));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let mut grid = Grid::new(3, 3);
+        grid.rule.birth[2] = true; // B23/S23
+        grid.add_cell((0, 0));
+        grid.add_cell((2, 0)); // Dead cell at (1,1) has two neighbors
+        grid.tick();
+        assert!(grid.cells.contains(&(1, 1))); // Cell should become alive
+        assert!(!grid.cells.contains(&(0, 0))); // Cells with 1 neighbor should die
+    }
+
+    #[test]
+    fn test_birth_from_zero_neighbors() {
+        let mut grid = Grid::new(3, 3);
+        grid.rule.birth[0] = true;
+        grid.tick();
+        assert_eq!(grid.cells.len(), 9); // Every empty cell should become alive
+    }
+
+    #[test]
+    fn test_dead_boundary() {
+        let mut grid = Grid::new(3, 3);
+        grid.boundary = Boundary::Dead;
+        grid.add_cell((2, 0)); // Vertical blinker on the right edge
+        grid.add_cell((2, 1));
+        grid.add_cell((2, 2));
+
+        grid.tick();
+        assert_eq!(grid.cells, HashSet::from([(1, 1), (2, 1)])); // (3,1) is off the board
+    }
+
     #[test]
     fn test_resize() {
         let mut grid = Grid::new(5, 5);
@@ -209,6 +1466,444 @@ mod tests {
         assert!(grid.cells.contains(&(4, 4)));
         grid.resize(3, 3);
         assert!(grid.cells.contains(&(2, 2)));
-        assert!(!grid.cells.contains(&(4, 4))); // Cell should be out of bounds
+        assert!(grid.cells.contains(&(4, 4))); // Out of sight, but still alive
+
+        grid.boundary = Boundary::Dead;
+        grid.resize(2, 2);
+        assert!(!grid.cells.contains(&(2, 2))); // Cell should be out of bounds
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let mut grid = Grid::new(10, 10);
+        assert_eq!(grid.bounding_box(), None);
+
+        grid.add_cell((3, 2));
+        grid.add_cell((5, 7));
+        grid.add_cell((4, 4));
+
+        let bounds = grid.bounding_box().unwrap();
+        assert_eq!(
+            bounds,
+            BoundingBox {
+                x: 3,
+                y: 2,
+                width: 3,
+                height: 6
+            }
+        );
+        assert!(bounds.contains(&(5, 7)));
+        assert!(!bounds.contains(&(6, 7)));
+    }
+
+    #[test]
+    fn test_bounding_box_padding() {
+        let bounds = BoundingBox {
+            x: 1,
+            y: 4,
+            width: 2,
+            height: 2,
+        };
+
+        // the box can grow past zero, into negative coordinates
+        assert_eq!(
+            bounds.pad(3),
+            BoundingBox {
+                x: -2,
+                y: 1,
+                width: 8,
+                height: 8
+            }
+        );
+        assert_eq!(bounds.pad(3).center(), (1, 4));
+    }
+
+    #[test]
+    fn test_display_bounds() {
+        let mut grid = Grid::new(4, 3);
+        grid.add_cell((1, 1));
+        grid.add_cell((2, 1));
+        grid.show_bounds = true;
+
+        assert_eq!(grid.to_string(), "🟫🟫🟫🟫\n🟫⬛⬛🟫\n🟫🟫🟫🟫\n");
+    }
+
+    #[test]
+    fn test_preview_overlapping_live_cells() {
+        let mut grid = Grid::new(4, 1);
+        grid.add_cell((1, 0));
+        grid.preview(Oscillator::Blinker, (0, 0));
+
+        assert_eq!(grid.overlap(Oscillator::Blinker, (0, 0)), 1);
+        assert_eq!(grid.overlap(Oscillator::Blinker, (2, 0)), 0);
+        assert_eq!(grid.to_string(), "🟦🟧🟦⬜\n");
+    }
+
+    #[test]
+    fn test_orient_keeps_the_middle() {
+        let mut grid = Grid::new(20, 20);
+        grid.seed(Oscillator::Blinker, (4, 5));
+        grid.births.insert((6, 5), 3);
+
+        grid.orient(Orientation {
+            quarter_turns: 1,
+            ..Default::default()
+        });
+        assert_eq!(grid.cells, HashSet::from([(5, 4), (5, 5), (5, 6)]));
+        assert_eq!(grid.births[&(5, 6)], 3);
+
+        // a glider flipped left to right heads the other way, in the same box
+        let mut grid = Grid::new(20, 20);
+        grid.seed(Spaceship::Glider, (0, 0));
+        let before = grid.bounding_box();
+        grid.orient(Orientation {
+            flip_horizontal: true,
+            ..Default::default()
+        });
+        assert_eq!(grid.bounding_box(), before);
+        assert_eq!(
+            grid.cells,
+            HashSet::from([(0, 0), (1, 1), (-1, 2), (0, 2), (1, 2)])
+        );
+        for _ in 0..4 {
+            grid.tick();
+        }
+        assert_eq!(grid.bounding_box().map(|bounds| bounds.x), Some(0));
+    }
+
+    #[test]
+    fn test_translate_across_edges() {
+        let mut grid = Grid::new(10, 10);
+        grid.boundary = Boundary::Torus;
+        grid.seed(Still::Block, (8, 4));
+        assert!(grid.translate(1, -5));
+        assert_eq!(grid.cells, HashSet::from([(9, 9), (0, 9), (9, 0), (0, 0)]));
+
+        grid.clear();
+        grid.boundary = Boundary::Dead;
+        grid.seed(Still::Block, (7, 4));
+        assert!(grid.translate(1, 0));
+        assert!(!grid.translate(1, 0));
+        assert_eq!(grid.cells, HashSet::from([(8, 4), (9, 4), (8, 5), (9, 5)]));
+    }
+
+    #[test]
+    fn test_crop() {
+        let mut grid = Grid::new(40, 20);
+        grid.seed(Oscillator::Blinker, (10, 5));
+
+        let cropped = grid.crop(2).map(|board| (board.width, board.height));
+        assert_eq!(cropped, Some((7, 5)));
+        assert_eq!((grid.width, grid.height), (7, 5));
+        assert_eq!(grid.cells, HashSet::from([(2, 2), (3, 2), (4, 2)]));
+
+        grid.clear();
+        assert_eq!(grid.crop(2), None);
+    }
+
+    #[test]
+    fn test_zoom_to_fit() {
+        let mut grid = Grid::new(100, 100);
+        grid.resize_viewport(10, 5);
+        assert!(!grid.zoom_to_fit());
+
+        grid.add_cell((20, 30));
+        grid.add_cell((50, 40));
+        assert!(grid.zoom_to_fit());
+        assert_eq!(grid.zoom, 4);
+        assert!(grid.viewport.contains(&(20, 30)));
+        assert!(grid.viewport.contains(&(50, 40)));
+
+        // close up again once there's less to see
+        grid.remove_cell(&(50, 40));
+        assert!(grid.zoom_to_fit());
+        assert_eq!(grid.zoom, 1);
+        assert!(grid.viewport.contains(&(20, 30)));
+    }
+
+    #[test]
+    fn test_guides() {
+        let mut grid = Grid::new(24, 12);
+        grid.viewport.x = -5;
+        grid.viewport.y = 8;
+        assert_eq!(grid.guides(), Guides::default());
+
+        grid.show_guides = true;
+        assert_eq!(
+            grid.guides(),
+            Guides {
+                columns: vec![(5, 0), (15, 10)],
+                rows: vec![(2, 10)],
+            }
+        );
+
+        // zoomed out, each line goes through the character showing its cell
+        grid.set_zoom(2);
+        grid.viewport.x = -5;
+        assert_eq!(grid.guides().columns, vec![(2, 0), (12, 20), (22, 40)]);
+    }
+
+    #[test]
+    fn test_guides_drawn_over_dead_cells() {
+        let mut grid = Grid::new(3, 2);
+        grid.show_guides = true;
+        grid.viewport.x = -1;
+        grid.add_cell((1, 1));
+        assert_eq!(grid.to_string(), "──┼───\n⬜│ ⬛\n");
+    }
+
+    #[test]
+    fn test_cells_live_past_the_top_left_edges() {
+        let mut grid = Grid::new(5, 5);
+        // a glider heading up and left, already touching the corner
+        for cell in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 2)] {
+            grid.add_cell(cell);
+        }
+
+        for _ in 0..4 {
+            grid.tick();
+        }
+
+        // the same glider, one cell further up and left, not clamped to zero
+        let expected = HashSet::from([(-1, -1), (0, -1), (1, -1), (-1, 0), (0, 1)]);
+        assert_eq!(grid.cells, expected);
+    }
+
+    #[test]
+    fn test_torus_wraps_around() {
+        let mut grid = Grid::new(6, 6);
+        grid.boundary = Boundary::Torus;
+        let glider = [(0, 0), (1, 0), (2, 0), (0, 1), (1, 2)];
+        for cell in glider {
+            grid.add_cell(cell);
+        }
+
+        // a glider moves one cell diagonally every four generations
+        for _ in 0..4 {
+            grid.tick();
+        }
+        let expected = HashSet::from([(5, 5), (0, 5), (1, 5), (5, 0), (0, 1)]);
+        assert_eq!(grid.cells, expected);
+
+        for _ in 0..20 {
+            grid.tick();
+        }
+        assert_eq!(grid.cells, HashSet::from(glider));
+    }
+
+    #[test]
+    fn test_wrapping_edges() {
+        let wrap = |boundary: Boundary, cell| boundary.wrap(cell, 6, 4);
+
+        assert_eq!(wrap(Boundary::Open, (-1, 7)), (-1, 7));
+        assert_eq!(wrap(Boundary::Torus, (-1, 7)), (5, 3));
+
+        // the left and right edges wrap as on a torus, the top and bottom mirrored
+        assert_eq!(wrap(Boundary::Klein, (-1, 2)), (5, 2));
+        assert_eq!(wrap(Boundary::Klein, (1, -1)), (4, 3));
+        assert_eq!(wrap(Boundary::Klein, (1, 4)), (4, 0));
+
+        // every edge mirrored
+        assert_eq!(wrap(Boundary::Cross, (-1, 1)), (5, 2));
+        assert_eq!(wrap(Boundary::Cross, (6, 0)), (0, 3));
+        assert_eq!(wrap(Boundary::Cross, (1, -1)), (4, 3));
+        // the corners meet
+        assert_eq!(wrap(Boundary::Cross, (-1, -1)), (0, 0));
+    }
+
+    #[test]
+    fn test_klein_bottle_mirrors_across_the_top() {
+        let mut grid = Grid::new(6, 4);
+        grid.boundary = Boundary::Klein;
+        // a vertical blinker whose top cell came back mirrored at the bottom
+        for cell in [(4, 3), (1, 0), (1, 1)] {
+            grid.add_cell(cell);
+        }
+
+        grid.tick();
+        assert_eq!(grid.cells, HashSet::from([(0, 0), (1, 0), (2, 0)]));
+    }
+
+    #[test]
+    fn test_generations_cells_fade_out() {
+        let mut grid = Grid::new(3, 3);
+        grid.rule = "B2/S/C3".parse().unwrap(); // Brian's Brain
+        grid.add_cell((0, 0));
+        grid.add_cell((1, 0));
+
+        grid.tick();
+        assert_eq!(
+            grid.cells,
+            HashSet::from([(0, -1), (1, -1), (0, 1), (1, 1)])
+        );
+        assert_eq!(grid.dying, HashMap::from([((0, 0), 2), ((1, 0), 2)]));
+        assert_eq!(grid.to_string(), "🟥🟥⬜\n⬛⬛⬜\n⬜⬜⬜\n");
+
+        grid.tick();
+        assert!(!grid.dying.contains_key(&(0, 0)));
+        assert_eq!(grid.dying[&(0, 1)], 2);
+    }
+
+    #[test]
+    fn test_dying_cells_are_not_born() {
+        let mut grid = Grid::new(3, 3);
+        grid.rule = "B2/S/C3".parse().unwrap();
+        grid.add_cell((-1, -1));
+        grid.add_cell((1, 1));
+        // the only cell with two neighbors
+        grid.dying.insert((0, 0), 2);
+
+        grid.tick();
+        assert!(grid.cells.is_empty());
+        assert_eq!(grid.dying, HashMap::from([((-1, -1), 2), ((1, 1), 2)]));
+
+        // back to a Life-like rule, nothing fades
+        grid.rule = Rule::default();
+        grid.tick();
+        assert!(grid.dying.is_empty());
+    }
+
+    #[test]
+    fn test_larger_than_life_neighborhoods() {
+        let mut grid = Grid::new(10, 10);
+        grid.add_cell((0, 0));
+
+        grid.rule = "R2,C0,M0,S1..1,B1..1,NM".parse().unwrap();
+        let counts = grid.count_all_neighbors(grid.topology());
+        assert_eq!(counts.len(), 24);
+        assert_eq!(counts[&(2, -2)], 1);
+
+        grid.rule = "R2,C0,M0,S1..1,B1..1,NN".parse().unwrap();
+        let counts = grid.count_all_neighbors(grid.topology());
+        assert_eq!(counts.len(), 12);
+        assert!(!counts.contains_key(&(2, -2)));
+    }
+
+    #[test]
+    fn test_larger_than_life_with_radius_one_is_life_like() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let run = |rule: &str| {
+            let mut grid = Grid::new(20, 20);
+            grid.rule = rule.parse().unwrap();
+            for cell in glider {
+                grid.add_cell(cell);
+            }
+            for _ in 0..8 {
+                grid.tick();
+            }
+            grid.cells
+        };
+
+        let life = run("B3/S23");
+        assert_eq!(run("R1,C0,M0,S2..3,B3..3,NM"), life);
+        // counting the cell itself shifts survival up by one
+        assert_eq!(run("R1,C0,M1,S3..4,B3..3,NM"), life);
+    }
+
+    #[test]
+    fn test_changing_boundary_recounts_neighbors() {
+        let mut grid = Grid::new(6, 6);
+        // a blinker split across the left and right edges, only whole on a torus
+        for cell in [(5, 2), (0, 2), (1, 2)] {
+            grid.add_cell(cell);
+        }
+        grid.tick();
+        assert!(grid.cells.is_empty());
+
+        for cell in [(5, 2), (0, 2), (1, 2)] {
+            grid.add_cell(cell);
+        }
+        grid.boundary = Boundary::Torus;
+        grid.tick();
+        assert_eq!(grid.cells, HashSet::from([(0, 1), (0, 2), (0, 3)]));
+
+        grid.boundary = Boundary::Open;
+        grid.tick();
+        assert_eq!(grid.cells, HashSet::from([(-1, 2), (0, 2), (1, 2)]));
+    }
+
+    #[test]
+    fn test_viewport() {
+        let mut grid = Grid::new(2, 2);
+        grid.add_cell((-1, -1));
+        grid.add_cell((5, 5));
+        assert_eq!(grid.to_string(), "⬜⬜\n⬜⬜\n");
+
+        grid.pan(-1, -1);
+        assert_eq!(grid.to_string(), "⬛⬜\n⬜⬜\n");
+        assert_eq!(grid.to_universe((1, 0)), (0, -1));
+        assert_eq!(grid.clamp_to_viewport((10, -10)), (0, -1));
+    }
+
+    #[test]
+    fn test_zoomed_out_shading() {
+        let mut grid = Grid::new(4, 2);
+        // a full square, a single cell, and nothing
+        for cell in [(0, 0), (1, 0), (0, 1), (1, 1), (3, 1)] {
+            grid.add_cell(cell);
+        }
+
+        grid.set_zoom(2);
+        grid.resize_viewport(3, 1);
+        grid.viewport.x = 0;
+        grid.viewport.y = 0;
+
+        assert_eq!(grid.to_string(), "⬛░░⬜\n");
+        assert_eq!(grid.to_universe((1, 0)), (2, 0));
+    }
+
+    #[test]
+    fn test_immigration_newborns_take_the_majority_color() {
+        let mut grid = Grid::new(10, 10);
+        grid.rule = "immigration".parse().unwrap();
+        // a blinker with one cell of the second color
+        for (cell, color) in [((4, 5), 0), ((5, 5), 0), ((6, 5), 1)] {
+            grid.color = color;
+            grid.seed(cell, cell);
+        }
+        assert_eq!(grid.colors[&(6, 5)], 1);
+
+        grid.tick();
+        assert_eq!(
+            grid.colors,
+            HashMap::from([((5, 4), 0), ((5, 5), 0), ((5, 6), 0)])
+        );
+    }
+
+    #[test]
+    fn test_quadlife_newborns_of_three_colors_take_the_fourth() {
+        let mut grid = Grid::new(10, 10);
+        grid.rule = "quadlife".parse().unwrap();
+        for (cell, color) in [((4, 5), 0), ((5, 5), 1), ((6, 5), 2)] {
+            grid.color = color;
+            grid.seed(cell, cell);
+        }
+
+        grid.tick();
+        assert_eq!(grid.colors[&(5, 4)], 3);
+        assert_eq!(grid.colors[&(5, 6)], 3);
+        // survivors keep theirs
+        assert_eq!(grid.colors[&(5, 5)], 1);
+        assert_eq!(
+            grid.to_string().lines().nth(4),
+            Some("⬜⬜⬜⬜⬜🟩⬜⬜⬜⬜")
+        );
+    }
+
+    #[test]
+    fn test_neighbor_counts_follow_births_and_deaths() {
+        let mut grid = Grid::new(10, 10);
+        grid.boundary = Boundary::Dead;
+        // an R-pentomino touching the bottom edge
+        for cell in [(5, 7), (6, 7), (4, 8), (5, 8), (5, 9)] {
+            grid.add_cell(cell);
+        }
+
+        for _ in 0..20 {
+            grid.tick();
+
+            let recounted = grid.count_all_neighbors(grid.topology());
+            assert_eq!(grid.neighbors.as_ref().map(|n| &n.counts), Some(&recounted));
+        }
     }
 }
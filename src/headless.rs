@@ -0,0 +1,214 @@
+use crate::{
+    args::{Args, Format},
+    grid::{Cell, Grid},
+    pattern::Pattern,
+    stats::StatsLog,
+};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    io::{ErrorKind, Read, Write},
+    thread,
+    time::Duration,
+};
+
+/// A generation as written by `--format json`.
+#[derive(Debug, Serialize)]
+struct JsonFrame<'c> {
+    generation: usize,
+    population: usize,
+    cells: &'c [Cell],
+}
+
+/// Runs without the terminal UI: reads a plaintext board from `input` and
+/// writes it and each following generation to `output`, one frame at a time,
+/// until `--generations` have run or `output` is closed.
+pub fn run<R: Read, W: Write>(args: &Args, mut input: R, mut output: W) -> std::io::Result<()> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let pattern = Pattern::parse_cells(&text).map_err(std::io::Error::other)?;
+
+    let mut game = Grid::new(
+        args.width.unwrap_or(pattern.width),
+        args.height.unwrap_or(pattern.height),
+    );
+    game.seed(pattern, (0, 0));
+    if let Some(rule) = args.rule {
+        game.rule = rule;
+    }
+    if let Some(boundary) = args.boundary {
+        game.boundary = boundary;
+    }
+    game.engine = args.engine.unwrap_or_default();
+
+    let mut stats = match &args.stats {
+        Some(path) => Some(StatsLog::create(path, &game)?),
+        None => None,
+    };
+    let result = match stream(args, &mut game, &mut output, stats.as_mut()) {
+        // whoever reads the frames stopped, e.g. `head`
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    };
+    match stats {
+        Some(stats) => result.and(stats.finish()),
+        None => result,
+    }
+}
+
+fn stream<W: Write, S: Write>(
+    args: &Args,
+    game: &mut Grid,
+    mut output: W,
+    mut stats: Option<&mut StatsLog<S>>,
+) -> std::io::Result<()> {
+    let interval = match args.fps {
+        Some(fps) if fps > 0 => Some(Duration::from_secs(1) / fps),
+        _ => None,
+    };
+
+    for generation in 0.. {
+        match args.format {
+            Format::Text => {
+                write_frame(&mut output, &game.cells, game.width, game.height)?;
+                writeln!(output, "{}", args.separator)?;
+            }
+            Format::Json => write_json_frame(&mut output, generation, &game.cells)?,
+        }
+        output.flush()?;
+
+        if args
+            .generations
+            .is_some_and(|generations| generation >= generations)
+        {
+            break;
+        }
+        if let Some(interval) = interval {
+            thread::sleep(interval);
+        }
+        game.tick();
+        if let Some(stats) = &mut stats {
+            stats.record(game)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a `width` by `height` board as rows of `O` (alive) and `.` (dead).
+pub fn write_frame<W: Write>(
+    mut writer: W,
+    cells: &HashSet<Cell>,
+    width: usize,
+    height: usize,
+) -> std::io::Result<()> {
+    for y in 0..height as i64 {
+        let row: String = (0..width as i64)
+            .map(|x| if cells.contains(&(x, y)) { 'O' } else { '.' })
+            .collect();
+        writeln!(writer, "{}", row)?;
+    }
+    Ok(())
+}
+
+/// Writes a generation as a single line of JSON, with cells sorted by row.
+pub fn write_json_frame<W: Write>(
+    mut writer: W,
+    generation: usize,
+    cells: &HashSet<Cell>,
+) -> std::io::Result<()> {
+    let mut sorted: Vec<Cell> = cells.iter().copied().collect();
+    sorted.sort_unstable_by_key(|&(x, y)| (y, x));
+
+    let frame = JsonFrame {
+        generation,
+        population: sorted.len(),
+        cells: &sorted,
+    };
+    serde_json::to_writer(&mut writer, &frame)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse_from(std::iter::once("cli-game-of-life").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_blinker_frames() {
+        let mut output = Vec::new();
+        run(
+            &args(&["--pipe", "--generations", "2", "--separator", "--"]),
+            "...\nOOO\n...\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "...\nOOO\n...\n--\n.O.\n.O.\n.O.\n--\n...\nOOO\n...\n--\n"
+        );
+    }
+
+    #[test]
+    fn test_board_size() {
+        let mut output = Vec::new();
+        run(
+            &args(&[
+                "--pipe",
+                "--generations",
+                "0",
+                "--width",
+                "4",
+                "--height",
+                "2",
+            ]),
+            "O\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "O...\n....\n\n");
+    }
+
+    #[test]
+    fn test_json_frames() {
+        let mut output = Vec::new();
+        run(
+            &args(&["--pipe", "--format", "json", "--generations", "1"]),
+            "...\nOOO\n...\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            serde_json::json!({
+                "generation": 0,
+                "population": 3,
+                "cells": [[0, 1], [1, 1], [2, 1]],
+            })
+        );
+        assert_eq!(
+            lines[1]["cells"],
+            serde_json::json!([[1, 0], [1, 1], [1, 2]])
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let result = run(&args(&["--pipe"]), "O?O\n".as_bytes(), Vec::new());
+        assert!(result.is_err());
+    }
+}
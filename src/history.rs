@@ -0,0 +1,191 @@
+use crate::grid::{Cell, Grid};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// How many edits can be undone.
+pub const HISTORY_LENGTH: usize = 100;
+
+/// What's on the board at some point: live cells and their ages, along with
+/// the fading cells, wire and colors of the rules that have them, and how big
+/// the board was.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    generation: usize,
+    width: usize,
+    height: usize,
+    cells: HashSet<Cell>,
+    dying: HashMap<Cell, usize>,
+    wires: HashSet<Cell>,
+    colors: HashMap<Cell, usize>,
+    births: HashMap<Cell, usize>,
+}
+
+impl Snapshot {
+    pub fn of(grid: &Grid) -> Snapshot {
+        Snapshot {
+            generation: grid.generation,
+            width: grid.width,
+            height: grid.height,
+            cells: grid.cells.clone(),
+            dying: grid.dying.clone(),
+            wires: grid.wires.clone(),
+            colors: grid.colors.clone(),
+            births: grid.births.clone(),
+        }
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Puts the board back the way it was, leaving the rule and edges alone.
+    pub fn restore(&self, grid: &mut Grid) {
+        grid.clear();
+        grid.resize(self.width, self.height);
+        for cell in &self.cells {
+            grid.add_cell(*cell);
+        }
+        grid.dying = self.dying.clone();
+        grid.wires = self.wires.clone();
+        grid.colors = self.colors.clone();
+        grid.births = self.births.clone();
+        grid.generation = self.generation;
+    }
+}
+
+/// The boards from before the latest edits, newest last, and the ones undone
+/// since, so they can be redone until the next edit.
+#[derive(Debug)]
+pub struct History {
+    undo: VecDeque<Snapshot>,
+    redo: Vec<Snapshot>,
+    capacity: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new(HISTORY_LENGTH)
+    }
+}
+
+impl History {
+    pub fn new(capacity: usize) -> History {
+        History {
+            undo: VecDeque::with_capacity(capacity),
+            redo: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Remembers the board before an edit, dropping the oldest one when full.
+    pub fn record(&mut self, grid: &Grid) {
+        if self.undo.len() == self.capacity {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(Snapshot::of(grid));
+        self.redo.clear();
+    }
+
+    /// Goes back to the board from before the latest edit, returning false
+    /// when there's nothing left to undo.
+    pub fn undo(&mut self, grid: &mut Grid) -> bool {
+        let Some(snapshot) = self.undo.pop_back() else {
+            return false;
+        };
+        self.redo.push(Snapshot::of(grid));
+        snapshot.restore(grid);
+        true
+    }
+
+    /// Brings back the board the latest undo went back from, returning false
+    /// when there's nothing to redo.
+    pub fn redo(&mut self, grid: &mut Grid) -> bool {
+        let Some(snapshot) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push_back(Snapshot::of(grid));
+        snapshot.restore(grid);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::{Oscillator, Seed};
+
+    #[test]
+    fn test_undo_and_redo() {
+        let mut grid = Grid::new(10, 10);
+        let mut history = History::default();
+
+        history.record(&grid);
+        grid.seed(Seed::Oscillator(Oscillator::Blinker), (4, 4));
+        let placed = grid.cells.clone();
+        history.record(&grid);
+        grid.tick();
+        let ticked = grid.cells.clone();
+
+        assert!(history.undo(&mut grid));
+        assert_eq!(grid.cells, placed);
+        assert_eq!(grid.generation, 0);
+        assert!(history.undo(&mut grid));
+        assert!(grid.cells.is_empty());
+        assert!(!history.undo(&mut grid));
+
+        assert!(history.redo(&mut grid));
+        assert!(history.redo(&mut grid));
+        assert_eq!(grid.cells, ticked);
+        assert_eq!(grid.generation, 1);
+        assert!(!history.redo(&mut grid));
+
+        // neighbor counts are rebuilt, so the restored board ticks as usual
+        grid.tick();
+        assert_eq!(grid.cells, placed);
+    }
+
+    #[test]
+    fn test_edit_forgets_redo() {
+        let mut grid = Grid::new(10, 10);
+        let mut history = History::default();
+
+        history.record(&grid);
+        grid.add_cell((1, 1));
+        assert!(history.undo(&mut grid));
+
+        history.record(&grid);
+        grid.add_cell((2, 2));
+        assert!(!history.redo(&mut grid));
+        assert_eq!(grid.cells, HashSet::from([(2, 2)]));
+    }
+
+    #[test]
+    fn test_undo_restores_size() {
+        let mut grid = Grid::new(10, 10);
+        let mut history = History::default();
+
+        grid.add_cell((8, 8));
+        history.record(&grid);
+        grid.crop(1);
+        assert_eq!((grid.width, grid.height), (3, 3));
+
+        assert!(history.undo(&mut grid));
+        assert_eq!((grid.width, grid.height), (10, 10));
+        assert_eq!(grid.cells, HashSet::from([(8, 8)]));
+    }
+
+    #[test]
+    fn test_keeps_latest() {
+        let mut grid = Grid::new(10, 10);
+        let mut history = History::new(2);
+
+        for x in 0..3 {
+            history.record(&grid);
+            grid.add_cell((x, 0));
+        }
+
+        assert!(history.undo(&mut grid));
+        assert!(history.undo(&mut grid));
+        assert!(!history.undo(&mut grid));
+        assert_eq!(grid.cells, HashSet::from([(0, 0)]));
+    }
+}
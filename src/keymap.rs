@@ -0,0 +1,413 @@
+use crate::config::Config;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+    io::Write,
+    str::FromStr,
+};
+
+/// Every action a key can be bound to, by name, with its key by default.
+/// `Esc` always closes and quits, and the digits always pick seeds and,
+/// with `Alt`, set the speed.
+pub const ACTIONS: [(&str, &str); 72] = [
+    ("quit", "q"),
+    ("play", "p"),
+    ("tick", "enter"),
+    ("place", "space"),
+    ("up", "up"),
+    ("down", "down"),
+    ("left", "left"),
+    ("right", "right"),
+    ("center", "home"),
+    ("fit", "end"),
+    ("zoom-in", "+"),
+    ("zoom-out", "-"),
+    ("slower", "["),
+    ("faster", "]"),
+    ("slow-motion", "alt+0"),
+    ("fast-forward", ">"),
+    ("clear", "delete"),
+    ("undo", "u"),
+    ("redo", "ctrl+r"),
+    ("single-cell", "0"),
+    ("next-category", "tab"),
+    ("previous-category", "shift+tab"),
+    ("next-seed", "pagedown"),
+    ("previous-seed", "pageup"),
+    ("browse-seeds", "ctrl+y"),
+    ("draw-seed", "ctrl+w"),
+    ("load-pattern", "l"),
+    ("scatter", "ctrl+a"),
+    ("turn-left", ","),
+    ("turn-right", "."),
+    ("flip-across", "/"),
+    ("flip-down", ";"),
+    ("select", "ctrl+space"),
+    ("copy", "ctrl+c"),
+    ("cut", "ctrl+x"),
+    ("paste", "ctrl+v"),
+    ("stamp", "ctrl+b"),
+    ("move", "ctrl+d"),
+    ("crop", "ctrl+k"),
+    ("snap", "s"),
+    ("edges", "w"),
+    ("mirror", "\\"),
+    ("mirror-at-cursor", "|"),
+    ("seed-color", "t"),
+    ("goto", "g"),
+    ("advance", "ctrl+f"),
+    ("breakpoint", "ctrl+p"),
+    ("watch", "v"),
+    ("analyze", "?"),
+    ("census", "n"),
+    ("neighbors", "ctrl+n"),
+    ("remove-escaped", "z"),
+    ("highlight", "h"),
+    ("onion-skin", "o"),
+    ("age", "y"),
+    ("theme", "c"),
+    ("draw", "b"),
+    ("measure", "m"),
+    ("guides", "#"),
+    ("graph", "ctrl+g"),
+    ("log-stats", "ctrl+t"),
+    ("edit-rule", "r"),
+    ("explore-rules", "x"),
+    ("leaderboard", "ctrl+l"),
+    ("command", ":"),
+    ("keys", "f1"),
+    ("bookmark", "k"),
+    ("jump", "j"),
+    ("instant-replay", "i"),
+    ("export", "ctrl+e"),
+    ("save", "ctrl+s"),
+    ("load", "ctrl+o"),
+];
+
+/// A key with the modifiers held with it. Letters stand for themselves in
+/// either case, so `shift` only matters on keys that aren't characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    /// The key as pressed, with shifted characters' `Shift` left out and
+    /// letters made lowercase, as bindings are written.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        match code {
+            KeyCode::Char(ch) => Key {
+                code: KeyCode::Char(ch.to_ascii_lowercase()),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            KeyCode::BackTab => Key {
+                code,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            _ => Key { code, modifiers },
+        }
+    }
+}
+
+/// Keys without a character, by name.
+const KEY_NAMES: [(&str, KeyCode); 14] = [
+    ("space", KeyCode::Char(' ')),
+    ("enter", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("backspace", KeyCode::Backspace),
+    ("delete", KeyCode::Delete),
+    ("insert", KeyCode::Insert),
+    ("home", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+];
+
+const MODIFIER_NAMES: [(&str, KeyModifiers); 3] = [
+    ("ctrl", KeyModifiers::CONTROL),
+    ("alt", KeyModifiers::ALT),
+    ("shift", KeyModifiers::SHIFT),
+];
+
+impl FromStr for Key {
+    type Err = String;
+
+    /// Parses keys written like `q`, `ctrl+y`, `shift+tab`, `alt+,`, `f5`
+    /// or `pagedown`, in any case.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid key {:?}, expected e.g. q, ctrl+y or pagedown",
+                text
+            )
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text.trim();
+        // a `+` after the modifiers is the key itself, as in `ctrl++`
+        while let Some((name, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            let (_, modifier) = MODIFIER_NAMES
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name.trim()))
+                .ok_or_else(invalid)?;
+            modifiers |= *modifier;
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ if rest.eq_ignore_ascii_case("tab") && modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::BackTab
+            }
+            _ => match KEY_NAMES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(rest))
+            {
+                Some((_, code)) => *code,
+                None => match rest.strip_prefix(['f', 'F']).map(str::parse) {
+                    Some(Ok(number @ 1..=12)) => KeyCode::F(number),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+        Ok(Key::new(code, modifiers))
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (name, modifier) in MODIFIER_NAMES {
+            if self.modifiers.contains(modifier)
+                || (self.code == KeyCode::BackTab && name == "shift")
+            {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match self.code {
+            KeyCode::BackTab => write!(f, "tab"),
+            KeyCode::F(number) => write!(f, "f{}", number),
+            KeyCode::Char(ch) if ch != ' ' => write!(f, "{}", ch),
+            code => match KEY_NAMES.iter().find(|(_, known)| *known == code) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{:?}", code),
+            },
+        }
+    }
+}
+
+/// Which key does what: the defaults, with those the config file changes.
+/// Keys are turned into the ones the game knows the actions by before
+/// they're handled, the way the gamepad's buttons are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    /// Each action's name, its key by default and the key it's bound to.
+    bindings: Vec<(&'static str, Key, Key)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Keymap {
+            bindings: ACTIONS
+                .iter()
+                .map(|(name, key)| {
+                    let key = key.parse().expect("default keys are valid");
+                    (*name, key, key)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// The defaults with the keys from the config file, by action name,
+    /// failing on unknown actions or keys and on keys bound twice.
+    pub fn new(keys: &BTreeMap<String, String>) -> Result<Keymap, String> {
+        let mut keymap = Keymap::default();
+        for (action, key) in keys {
+            let binding = keymap
+                .bindings
+                .iter_mut()
+                .find(|(name, _, _)| name == action)
+                .ok_or_else(|| format!("no action {:?} to bind a key to", action))?;
+            binding.2 = key.parse()?;
+        }
+
+        for (index, (name, _, key)) in keymap.bindings.iter().enumerate() {
+            if let Some((other, _, _)) = keymap.bindings[..index]
+                .iter()
+                .find(|(_, _, bound)| bound == key)
+            {
+                return Err(format!("{} is bound to both {} and {}", key, other, name));
+            }
+        }
+        Ok(keymap)
+    }
+
+    /// The keymap set in `config.toml`, or the defaults without one.
+    pub fn load() -> Result<Keymap, String> {
+        Keymap::new(&Config::load()?.keys).map_err(|error| format!("config.toml: {}", error))
+    }
+
+    /// The key the game knows what's pressed by: that of the action it's
+    /// bound to, keeping any other modifiers held with it, as with `Shift`
+    /// and a key bound to moving. A default key whose action was moved to
+    /// another key does nothing, so `None`, and other keys are left alone.
+    pub fn translate(&self, code: KeyCode, modifiers: KeyModifiers) -> Option<Key> {
+        let pressed = Key::new(code, modifiers);
+        let plain = Key::new(pressed.code, KeyModifiers::NONE);
+        let bound = |pressed: Key| self.bindings.iter().find(|(_, _, key)| *key == pressed);
+        match (bound(pressed), bound(plain)) {
+            (Some((_, default, key)), _) if default != key => Some(*default),
+            (None, Some((_, default, key))) if default != key => Some(Key {
+                modifiers: default.modifiers | pressed.modifiers,
+                ..*default
+            }),
+            (None, None)
+                if self.bindings.iter().any(|(_, default, key)| {
+                    default != key && (*default == pressed || *default == plain)
+                }) =>
+            {
+                None
+            }
+            _ => Some(Key { code, modifiers }),
+        }
+    }
+
+    /// Writes the bindings the way the config file takes them.
+    pub fn write<W: Write>(&self, mut output: W) -> std::io::Result<()> {
+        writeln!(output, "[keys]")?;
+        for (name, _, key) in &self.bindings {
+            writeln!(output, "{} = {:?}", name, key.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the keys each action is bound to, as the config file takes them.
+pub fn run<W: Write>(output: W) -> std::io::Result<()> {
+    Keymap::load().map_err(std::io::Error::other)?.write(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(keys: &[(&str, &str)]) -> Result<Keymap, String> {
+        let keys = keys
+            .iter()
+            .map(|(action, key)| (action.to_string(), key.to_string()))
+            .collect();
+        Keymap::new(&keys)
+    }
+
+    fn key(text: &str) -> Key {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(key("Q"), Key::new(KeyCode::Char('q'), KeyModifiers::NONE));
+        assert_eq!(
+            key("ctrl+alt+Y"),
+            Key::new(
+                KeyCode::Char('y'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            )
+        );
+        assert_eq!(key("ctrl++").code, KeyCode::Char('+'));
+        assert_eq!(key("shift+tab").code, KeyCode::BackTab);
+        assert_eq!(key("F5").code, KeyCode::F(5));
+        assert!("f13".parse::<Key>().is_err());
+        assert!("hyper+q".parse::<Key>().is_err());
+        assert!("esc".parse::<Key>().is_err());
+        assert!("".parse::<Key>().is_err());
+
+        for text in [
+            "q",
+            "ctrl+space",
+            "alt+0",
+            "shift+tab",
+            "shift+up",
+            "+",
+            "f12",
+        ] {
+            assert_eq!(key(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let keymap = keymap(&[]).unwrap();
+        assert_eq!(keymap, Keymap::default());
+        for (code, modifiers) in [
+            (KeyCode::Char('P'), KeyModifiers::SHIFT),
+            (KeyCode::Up, KeyModifiers::SHIFT),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL),
+            (KeyCode::Char('7'), KeyModifiers::NONE),
+        ] {
+            assert_eq!(
+                keymap.translate(code, modifiers),
+                Some(Key { code, modifiers })
+            );
+        }
+    }
+
+    #[test]
+    fn test_translate() {
+        let keymap = keymap(&[
+            ("quit", "ctrl+q"),
+            ("place", "x"),
+            ("explore-rules", "space"),
+            ("turn-left", "a"),
+        ])
+        .unwrap();
+        let translate = |code, modifiers| keymap.translate(code, modifiers);
+
+        assert_eq!(translate(KeyCode::Char('q'), KeyModifiers::NONE), None);
+        assert_eq!(
+            translate(KeyCode::Char('q'), KeyModifiers::CONTROL),
+            Some(key("q"))
+        );
+        // swapped
+        assert_eq!(
+            translate(KeyCode::Char('X'), KeyModifiers::SHIFT),
+            Some(key("space"))
+        );
+        assert_eq!(
+            translate(KeyCode::Char(' '), KeyModifiers::NONE),
+            Some(key("x"))
+        );
+        // other modifiers held with it are kept, and bindings with their own
+        // are left alone
+        assert_eq!(
+            translate(KeyCode::Char('a'), KeyModifiers::ALT),
+            Some(key("alt+,"))
+        );
+        assert_eq!(translate(KeyCode::Char(','), KeyModifiers::ALT), None);
+        assert_eq!(
+            translate(KeyCode::Char(' '), KeyModifiers::CONTROL),
+            Some(key("ctrl+space"))
+        );
+    }
+
+    #[test]
+    fn test_conflicts() {
+        assert_eq!(
+            keymap(&[("place", "p")]),
+            Err(String::from("p is bound to both play and place"))
+        );
+        assert_eq!(
+            keymap(&[("turn-left", "ctrl+C")]),
+            Err(String::from("ctrl+c is bound to both turn-left and copy"))
+        );
+        assert!(keymap(&[("play", "space"), ("place", "p")]).is_ok());
+        assert!(keymap(&[("fly", "f")]).is_err());
+        assert!(keymap(&[("quit", "hyper+q")]).is_err());
+    }
+}
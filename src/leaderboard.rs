@@ -0,0 +1,130 @@
+use crate::config;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const LEADERBOARD_FILE: &str = "leaderboard.json";
+/// How many soups the leaderboard keeps.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// A soup that made the leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The seed the soup is made from, as searched.
+    pub seed: u64,
+    /// The generation it settled in.
+    pub lifespan: usize,
+    /// How many cells were left once it had.
+    pub population: usize,
+}
+
+/// The longest-lived soups searched so far, longest first, kept in the
+/// config directory so every search adds to the same one.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    /// Where the leaderboard is kept: `leaderboard.json` in the config
+    /// directory.
+    pub fn path() -> std::io::Result<PathBuf> {
+        config::config_dir()
+            .map(|dir| dir.join(LEADERBOARD_FILE))
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory"))
+    }
+
+    /// Reads the leaderboard at `path`, or starts an empty one if there's none.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Leaderboard> {
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|error| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid leaderboard {}: {}", path.as_ref().display(), error),
+                )
+            }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Leaderboard::default())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let text = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, text + "\n")
+    }
+
+    /// Adds a soup if it lived longer than one on the board, or there's room,
+    /// returning its place from 1. Soups already on it aren't added again.
+    pub fn offer(&mut self, entry: Entry) -> Option<usize> {
+        if self.entries.iter().any(|kept| kept.seed == entry.seed) {
+            return None;
+        }
+        // longest first, then the biggest left, then the earliest found
+        let place = self.entries.partition_point(|kept| {
+            (kept.lifespan, kept.population) >= (entry.lifespan, entry.population)
+        });
+        if place >= LEADERBOARD_SIZE {
+            return None;
+        }
+        self.entries.insert(place, entry);
+        self.entries.truncate(LEADERBOARD_SIZE);
+        Some(place + 1)
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(seed: u64, lifespan: usize) -> Entry {
+        Entry {
+            seed,
+            lifespan,
+            population: 20,
+        }
+    }
+
+    #[test]
+    fn test_offer() {
+        let mut leaderboard = Leaderboard::default();
+        for seed in 0..LEADERBOARD_SIZE as u64 {
+            assert!(leaderboard
+                .offer(entry(seed, 100 + seed as usize))
+                .is_some());
+        }
+
+        // too short to make it, then long enough to top it
+        assert_eq!(leaderboard.offer(entry(50, 100)), None);
+        assert_eq!(leaderboard.offer(entry(51, 5000)), Some(1));
+        assert_eq!(leaderboard.offer(entry(51, 5000)), None);
+        // a tie goes after the soup found first
+        assert_eq!(leaderboard.offer(entry(52, 5000)), Some(2));
+
+        let seeds: Vec<u64> = leaderboard.entries().iter().map(|e| e.seed).collect();
+        assert_eq!(seeds, vec![51, 52, 9, 8, 7, 6, 5, 4, 3, 2]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir()
+            .join(format!("{}-leaderboard", std::process::id()))
+            .join(LEADERBOARD_FILE);
+        assert_eq!(Leaderboard::load(&path).unwrap(), Leaderboard::default());
+
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.offer(entry(7, 4000));
+        leaderboard.save(&path).unwrap();
+        let loaded = Leaderboard::load(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded, leaderboard);
+    }
+}
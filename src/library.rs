@@ -0,0 +1,311 @@
+use crate::{
+    config,
+    pattern::Pattern,
+    seed::{Seed, SEEDS},
+};
+use std::path::{Path, PathBuf};
+
+const SEEDS_DIR: &str = "seeds";
+const SEED_EXTENSION: &str = "rle";
+/// Where seeds saved without a category go.
+pub const DEFAULT_CATEGORY: &str = "Uncategorized";
+
+/// A seed drawn in the editor and saved, which the seed selector offers
+/// after the built-in ones.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSeed {
+    pub category: String,
+    /// The seed's cells, named after it.
+    pub pattern: Pattern,
+}
+
+impl UserSeed {
+    pub fn name(&self) -> &str {
+        self.pattern.name.as_deref().unwrap_or_default()
+    }
+}
+
+/// Where a seed in the [`catalog`] comes from.
+#[derive(Debug, Clone, Copy)]
+pub enum Source {
+    BuiltIn(Seed),
+    /// One of the user's seeds, by where it is among them.
+    User(usize),
+}
+
+/// The seeds of a category in the [`catalog`], named.
+#[derive(Debug, Clone)]
+pub struct Category {
+    pub name: String,
+    pub seeds: Vec<(String, Source)>,
+}
+
+/// Every seed there is to pick, by category: the built-in ones first, then
+/// the user's, which join a built-in category of the same name.
+pub fn catalog(user_seeds: &[UserSeed]) -> Vec<Category> {
+    let built_in = SEEDS
+        .iter()
+        .map(|seed| (seed.category(), seed.name(), Source::BuiltIn(*seed)));
+    let user = user_seeds
+        .iter()
+        .enumerate()
+        .map(|(index, seed)| (seed.category.as_str(), seed.name(), Source::User(index)));
+
+    let mut catalog: Vec<Category> = Vec::new();
+    for (category, name, source) in built_in.chain(user) {
+        let seed = (name.to_string(), source);
+        match catalog.iter_mut().find(|kept| kept.name == category) {
+            Some(kept) => kept.seeds.push(seed),
+            None => catalog.push(Category {
+                name: category.to_string(),
+                seeds: vec![seed],
+            }),
+        }
+    }
+    catalog
+}
+
+/// A seed picked from the [`catalog`]: which category, and where in it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Pick {
+    pub category: usize,
+    pub seed: usize,
+}
+
+impl Pick {
+    /// Finds a seed by its category and name.
+    pub fn find(catalog: &[Category], category: &str, name: &str) -> Option<Pick> {
+        let index = catalog.iter().position(|kept| kept.name == category)?;
+        let seed = catalog[index]
+            .seeds
+            .iter()
+            .position(|(kept, _)| kept == name)?;
+        Some(Pick {
+            category: index,
+            seed,
+        })
+    }
+
+    /// The seed picked, if it's still in the catalog.
+    pub fn get(self, catalog: &[Category]) -> Option<&(String, Source)> {
+        catalog.get(self.category)?.seeds.get(self.seed)
+    }
+
+    /// The first seed of the next category, or going `back`, the previous
+    /// one, from the last back around to the first.
+    pub fn next_category(self, catalog: &[Category], back: bool) -> Pick {
+        Pick {
+            category: step(self.category, catalog.len(), back),
+            seed: 0,
+        }
+    }
+
+    /// The next seed in the same category, or going `back`, the previous
+    /// one, from its last seed back around to its first.
+    pub fn next_seed(self, catalog: &[Category], back: bool) -> Pick {
+        let count = catalog
+            .get(self.category)
+            .map(|category| category.seeds.len())
+            .unwrap_or_default();
+        Pick {
+            seed: step(self.seed, count, back),
+            ..self
+        }
+    }
+}
+
+/// One forward or back from `index` among `count`, wrapping around.
+fn step(index: usize, count: usize, back: bool) -> usize {
+    match (back, index) {
+        _ if count == 0 => 0,
+        (true, 0) => count - 1,
+        (true, index) => index.min(count) - 1,
+        (false, index) => (index + 1) % count,
+    }
+}
+
+/// Where the user's seeds are kept: `seeds` in the config directory, with a
+/// directory for each category holding an RLE file for each seed.
+pub fn dir() -> std::io::Result<PathBuf> {
+    config::config_dir()
+        .map(|dir| dir.join(SEEDS_DIR))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory"))
+}
+
+/// Reads every seed kept in `dir`, by category and then by name, or none if
+/// there's nothing there yet. Files that aren't patterns are skipped.
+pub fn load<P: AsRef<Path>>(dir: P) -> std::io::Result<Vec<UserSeed>> {
+    let categories = match std::fs::read_dir(dir) {
+        Ok(categories) => categories,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut seeds = Vec::new();
+    for category in categories {
+        let category = category?;
+        if !category.file_type()?.is_dir() {
+            continue;
+        }
+        let name = category.file_name().to_string_lossy().into_owned();
+        for file in std::fs::read_dir(category.path())? {
+            let path = file?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SEED_EXTENSION) {
+                continue;
+            }
+            let Ok(mut pattern) = Pattern::load(&path) else {
+                continue;
+            };
+            if pattern.name.is_none() {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                pattern.name = Some(stem.into_owned());
+            }
+            seeds.push(UserSeed {
+                category: name.clone(),
+                pattern,
+            });
+        }
+    }
+    seeds.sort_by(|a, b| (&a.category, a.name()).cmp(&(&b.category, b.name())));
+    Ok(seeds)
+}
+
+/// Saves a seed in `dir` as `<category>/<name>.rle`, replacing one saved
+/// under the same name, and returns the file's path.
+pub fn save<P: AsRef<Path>>(dir: P, seed: &UserSeed) -> std::io::Result<PathBuf> {
+    let dir = dir.as_ref().join(file_name(&seed.category));
+    std::fs::create_dir_all(&dir)?;
+
+    let path = dir.join(format!("{}.{}", file_name(seed.name()), SEED_EXTENSION));
+    let mut text = Vec::new();
+    seed.pattern.write_rle(&mut text)?;
+    std::fs::write(&path, text)?;
+    Ok(path)
+}
+
+/// A name made safe to use as a file name, keeping letters, digits, spaces,
+/// dashes and underscores and replacing anything else with a dash.
+fn file_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .chars()
+        .map(|ch| match ch {
+            ' ' | '-' | '_' => ch,
+            ch if ch.is_alphanumeric() => ch,
+            _ => '-',
+        })
+        .collect();
+    match name.is_empty() {
+        true => String::from("seed"),
+        false => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::BoundingBox;
+
+    fn seed(category: &str, name: &str) -> UserSeed {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut pattern = Pattern::from_cells(&glider, BoundingBox::of(&glider).unwrap());
+        pattern.name = Some(name.to_string());
+        UserSeed {
+            category: category.to_string(),
+            pattern,
+        }
+    }
+
+    #[test]
+    fn test_catalog() {
+        let user_seeds = [seed("Guns", "Glider gun"), seed("Spaceships", "Copperhead")];
+        let catalog = catalog(&user_seeds);
+
+        let names: Vec<&str> = catalog.iter().map(|kept| kept.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "Cells",
+                "Still lifes",
+                "Oscillators",
+                "Spaceships",
+                "Methuselahs",
+                "Guns",
+                "Eaters",
+                "Circuits"
+            ]
+        );
+        // the user's seeds join the built-in ones
+        let gun = Pick::find(&catalog, "Guns", "Glider gun").unwrap();
+        assert!(matches!(gun.get(&catalog), Some((_, Source::User(0)))));
+        let copperhead = Pick::find(&catalog, "Spaceships", "Copperhead").unwrap();
+        assert_eq!(
+            copperhead,
+            Pick {
+                category: 3,
+                seed: 4
+            }
+        );
+        assert!(matches!(
+            copperhead.get(&catalog),
+            Some((_, Source::User(1)))
+        ));
+        assert_eq!(Pick::find(&catalog, "Guns", "Copperhead"), None);
+    }
+
+    #[test]
+    fn test_pick_wraps_around() {
+        let catalog = catalog(&[]);
+        let glider = Pick::find(&catalog, "Spaceships", "Glider").unwrap();
+        let hwss = Pick::find(&catalog, "Spaceships", "HWSS").unwrap();
+
+        assert_eq!(glider.next_seed(&catalog, true), hwss);
+        assert_eq!(hwss.next_seed(&catalog, false), glider);
+        assert_eq!(
+            hwss.next_category(&catalog, false),
+            Pick {
+                category: 4,
+                seed: 0
+            }
+        );
+        assert_eq!(
+            Pick::default().next_category(&catalog, true),
+            Pick {
+                category: catalog.len() - 1,
+                seed: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("My glider"), "My glider");
+        assert_eq!(file_name("../p46 gun"), "---p46 gun");
+        assert_eq!(file_name(" "), "seed");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("{}-seeds", std::process::id()));
+        assert!(load(&dir).unwrap().is_empty());
+
+        let saved = [
+            seed("Spaceships", "Glider"),
+            seed("Guns", "Glider gun"),
+            seed("Spaceships", "Another glider"),
+        ];
+        for seed in &saved {
+            save(&dir, seed).unwrap();
+        }
+        // saving under the same name replaces the seed
+        let path = save(&dir, &saved[0]).unwrap();
+        assert_eq!(path, dir.join("Spaceships").join("Glider.rle"));
+        let loaded = load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            loaded,
+            vec![saved[1].clone(), saved[2].clone(), saved[0].clone()]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{library, pattern::Pattern};
+use clap::Args as ClapArgs;
+use std::{
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// Where LifeWiki keeps its pattern files, as `<name>.rle`.
+const PATTERNS_URL: &str = "https://conwaylife.com/patterns/";
+/// The category fetched patterns are kept in, among the user's seeds.
+pub const CATEGORY: &str = "LifeWiki";
+
+/// Programs that download a URL to standard output, tried in turn.
+const DOWNLOAD_TOOLS: [(&str, &[&str]); 2] = [
+    (
+        "curl",
+        &["--fail", "--silent", "--show-error", "--location"],
+    ),
+    ("wget", &["--quiet", "--output-document=-"]),
+];
+
+/// Options for the `fetch` subcommand.
+#[derive(Debug, Clone, PartialEq, ClapArgs)]
+pub struct FetchArgs {
+    /// The pattern's name on LifeWiki, like "Gosper glider gun"
+    pub name: String,
+
+    /// Download it again even if it was fetched before
+    #[arg(long)]
+    pub refresh: bool,
+}
+
+/// The name LifeWiki files a pattern under, its letters and digits in
+/// lowercase, so "Gosper glider gun" is `gosperglidergun`.
+pub fn file_name(name: &str) -> String {
+    name.chars()
+        .filter(|ch| ch.is_ascii_alphanumeric())
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect()
+}
+
+/// Where a pattern fetched from LifeWiki is kept: with the user's seeds, so
+/// it can be picked like them, in the LifeWiki category.
+pub fn path(name: &str) -> std::io::Result<PathBuf> {
+    Ok(library::dir()?
+        .join(CATEGORY)
+        .join(format!("{}.rle", file_name(name))))
+}
+
+/// The pattern fetched before under `name`, if there is one.
+pub fn cached(name: &str) -> Option<PathBuf> {
+    path(name).ok().filter(|path| path.exists())
+}
+
+/// Downloads a pattern from LifeWiki and keeps it, unless it was fetched
+/// before and isn't to be refreshed, returning where it's kept and the
+/// pattern, and whether it had to be downloaded.
+pub fn fetch(name: &str, refresh: bool) -> std::io::Result<(PathBuf, Pattern, bool)> {
+    let invalid = |error: String| std::io::Error::new(ErrorKind::InvalidData, error);
+    let path = path(name)?;
+    if path.exists() && !refresh {
+        let pattern = Pattern::load(&path).map_err(invalid)?;
+        return Ok((path, pattern, false));
+    }
+
+    let url = format!("{}{}.rle", PATTERNS_URL, file_name(name));
+    let text = String::from_utf8_lossy(&download(&url)?).into_owned();
+    let pattern = Pattern::parse_rle(&text)
+        .map_err(|error| invalid(format!("{} isn't a pattern: {}", url, error)))?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, text)?;
+    Ok((path, pattern, true))
+}
+
+/// The body at `url`, downloaded with the first download program there is.
+fn download(url: &str) -> std::io::Result<Vec<u8>> {
+    for (tool, args) in DOWNLOAD_TOOLS {
+        let output = Command::new(tool)
+            .args(args)
+            .arg(url)
+            .stdin(Stdio::null())
+            .output();
+        match output {
+            Ok(output) if output.status.success() => return Ok(output.stdout),
+            Ok(output) => {
+                let error = String::from_utf8_lossy(&output.stderr);
+                return Err(std::io::Error::other(format!(
+                    "could not download {}: {}",
+                    url,
+                    error.trim()
+                )));
+            }
+            Err(error) if error.kind() == ErrorKind::NotFound => continue,
+            Err(error) => return Err(error),
+        }
+    }
+    Err(std::io::Error::new(
+        ErrorKind::NotFound,
+        "no curl or wget found to download with",
+    ))
+}
+
+/// Fetches the pattern named and says where it's kept.
+pub fn run<W: Write>(args: &FetchArgs, mut output: W) -> std::io::Result<()> {
+    let (path, pattern, downloaded) = fetch(&args.name, args.refresh)?;
+    writeln!(
+        output,
+        "{} {} ({}x{}, {} cells) {} {}",
+        if downloaded { "Fetched" } else { "Already had" },
+        pattern.name.as_deref().unwrap_or(&args.name),
+        pattern.width,
+        pattern.height,
+        pattern.cells.len(),
+        if downloaded { "to" } else { "in" },
+        path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name("Gosper glider gun"), "gosperglidergun");
+        assert_eq!(file_name("Penta-decathlon"), "pentadecathlon");
+        assert_eq!(file_name("../R-pentomino"), "rpentomino");
+    }
+}
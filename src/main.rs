@@ -1,7 +1,78 @@
+pub mod args;
+pub mod bench;
+pub mod browser;
+pub mod capability;
+pub mod census;
 pub mod cli;
+pub mod clipboard;
+pub mod compress;
+pub mod config;
+pub mod cursor;
+pub mod cycle;
+pub mod dense;
+pub mod elementary;
+pub mod escape;
+pub mod explorer;
+pub mod export;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+pub mod golly;
 pub mod grid;
+pub mod headless;
+pub mod history;
+pub mod keymap;
+pub mod leaderboard;
+pub mod library;
+pub mod lifewiki;
+pub mod palette;
+pub mod pattern;
+pub mod patterns;
+pub mod recording;
+pub mod render;
+pub mod rule;
+pub mod savestate;
+pub mod scene;
+pub mod search;
 pub mod seed;
+pub mod soup;
+pub mod stats;
+pub mod theme;
+pub mod watch;
+pub mod wireworld;
 
-fn main() -> std::io::Result<()> {
-    cli::run()
+use args::{Args, Command};
+use clap::Parser;
+use std::io::IsTerminal;
+
+fn main() {
+    let mut args = Args::parse();
+    let layers = match config::layers(&args) {
+        Ok(layers) => layers,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    config::merge(&layers).apply(&mut args);
+
+    let result = match &args.command {
+        _ if args.print_config => config::print(&layers, std::io::stdout().lock()),
+        Some(Command::Bench(bench)) => bench::run(bench, std::io::stdout().lock()),
+        Some(Command::Fetch(fetch)) => lifewiki::run(fetch, std::io::stdout().lock()),
+        Some(Command::Keys) => keymap::run(std::io::stdout().lock()),
+        Some(Command::Search(search)) if std::io::stdout().is_terminal() => {
+            search::dashboard(search)
+        }
+        Some(Command::Search(search)) => search::run(search, std::io::stdout().lock()),
+        Some(Command::Submit(submit)) => search::submit(submit, std::io::stdout().lock()),
+        None if args.pipe => {
+            headless::run(&args, std::io::stdin().lock(), std::io::stdout().lock())
+        }
+        Some(Command::Replay(_)) | None => cli::run(args),
+    };
+
+    if let Err(error) = result {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,240 @@
+use crate::{args, render::Renderer, rule::Rule};
+use clap::ValueEnum;
+
+/// A command typed on the command line opened with `:`, most doing what a
+/// key does after asking for something.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Rule,
+    Step,
+    Speed,
+    Goto,
+    Clear,
+    Save,
+    Load,
+    SaveState,
+    LoadState,
+    Bookmark,
+    Break,
+    Stats,
+    Scatter,
+    Theme,
+    Draw,
+    Keys,
+}
+
+impl Command {
+    pub const ALL: [Command; 16] = [
+        Command::Rule,
+        Command::Step,
+        Command::Speed,
+        Command::Goto,
+        Command::Clear,
+        Command::Save,
+        Command::Load,
+        Command::SaveState,
+        Command::LoadState,
+        Command::Bookmark,
+        Command::Break,
+        Command::Stats,
+        Command::Scatter,
+        Command::Theme,
+        Command::Draw,
+        Command::Keys,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Rule => "rule",
+            Command::Step => "step",
+            Command::Speed => "speed",
+            Command::Goto => "goto",
+            Command::Clear => "clear",
+            Command::Save => "save",
+            Command::Load => "load",
+            Command::SaveState => "savestate",
+            Command::LoadState => "loadstate",
+            Command::Bookmark => "bookmark",
+            Command::Break => "break",
+            Command::Stats => "stats",
+            Command::Scatter => "scatter",
+            Command::Theme => "theme",
+            Command::Draw => "draw",
+            Command::Keys => "keys",
+        }
+    }
+
+    /// How it's typed, with what it takes in capitals, optional in brackets.
+    pub fn usage(&self) -> &'static str {
+        match self {
+            Command::Rule => "rule RULE",
+            Command::Step => "step N",
+            Command::Speed => "speed N",
+            Command::Goto => "goto X,Y",
+            Command::Clear => "clear",
+            Command::Save => "save FILE",
+            Command::Load => "load FILE",
+            Command::SaveState => "savestate FILE",
+            Command::LoadState => "loadstate FILE",
+            Command::Bookmark => "bookmark [NAME]",
+            Command::Break => "break [CONDITION]",
+            Command::Stats => "stats FILE",
+            Command::Scatter => "scatter N[r]",
+            Command::Theme => "theme [NAME]",
+            Command::Draw => "draw [RENDERER]",
+            Command::Keys => "keys",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Command::Rule => "Switch rules, like B36/S23 or highlife",
+            Command::Step => "Advance N generations",
+            Command::Speed => "Run at N generations a second, 0 for unlimited",
+            Command::Goto => "Move the cursor to a cell",
+            Command::Clear => "Clear the board",
+            Command::Save => "Export the board as .rle, .cells, .lif, .png or .txt",
+            Command::Load => "Load a pattern, apgcode or scene to place",
+            Command::SaveState => "Save the whole game, .gz/.zst to compress",
+            Command::LoadState => "Load a saved game",
+            Command::Bookmark => "Bookmark this generation",
+            Command::Break => "Pause at gen N, pop >N <N =N or cell x,y, or clear",
+            Command::Stats => "Log each generation's stats as CSV",
+            Command::Scatter => "Scatter N copies of the seed, r to turn each",
+            Command::Theme => "Switch color themes, to the next one without a name",
+            Command::Draw => "Switch how cells are drawn, to the next way without a name",
+            Command::Keys => "List every key and what it does",
+        }
+    }
+
+    /// Whether what's typed after it is what it takes.
+    fn accepts(&self, argument: &str) -> bool {
+        match self {
+            Command::Rule => argument.parse::<Rule>().is_ok(),
+            Command::Step | Command::Speed => argument.parse::<u32>().is_ok(),
+            Command::Goto => args::parse_coordinates(argument).is_some(),
+            Command::Clear | Command::Keys => argument.is_empty(),
+            Command::Save
+            | Command::Load
+            | Command::SaveState
+            | Command::LoadState
+            | Command::Stats => !argument.is_empty(),
+            Command::Bookmark | Command::Break | Command::Theme => true,
+            Command::Draw => argument.is_empty() || Renderer::from_str(argument, true).is_ok(),
+            Command::Scatter => argument
+                .strip_suffix(['r', 'R'])
+                .unwrap_or(argument)
+                .parse::<usize>()
+                .is_ok(),
+        }
+    }
+}
+
+/// The commands whose names start with the first word typed, all of them
+/// before anything is.
+pub fn matching(line: &str) -> Vec<Command> {
+    let typed = line.split_whitespace().next().unwrap_or_default();
+    Command::ALL
+        .into_iter()
+        .filter(|command| command.name().starts_with(typed))
+        .collect()
+}
+
+/// Reads a command line into the command, known by its name or the start
+/// of it if that's enough to tell, and what's typed after it.
+pub fn parse(line: &str) -> Result<(Command, &str), String> {
+    let line = line.trim();
+    let (typed, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument = argument.trim();
+    let found = matching(typed);
+    let command = match found[..] {
+        _ if typed.is_empty() => return Err(String::from("Type a command")),
+        [command] => command,
+        _ => match found.iter().find(|command| command.name() == typed) {
+            Some(command) => *command,
+            None if found.is_empty() => return Err(format!("No command {:?}", typed)),
+            None => {
+                let names: Vec<&str> = found.iter().map(Command::name).collect();
+                return Err(format!("{:?} could be {}", typed, names.join(", ")));
+            }
+        },
+    };
+
+    match command.accepts(argument) {
+        true => Ok((command, argument)),
+        false => Err(format!("Usage: {}", command.usage())),
+    }
+}
+
+/// The command line with the command's name finished, as far as the names
+/// starting with what's typed agree, or `None` if there's nothing to add.
+pub fn complete(line: &str) -> Option<String> {
+    if line.contains(' ') {
+        return None;
+    }
+    let found = matching(line);
+    let first = found.first()?.name();
+    let common = found.iter().fold(first, |common, command| {
+        let shared = common
+            .chars()
+            .zip(command.name().chars())
+            .take_while(|(a, b)| a == b)
+            .count();
+        &common[..shared]
+    });
+    match (found.len(), common.len() > line.len()) {
+        (1, _) => Some(format!("{} ", first)),
+        (_, true) => Some(common.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("rule B36/S23"), Ok((Command::Rule, "B36/S23")));
+        assert_eq!(parse(" step   500 "), Ok((Command::Step, "500")));
+        // started names that can only be one command
+        assert_eq!(parse("go 40,12"), Ok((Command::Goto, "40,12")));
+        assert_eq!(parse("save foo.rle"), Ok((Command::Save, "foo.rle")));
+        assert_eq!(parse("cl"), Ok((Command::Clear, "")));
+        assert_eq!(parse("bookmark"), Ok((Command::Bookmark, "")));
+        assert_eq!(parse("scatter 30r"), Ok((Command::Scatter, "30r")));
+        assert_eq!(parse("draw Braille"), Ok((Command::Draw, "Braille")));
+
+        assert_eq!(
+            parse("st 5"),
+            Err(String::from("\"st\" could be step, stats"))
+        );
+        assert_eq!(parse("fly"), Err(String::from("No command \"fly\"")));
+        assert_eq!(parse("step"), Err(String::from("Usage: step N")));
+        assert_eq!(parse("rule B9"), Err(String::from("Usage: rule RULE")));
+        assert_eq!(
+            parse("draw sixel"),
+            Err(String::from("Usage: draw [RENDERER]"))
+        );
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_complete() {
+        assert_eq!(complete("ru").as_deref(), Some("rule "));
+        // as far as they agree
+        assert_eq!(complete("s").as_deref(), None);
+        assert_eq!(complete("sa").as_deref(), Some("save"));
+        assert_eq!(complete("save").as_deref(), None);
+        assert_eq!(complete("loads").as_deref(), Some("loadstate "));
+        assert_eq!(complete("step 5"), None);
+        assert_eq!(complete("xyz"), None);
+    }
+
+    #[test]
+    fn test_matching() {
+        assert_eq!(matching("").len(), Command::ALL.len());
+        assert_eq!(matching("st 10"), vec![Command::Step, Command::Stats]);
+        assert!(matching("x").is_empty());
+    }
+}
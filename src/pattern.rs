@@ -0,0 +1,1039 @@
+use crate::{
+    compress,
+    grid::{BoundingBox, Cell},
+    lifewiki,
+    rule::Rule,
+    seed::{IsSeed, Orientation},
+};
+use std::{
+    collections::HashSet,
+    io::{Read, Write},
+    path::Path,
+};
+
+/// Golly and LifeWiki keep RLE lines at most this long.
+const RLE_LINE_LENGTH: usize = 70;
+/// Macrocell patterns with more live cells than this are refused, since every
+/// one of them ends up in the grid.
+const MACROCELL_MAX_POPULATION: usize = 20_000_000;
+/// Macrocell leaves are 8 by 8 squares, so they sit at level 3.
+const MACROCELL_LEAF_LEVEL: u32 = 3;
+
+/// A pattern read from a file, placed with its top-left corner at the origin.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pattern {
+    pub name: Option<String>,
+    /// Who made or found it, from RLE's `#O` line.
+    pub author: Option<String>,
+    /// Whatever else the file says about it, a line each.
+    pub comments: Vec<String>,
+    /// The rule the pattern was made for, as written in the file.
+    pub rule: Option<String>,
+    pub width: usize,
+    pub height: usize,
+    /// Live cells, as offsets from the top-left corner.
+    pub cells: Vec<Cell>,
+}
+
+impl IsSeed for Pattern {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        self.cells
+            .iter()
+            .map(|cell| (origin.0 + cell.0, origin.1 + cell.1))
+            .collect()
+    }
+}
+
+impl Pattern {
+    /// Takes the live cells inside `bounds`, relative to its top-left corner.
+    pub fn from_cells<'c>(
+        cells: impl IntoIterator<Item = &'c Cell>,
+        bounds: BoundingBox,
+    ) -> Pattern {
+        let mut cells: Vec<Cell> = cells
+            .into_iter()
+            .filter(|cell| bounds.contains(cell))
+            .map(|&(x, y)| (x - bounds.x, y - bounds.y))
+            .collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+
+        Pattern {
+            name: None,
+            author: None,
+            comments: Vec::new(),
+            rule: None,
+            width: bounds.width,
+            height: bounds.height,
+            cells,
+        }
+    }
+
+    /// The pattern turned and flipped, with the top-left corner of its box
+    /// back at the origin.
+    pub fn oriented(&self, orientation: Orientation) -> Pattern {
+        if self.width == 0 || self.height == 0 {
+            return self.clone();
+        }
+        let corner = orientation.apply((self.width as i64 - 1, self.height as i64 - 1));
+        let (left, top) = (corner.0.min(0), corner.1.min(0));
+        let mut cells: Vec<Cell> = self
+            .cells
+            .iter()
+            .map(|cell| {
+                let (x, y) = orientation.apply(*cell);
+                (x - left, y - top)
+            })
+            .collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+
+        Pattern {
+            width: corner.0.unsigned_abs() as usize + 1,
+            height: corner.1.unsigned_abs() as usize + 1,
+            cells,
+            ..self.clone()
+        }
+    }
+
+    /// The rule from the header, if there is one this game can run.
+    pub fn parse_rule(&self) -> Option<Rule> {
+        self.rule.as_deref()?.parse().ok()
+    }
+
+    /// Reads a pattern file, decompressing `.gz` and `.zst` files.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Pattern, String> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        compress::open(path)
+            .and_then(|mut reader| reader.read_to_string(&mut text))
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        Pattern::parse_as(path, &text).map_err(|error| format!("{}: {}", path.display(), error))
+    }
+
+    /// Parses a pattern in the format its file's extension says, RLE unless
+    /// it's another one read.
+    pub fn parse_as(path: &Path, text: &str) -> Result<Pattern, String> {
+        match compress::format_extension(path) {
+            Some("cells") => Pattern::parse_cells(text),
+            Some("lif") | Some("life") => Pattern::parse_life(text),
+            Some("mc") => Pattern::parse_macrocell(text),
+            _ => Pattern::parse_rle(text),
+        }
+    }
+
+    /// Reads a pattern file, or if there's no such file, decodes `name` as an
+    /// apgcode or reads the pattern fetched from LifeWiki under that name.
+    pub fn load_or_decode(name: &str) -> Result<Pattern, String> {
+        match Path::new(name).exists() {
+            false if name.starts_with('x') && name.contains('_') => Pattern::parse_apgcode(name),
+            false => match lifewiki::cached(name) {
+                Some(path) => Pattern::load(path),
+                None => Pattern::load(name),
+            },
+            true => Pattern::load(name),
+        }
+    }
+
+    /// Parses a pattern in any of the formats read, telling them apart by
+    /// their first lines, as when pasted from elsewhere.
+    pub fn parse(text: &str) -> Result<Pattern, String> {
+        let text = text.trim_start();
+        let first = text.lines().next().unwrap_or_default().trim();
+        if first.starts_with("[M2]") {
+            Pattern::parse_macrocell(text)
+        } else if first.starts_with("#Life") {
+            Pattern::parse_life(text)
+        } else if first.starts_with('!') || first.starts_with(['.', 'O', '*']) {
+            Pattern::parse_cells(text)
+        } else if !text.contains(char::is_whitespace) && text.starts_with('x') && text.contains('_')
+        {
+            Pattern::parse_apgcode(text)
+        } else {
+            Pattern::parse_rle(text)
+        }
+    }
+
+    /// Parses the plaintext format, where `O` is alive and `.` is dead:
+    ///
+    /// ```txt
+    /// !Name: Glider
+    /// .O
+    /// ..O
+    /// OOO
+    /// ```
+    pub fn parse_cells(text: &str) -> Result<Pattern, String> {
+        let mut pattern = Pattern::default();
+
+        for line in text.lines().map(str::trim_end) {
+            if let Some(comment) = line.strip_prefix('!') {
+                match comment.strip_prefix("Name:") {
+                    Some(name) => pattern.name = Some(name.trim().to_string()),
+                    None if !comment.trim().is_empty() => {
+                        pattern.comments.push(comment.trim().to_string())
+                    }
+                    None => {}
+                }
+                continue;
+            }
+
+            let y = pattern.height;
+            for (x, ch) in line.chars().enumerate() {
+                match ch {
+                    'O' | '*' => pattern.cells.push((x as i64, y as i64)),
+                    '.' => {}
+                    _ => return Err(format!("unexpected {:?} in plaintext pattern", ch)),
+                }
+            }
+            pattern.width = pattern.width.max(line.chars().count());
+            pattern.height += 1;
+        }
+
+        Ok(pattern)
+    }
+
+    /// Writes the pattern in the plaintext format, the inverse of [`Pattern::parse_cells`].
+    pub fn write_cells<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        if let Some(name) = &self.name {
+            writeln!(writer, "!Name: {}", name)?;
+        }
+        for comment in &self.comments {
+            writeln!(writer, "!{}", comment)?;
+        }
+
+        let alive: HashSet<Cell> = self.cells.iter().copied().collect();
+        for y in 0..self.height {
+            // like RLE, dead cells at the end of a row are left out
+            let width = (0..self.width)
+                .rev()
+                .find(|&x| alive.contains(&(x as i64, y as i64)))
+                .map_or(0, |x| x + 1);
+            let row: String = (0..width)
+                .map(|x| {
+                    if alive.contains(&(x as i64, y as i64)) {
+                        'O'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(writer, "{}", row)?;
+        }
+        Ok(())
+    }
+
+    /// Parses Run Length Encoded text, as used by LifeWiki and Golly:
+    ///
+    /// ```txt
+    /// #N Glider
+    /// x = 3, y = 3, rule = B3/S23
+    /// bob$2bo$3o!
+    /// ```
+    pub fn parse_rle(text: &str) -> Result<Pattern, String> {
+        let mut pattern = Pattern::default();
+        let mut lines = text.lines().map(str::trim);
+
+        for line in lines.by_ref() {
+            if let Some(name) = line.strip_prefix("#N") {
+                pattern.name = Some(name.trim().to_string());
+            } else if let Some(author) = line.strip_prefix("#O") {
+                pattern.author = Some(author.trim().to_string());
+            } else if let Some(comment) =
+                line.strip_prefix("#C").or_else(|| line.strip_prefix("#c"))
+            {
+                pattern.comments.push(comment.trim().to_string());
+            } else if line.starts_with('#') || line.is_empty() {
+                continue;
+            } else {
+                pattern.parse_rle_header(line)?;
+                break;
+            }
+        }
+
+        let (mut x, mut y) = (0, 0);
+        let mut run: usize = 0;
+        // no run can go past the size in the header
+        let longest = pattern.width.max(pattern.height);
+
+        'body: for line in lines {
+            for ch in line.chars() {
+                match ch {
+                    '0'..='9' => {
+                        run = run
+                            .checked_mul(10)
+                            .and_then(|run| {
+                                run.checked_add(ch.to_digit(10).unwrap_or_default() as usize)
+                            })
+                            .filter(|&run| run <= longest)
+                            .ok_or_else(|| {
+                                format!(
+                                    "run longer than the pattern's {}x{}",
+                                    pattern.width, pattern.height
+                                )
+                            })?
+                    }
+                    'b' | '.' => {
+                        x += run.max(1);
+                        run = 0;
+                    }
+                    '$' => {
+                        y += run.max(1);
+                        x = 0;
+                        run = 0;
+                    }
+                    '!' => break 'body,
+                    ch if ch.is_whitespace() => {}
+                    // any other state counts as alive
+                    ch if ch.is_ascii_alphabetic() => {
+                        for _ in 0..run.max(1) {
+                            pattern.cells.push((x as i64, y as i64));
+                            x += 1;
+                        }
+                        run = 0;
+                    }
+                    _ => return Err(format!("unexpected {:?} in RLE data", ch)),
+                }
+            }
+        }
+
+        Ok(pattern)
+    }
+
+    /// Writes the pattern as Run Length Encoded text, the inverse of [`Pattern::parse_rle`].
+    pub fn write_rle<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        if let Some(name) = &self.name {
+            writeln!(writer, "#N {}", name)?;
+        }
+        if let Some(author) = &self.author {
+            writeln!(writer, "#O {}", author)?;
+        }
+        for comment in &self.comments {
+            writeln!(writer, "#C {}", comment)?;
+        }
+        write!(writer, "x = {}, y = {}", self.width, self.height)?;
+        if let Some(rule) = &self.rule {
+            write!(writer, ", rule = {}", rule)?;
+        }
+        writeln!(writer)?;
+
+        let alive: HashSet<Cell> = self.cells.iter().copied().collect();
+        let mut tokens = Vec::new();
+        let mut line_ends = 0;
+
+        for y in 0..self.height {
+            if y > 0 {
+                line_ends += 1;
+            }
+
+            let mut x = 0;
+            while x < self.width {
+                let state = alive.contains(&(x as i64, y as i64));
+                let mut run = 1;
+                while x + run < self.width && alive.contains(&((x + run) as i64, y as i64)) == state
+                {
+                    run += 1;
+                }
+                x += run;
+
+                // dead cells at the end of a row are implied
+                if !state && x == self.width {
+                    break;
+                }
+                if line_ends > 0 {
+                    tokens.push(rle_token(line_ends, '$'));
+                    line_ends = 0;
+                }
+                tokens.push(rle_token(run, if state { 'o' } else { 'b' }));
+            }
+        }
+        tokens.push("!".to_string());
+
+        let mut line = String::new();
+        for token in tokens {
+            if line.len() + token.len() > RLE_LINE_LENGTH {
+                writeln!(writer, "{}", line)?;
+                line.clear();
+            }
+            line.push_str(&token);
+        }
+        writeln!(writer, "{}", line)
+    }
+
+    /// Parses Life 1.05 or Life 1.06 text, telling them apart by the header.
+    pub fn parse_life(text: &str) -> Result<Pattern, String> {
+        match text.lines().next().map(str::trim) {
+            Some("#Life 1.05") => Pattern::parse_life_105(text),
+            Some("#Life 1.06") => Pattern::parse_life_106(text),
+            _ => Err(String::from("expected a #Life 1.05 or #Life 1.06 header")),
+        }
+    }
+
+    /// Parses Life 1.05, blocks of `.` and `*` rows placed by `#P x y` lines:
+    ///
+    /// ```txt
+    /// #Life 1.05
+    /// #D Glider
+    /// #N
+    /// #P -1 -1
+    /// .*
+    /// ..*
+    /// ***
+    /// ```
+    pub fn parse_life_105(text: &str) -> Result<Pattern, String> {
+        let mut pattern = Pattern::default();
+        let mut cells = Vec::new();
+        let (mut left, mut y) = (0, 0);
+
+        for line in text.lines().map(str::trim) {
+            if let Some(position) = line.strip_prefix("#P") {
+                (left, y) = parse_signed_pair(position)
+                    .ok_or_else(|| format!("invalid block position {:?}", line))?;
+            } else if let Some(description) = line.strip_prefix("#D") {
+                if pattern.name.is_none() {
+                    pattern.name = Some(description.trim().to_string());
+                }
+            } else if let Some(rule) = line.strip_prefix("#R") {
+                pattern.rule = Some(rule_from_life_105(rule.trim()));
+            } else if line.starts_with('#') || line.is_empty() {
+                continue;
+            } else {
+                for (x, ch) in line.chars().enumerate() {
+                    match ch {
+                        '*' => cells.push((left + x as i64, y)),
+                        '.' => {}
+                        _ => return Err(format!("unexpected {:?} in Life 1.05 data", ch)),
+                    }
+                }
+                y += 1;
+            }
+        }
+
+        pattern.place_signed(cells);
+        Ok(pattern)
+    }
+
+    /// Parses Life 1.06, one `x y` pair per live cell.
+    pub fn parse_life_106(text: &str) -> Result<Pattern, String> {
+        let mut cells = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+            cells.push(parse_signed_pair(line).ok_or_else(|| format!("invalid cell {:?}", line))?);
+        }
+
+        let mut pattern = Pattern::default();
+        pattern.place_signed(cells);
+        Ok(pattern)
+    }
+
+    /// Writes the pattern as Life 1.05, in a single block at the origin.
+    pub fn write_life_105<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "#Life 1.05")?;
+        if let Some(name) = &self.name {
+            writeln!(writer, "#D {}", name)?;
+        }
+        match self.parse_rule() {
+            Some(rule) if rule != Rule::default() => {
+                writeln!(writer, "#R {}", rule_to_life_105(&rule))?
+            }
+            _ => writeln!(writer, "#N")?,
+        }
+        writeln!(writer, "#P 0 0")?;
+
+        let alive: HashSet<Cell> = self.cells.iter().copied().collect();
+        for y in 0..self.height {
+            let width = (0..self.width)
+                .rev()
+                .find(|&x| alive.contains(&(x as i64, y as i64)))
+                .map_or(1, |x| x + 1);
+            let row: String = (0..width)
+                .map(|x| {
+                    if alive.contains(&(x as i64, y as i64)) {
+                        '*'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(writer, "{}", row)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the pattern as Life 1.06, one `x y` pair per live cell.
+    pub fn write_life_106<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "#Life 1.06")?;
+        for (x, y) in &self.cells {
+            writeln!(writer, "{} {}", x, y)?;
+        }
+        Ok(())
+    }
+
+    /// Parses Golly's Macrocell format, a quadtree where identical squares
+    /// are written once. Leaves are 8 by 8 squares of `.` and `*` rows split
+    /// by `$`, and every other line is `level nw ne sw se`, children being
+    /// 1-based line numbers of earlier nodes, or 0 for an empty square:
+    ///
+    /// ```txt
+    /// [M2] (golly 4.0)
+    /// #R B3/S23
+    /// .*$..*$***$
+    /// 4 1 0 0 1
+    /// ```
+    pub fn parse_macrocell(text: &str) -> Result<Pattern, String> {
+        Pattern::parse_macrocell_within(text, MACROCELL_MAX_POPULATION)
+    }
+
+    /// Parses Macrocell as [`Pattern::parse_macrocell`] does, failing before
+    /// any square is expanded on more than `max_population` live cells.
+    pub fn parse_macrocell_within(text: &str, max_population: usize) -> Result<Pattern, String> {
+        let mut lines = text.lines().map(str::trim);
+        if !lines.next().is_some_and(|line| line.starts_with("[M2]")) {
+            return Err(String::from("expected a [M2] header"));
+        }
+
+        let mut pattern = Pattern::default();
+        // node 0 is the empty square
+        let mut nodes = vec![MacrocellNode::Leaf(Vec::new())];
+        let mut populations = vec![0usize];
+
+        for line in lines {
+            if let Some(rule) = line.strip_prefix("#R") {
+                pattern.rule = Some(rule.trim().to_string());
+                continue;
+            } else if let Some(name) = line.strip_prefix("#N") {
+                pattern.name = Some(name.trim().to_string());
+                continue;
+            } else if line.starts_with('#') || line.is_empty() {
+                continue;
+            }
+
+            let node = match line.starts_with(['.', '*', '$']) {
+                true => MacrocellNode::parse_leaf(line)?,
+                false => MacrocellNode::parse_inner(line, &nodes)?,
+            };
+            populations.push(match &node {
+                MacrocellNode::Leaf(cells) => cells.len(),
+                MacrocellNode::Inner { children, .. } => {
+                    children.iter().fold(0, |total: usize, &child| {
+                        total.saturating_add(populations[child])
+                    })
+                }
+            });
+            nodes.push(node);
+        }
+
+        let root = nodes.len() - 1;
+        if populations[root] > max_population {
+            return Err(format!(
+                "{} live cells are too many, the limit is {}",
+                populations[root], max_population
+            ));
+        }
+
+        let mut cells = Vec::with_capacity(populations[root]);
+        let mut pending = vec![(root, 0, 0)];
+        while let Some((index, x, y)) = pending.pop() {
+            match &nodes[index] {
+                MacrocellNode::Leaf(leaf) => {
+                    cells.extend(leaf.iter().map(|cell| (x + cell.0, y + cell.1)))
+                }
+                MacrocellNode::Inner { level, children } => {
+                    let half = 1 << (level - 1);
+                    let corners = [(x, y), (x + half, y), (x, y + half), (x + half, y + half)];
+                    for (&child, (x, y)) in children.iter().zip(corners) {
+                        if populations[child] > 0 {
+                            pending.push((child, x, y));
+                        }
+                    }
+                }
+            }
+        }
+
+        pattern.place_signed(cells);
+        Ok(pattern)
+    }
+
+    /// Decodes an apgcode, the names Catagolue gives objects, such as
+    /// `xq4_153` for the glider. After the prefix (`xs` still life, `xp`
+    /// oscillator or `xq` spaceship) comes the extended Wechsler format:
+    /// strips 5 rows tall split by `z`, with each `0-9a-v` digit being a
+    /// column whose bits from lowest are the rows from the top. `w` and `x`
+    /// are 2 and 3 empty columns, and `y` followed by a digit is 4 or more.
+    pub fn parse_apgcode(code: &str) -> Result<Pattern, String> {
+        let invalid = || format!("invalid apgcode {:?}", code);
+        let (prefix, wechsler) = code.split_once('_').ok_or_else(invalid)?;
+        let kind = prefix.get(..2).ok_or_else(invalid)?;
+        if !matches!(kind, "xs" | "xp" | "xq") || prefix[2..].parse::<u32>().is_err() {
+            return Err(invalid());
+        }
+
+        let mut cells = Vec::new();
+        let (mut x, mut strip) = (0, 0);
+        let mut digits = wechsler.chars();
+
+        while let Some(ch) = digits.next() {
+            match ch {
+                'w' => x += 2,
+                'x' => x += 3,
+                'y' => {
+                    let gap = digits.next().and_then(|ch| ch.to_digit(36));
+                    x += 4 + gap.ok_or_else(invalid)? as usize;
+                }
+                'z' => {
+                    x = 0;
+                    strip += 1;
+                }
+                _ => {
+                    let column = ch.to_digit(32).ok_or_else(invalid)?;
+                    for row in (0..5).filter(|row| column & (1 << row) != 0) {
+                        cells.push((x as i64, (strip * 5 + row) as i64));
+                    }
+                    x += 1;
+                }
+            }
+        }
+
+        let mut pattern = Pattern {
+            name: Some(code.to_string()),
+            ..Default::default()
+        };
+        pattern.place_signed(cells);
+        Ok(pattern)
+    }
+
+    /// Moves cells so the top-left one is at the origin.
+    fn place_signed(&mut self, cells: Vec<Cell>) {
+        let left = cells.iter().map(|cell| cell.0).min().unwrap_or_default();
+        let top = cells.iter().map(|cell| cell.1).min().unwrap_or_default();
+
+        self.cells = cells.iter().map(|&(x, y)| (x - left, y - top)).collect();
+        self.cells.sort_by_key(|&(x, y)| (y, x));
+        self.cells.dedup();
+
+        if let Some(bounds) = BoundingBox::of(&self.cells) {
+            self.width = bounds.width;
+            self.height = bounds.height;
+        }
+    }
+
+    /// Reads `x = 3, y = 3, rule = B3/S23`.
+    fn parse_rle_header(&mut self, line: &str) -> Result<(), String> {
+        // the rule comes last and can have commas of its own, as in Larger than Life
+        let (fields, rule) = match line.find("rule") {
+            Some(start) => (
+                line[..start].trim_end().trim_end_matches(','),
+                &line[start..],
+            ),
+            None => (line, ""),
+        };
+        for field in fields
+            .split(',')
+            .chain(Some(rule).filter(|rule| !rule.is_empty()))
+        {
+            let (key, value) = field
+                .split_once('=')
+                .ok_or_else(|| format!("invalid RLE header {:?}", line))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "x" => {
+                    self.width = value
+                        .parse()
+                        .map_err(|_| format!("invalid width {:?}", value))?
+                }
+                "y" => {
+                    self.height = value
+                        .parse()
+                        .map_err(|_| format!("invalid height {:?}", value))?
+                }
+                "rule" => self.rule = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A square of a Macrocell quadtree.
+#[derive(Debug)]
+enum MacrocellNode {
+    /// An 8 by 8 square, as its live cells.
+    Leaf(Vec<Cell>),
+    /// A square `2^level` cells wide, split into four: nw, ne, sw and se.
+    Inner { level: u32, children: [usize; 4] },
+}
+
+impl MacrocellNode {
+    /// Reads `.*$..*$***$`.
+    fn parse_leaf(line: &str) -> Result<MacrocellNode, String> {
+        let size = 1 << MACROCELL_LEAF_LEVEL;
+        let mut cells = Vec::new();
+        let (mut x, mut y) = (0, 0);
+
+        for ch in line.chars() {
+            match ch {
+                '.' => x += 1,
+                '*' => {
+                    cells.push((x, y));
+                    x += 1;
+                }
+                '$' => {
+                    x = 0;
+                    y += 1;
+                }
+                _ => return Err(format!("unexpected {:?} in Macrocell leaf", ch)),
+            }
+            if x > size || y > size {
+                return Err(format!("Macrocell leaf larger than 8x8: {:?}", line));
+            }
+        }
+
+        Ok(MacrocellNode::Leaf(cells))
+    }
+
+    /// Reads `level nw ne sw se`, checking the children against `nodes`.
+    fn parse_inner(line: &str, nodes: &[MacrocellNode]) -> Result<MacrocellNode, String> {
+        let invalid = || format!("invalid Macrocell node {:?}", line);
+        let numbers: Vec<usize> = line
+            .split_whitespace()
+            .map(|number| number.parse().map_err(|_| invalid()))
+            .collect::<Result<_, _>>()?;
+        let [level, nw, ne, sw, se] = numbers[..] else {
+            return Err(invalid());
+        };
+        let level = level as u32;
+
+        if level <= MACROCELL_LEAF_LEVEL {
+            return Err(String::from(
+                "multi-state Macrocell patterns are not supported",
+            ));
+        }
+        // keeps every coordinate within a usize
+        if level >= usize::BITS {
+            return Err(format!("Macrocell level {} is too deep", level));
+        }
+
+        let children = [nw, ne, sw, se];
+        for &child in &children {
+            let child_level = match nodes.get(child).ok_or_else(invalid)? {
+                _ if child == 0 => continue,
+                MacrocellNode::Leaf(_) => MACROCELL_LEAF_LEVEL,
+                MacrocellNode::Inner { level, .. } => *level,
+            };
+            if child_level != level - 1 {
+                return Err(invalid());
+            }
+        }
+
+        Ok(MacrocellNode::Inner { level, children })
+    }
+}
+
+/// Reads `-1 2`.
+fn parse_signed_pair(text: &str) -> Option<(i64, i64)> {
+    let mut parts = text.split_whitespace();
+    let pair = (parts.next()?.parse().ok()?, parts.next()?.parse().ok()?);
+    match parts.next() {
+        Some(_) => None,
+        None => Some(pair),
+    }
+}
+
+/// Life 1.05 writes rules survival first, as in `23/3`.
+fn rule_from_life_105(rule: &str) -> String {
+    match rule.split_once('/') {
+        Some((survival, birth)) if !rule.contains(|ch: char| ch.is_ascii_alphabetic()) => {
+            format!("B{}/S{}", birth, survival)
+        }
+        _ => rule.to_string(),
+    }
+}
+
+/// Rules Life 1.05 has no notation for are written as rulestrings.
+fn rule_to_life_105(rule: &Rule) -> String {
+    if rule.states > 2 || !rule.is_life_like() {
+        return rule.to_string();
+    }
+
+    let digits = |counts: &[bool; 9]| -> String {
+        (0..9)
+            .filter(|count| counts[*count])
+            .map(|count| count.to_string())
+            .collect()
+    };
+    format!("{}/{}", digits(&rule.survival), digits(&rule.birth))
+}
+
+fn rle_token(run: usize, tag: char) -> String {
+    match run {
+        1 => tag.to_string(),
+        _ => format!("{}{}", run, tag),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_parse_glider() {
+        let pattern =
+            Pattern::parse_rle("#N Glider\n#C A comment\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!")
+                .unwrap();
+
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.comments, vec!["A comment"]);
+        assert_eq!(pattern.rule.as_deref(), Some("B3/S23"));
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+
+        let mut grid = Grid::new(10, 10);
+        grid.seed(pattern, (2, 2));
+
+        #[rustfmt::skip]
+        let expected_cells = HashSet::from([
+                    (3, 2),
+                            (4, 3),
+            (2, 4), (3, 4), (4, 4),
+        ]);
+
+        assert_eq!(grid.cells, expected_cells);
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let pattern = Pattern::parse_rle("x = 1, y = 1, rule = B36/S23\no!").unwrap();
+        assert_eq!(pattern.parse_rule(), Some("B36/S23".parse().unwrap()));
+
+        let pattern = Pattern::parse_rle("x = 1, y = 1\no!").unwrap();
+        assert_eq!(pattern.parse_rule(), None);
+
+        let pattern =
+            Pattern::parse_rle("x = 2, y = 1, rule = R5,C0,M1,S34..58,B34..45,NM\n2o!").unwrap();
+        assert_eq!((pattern.width, pattern.height), (2, 1));
+        assert_eq!(pattern.parse_rule(), Rule::preset("Bosco's Rule"));
+    }
+
+    #[test]
+    fn test_parse_multiline_runs() {
+        // blank rows are encoded as runs of `$`, and lines may break anywhere
+        let pattern = Pattern::parse_rle("x = 2, y = 4\n2o3$\nbo!\n").unwrap();
+
+        assert_eq!(pattern.cells, vec![(0, 0), (1, 0), (1, 3)]);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Pattern::parse_rle("x = a, y = 3\no!").is_err());
+        assert!(Pattern::parse_rle("x = 1 y = 3\no!").is_err());
+        assert!(Pattern::parse_rle("x = 1, y = 1\no?!").is_err());
+        // runs past the size in the header, or any size at all
+        assert!(Pattern::parse_rle("x = 3, y = 3\n4o!").is_err());
+        assert!(Pattern::parse_rle("x = 3, y = 3\n99999999999999999999999o!").is_err());
+    }
+
+    #[test]
+    fn test_parse_cells() {
+        let pattern = Pattern::parse_cells("!Name: Glider\n!A comment\n.O\n..O\nOOO\n").unwrap();
+
+        assert_eq!(pattern.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.comments, vec!["A comment"]);
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert!(Pattern::parse_cells("O#O").is_err());
+    }
+
+    #[test]
+    fn test_oriented() {
+        // the glider, heading down and right
+        let glider = Pattern::parse_cells(".O\n..O\nOOO\n").unwrap();
+        let turn = |quarter_turns| Orientation {
+            quarter_turns,
+            ..Default::default()
+        };
+
+        // a quarter turn clockwise sends it down and left
+        let turned = glider.oriented(turn(1));
+        assert_eq!((turned.width, turned.height), (3, 3));
+        assert_eq!(turned.cells, vec![(0, 0), (0, 1), (2, 1), (0, 2), (1, 2)]);
+        assert_eq!(glider.oriented(turn(4)), glider);
+
+        let flipped = glider.oriented(Orientation {
+            flip_horizontal: true,
+            ..Default::default()
+        });
+        assert_eq!(flipped.cells, vec![(1, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+
+        // a wide pattern turns tall
+        let bar = Pattern::parse_cells("OOOO\n").unwrap();
+        let upright = bar.oriented(turn(3));
+        assert_eq!((upright.width, upright.height), (1, 4));
+        assert_eq!(upright.cells, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+    }
+
+    #[test]
+    fn test_parse_guesses_format() {
+        let formats = [
+            "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n",
+            "!Name: Glider\n.O\n..O\nOOO\n",
+            "\n.O\n..O\nOOO",
+            "#Life 1.06\n1 0\n2 1\n0 2\n1 2\n2 2\n",
+            "xq4_153",
+        ];
+        for text in formats {
+            let pattern = Pattern::parse(text).unwrap();
+            assert_eq!(pattern.cells.len(), 5, "{:?}", text);
+        }
+        assert!(Pattern::parse("not a pattern").is_err());
+    }
+
+    #[test]
+    fn test_write_cells_round_trip() {
+        let cells = HashSet::from([(4, 2), (5, 2), (9, 2), (5, 6)]);
+        let mut pattern = Pattern::from_cells(&cells, BoundingBox::of(&cells).unwrap());
+        pattern.name = Some(String::from("Sketch"));
+        let mut text = Vec::new();
+        pattern.write_cells(&mut text).unwrap();
+
+        let text = String::from_utf8(text).unwrap();
+        assert_eq!(text, "!Name: Sketch\nOO...O\n\n\n\n.O\n");
+        assert_eq!(Pattern::parse_cells(&text).unwrap(), pattern);
+    }
+
+    #[test]
+    fn test_parse_life_105() {
+        let pattern = Pattern::parse_life(
+            "#Life 1.05\n#D Two gliders\n#R 23/36\n#P -1 -1\n.*\n..*\n***\n#P 4 0\n*\n",
+        )
+        .unwrap();
+
+        assert_eq!(pattern.name.as_deref(), Some("Two gliders"));
+        assert_eq!(pattern.parse_rule(), Some("B36/S23".parse().unwrap()));
+        assert_eq!((pattern.width, pattern.height), (6, 3));
+        assert_eq!(
+            pattern.cells,
+            vec![(1, 0), (2, 1), (5, 1), (0, 2), (1, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_life_106() {
+        let pattern = Pattern::parse_life("#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+
+        assert_eq!((pattern.width, pattern.height), (3, 3));
+        assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        assert!(Pattern::parse_life("#Life 1.06\n0 one\n").is_err());
+        assert!(Pattern::parse_life("0 1\n").is_err());
+    }
+
+    #[test]
+    fn test_write_life_round_trip() {
+        let mut pattern =
+            Pattern::parse_rle("#N Glider\nx = 3, y = 4, rule = B36/S23\nbo$2bo$3o$!").unwrap();
+        pattern.height = 3;
+
+        let mut text = Vec::new();
+        pattern.write_life_105(&mut text).unwrap();
+        let text = String::from_utf8(text).unwrap();
+        assert_eq!(
+            text,
+            "#Life 1.05\n#D Glider\n#R 23/36\n#P 0 0\n.*\n..*\n***\n"
+        );
+        let read = Pattern::parse_life(&text).unwrap();
+        assert_eq!(read.cells, pattern.cells);
+        assert_eq!(read.parse_rule(), pattern.parse_rule());
+
+        let mut text = Vec::new();
+        pattern.write_life_106(&mut text).unwrap();
+        let read = Pattern::parse_life(&String::from_utf8(text).unwrap()).unwrap();
+        assert_eq!(read.cells, pattern.cells);
+    }
+
+    #[test]
+    fn test_parse_macrocell() {
+        // a glider in the nw and se corners of a 16 by 16 square, repeated in
+        // each corner of a 32 by 32 square but the sw
+        let pattern = Pattern::parse_macrocell(
+            "[M2] (golly 4.0)\n#R B36/S23\n.*$..*$***$\n4 1 0 0 1\n5 2 2 0 2\n",
+        )
+        .unwrap();
+
+        assert_eq!(pattern.rule.as_deref(), Some("B36/S23"));
+        assert_eq!(pattern.cells.len(), 30);
+        assert_eq!((pattern.width, pattern.height), (27, 27));
+        assert_eq!(pattern.cells[0], (1, 0));
+        assert!(pattern.cells.contains(&(25, 26)));
+    }
+
+    #[test]
+    fn test_parse_macrocell_invalid() {
+        assert!(Pattern::parse_macrocell("4 1 0 0 0\n").is_err());
+        // refers to a node that doesn't exist yet
+        assert!(Pattern::parse_macrocell("[M2]\n.*$\n4 2 0 0 0\n").is_err());
+        // a level 5 node needs level 4 children
+        assert!(Pattern::parse_macrocell("[M2]\n.*$\n5 1 0 0 0\n").is_err());
+        // level 1 nodes only appear in multi-state patterns
+        assert!(Pattern::parse_macrocell("[M2]\n1 0 1 1 0\n").is_err());
+        assert!(Pattern::parse_macrocell("[M2]\n*********$\n").is_err());
+        // 60 cells, counted before they're expanded
+        let gliders = "[M2]\n.*$..*$***$\n4 1 1 1 1\n5 2 2 2 0\n";
+        assert!(Pattern::parse_macrocell_within(gliders, 59).is_err());
+        assert!(Pattern::parse_macrocell_within(gliders, 60).is_ok());
+    }
+
+    #[test]
+    fn test_parse_apgcode() {
+        let glider = Pattern::parse_apgcode("xq4_153").unwrap();
+        assert_eq!(glider.name.as_deref(), Some("xq4_153"));
+        assert_eq!((glider.width, glider.height), (3, 3));
+        assert_eq!(glider.cells, vec![(0, 0), (1, 0), (2, 0), (2, 1), (1, 2)]);
+
+        let blinker = Pattern::parse_apgcode("xp2_7").unwrap();
+        assert_eq!(blinker.cells, vec![(0, 0), (0, 1), (0, 2)]);
+
+        // two blocks with a gap of 4 + 2 columns, then a cell in the next strip
+        let sparse = Pattern::parse_apgcode("xs9_33y233z1").unwrap();
+        assert_eq!((sparse.width, sparse.height), (10, 6));
+        assert!(sparse.cells.contains(&(8, 0)));
+        assert!(sparse.cells.contains(&(0, 5)));
+    }
+
+    #[test]
+    fn test_parse_apgcode_invalid() {
+        assert!(Pattern::parse_apgcode("glider").is_err());
+        assert!(Pattern::parse_apgcode("yl144_1_16_afb5f3db909e60548f086e22ee3353ac").is_err());
+        assert!(Pattern::parse_apgcode("xqz_153").is_err());
+        assert!(Pattern::parse_apgcode("xq4_15!").is_err());
+    }
+
+    #[test]
+    fn test_write_rle() {
+        let glider = Pattern::parse_rle(
+            "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!",
+        )
+        .unwrap();
+        assert_eq!(glider.author.as_deref(), Some("Richard K. Guy"));
+        let mut text = Vec::new();
+        glider.write_rle(&mut text).unwrap();
+
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+        );
+    }
+
+    #[test]
+    fn test_write_rle_round_trip() {
+        let cells = HashSet::from([(4, 2), (5, 2), (9, 2), (5, 6), (40, 4)]);
+        let bounds = BoundingBox::of(&cells).unwrap().pad(1);
+        let pattern = Pattern::from_cells(&cells, bounds);
+        let mut text = Vec::new();
+        pattern.write_rle(&mut text).unwrap();
+
+        let text = String::from_utf8(text).unwrap();
+        assert!(text.lines().all(|line| line.len() <= RLE_LINE_LENGTH));
+        assert_eq!(Pattern::parse_rle(&text).unwrap(), pattern);
+    }
+}
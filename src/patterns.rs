@@ -0,0 +1,118 @@
+use crate::{config, golly, library::UserSeed};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+const PATTERNS_DIR: &str = "patterns";
+/// Where patterns at the top of the directory go, outside any folder.
+pub const CATEGORY: &str = "Patterns";
+/// How often the directory is looked at for changes.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where the user keeps pattern files to pick as seeds: `patterns` in the
+/// config directory, with a folder for each category.
+pub fn dir() -> std::io::Result<PathBuf> {
+    config::config_dir()
+        .map(|dir| dir.join(PATTERNS_DIR))
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no home directory"))
+}
+
+/// A directory of the user's pattern files, read for seeds and watched to
+/// read them again when any are added, removed or changed.
+#[derive(Debug)]
+pub struct PatternDir {
+    pub path: PathBuf,
+    /// Every file in it with when it was last changed and its size, as of
+    /// the last look.
+    files: Vec<(PathBuf, Option<SystemTime>, u64)>,
+    checked: Instant,
+}
+
+impl PatternDir {
+    pub fn new(path: PathBuf) -> PatternDir {
+        PatternDir {
+            files: files(&path),
+            path,
+            checked: Instant::now(),
+        }
+    }
+
+    /// The seeds in every pattern file in the directory, or none if it
+    /// doesn't exist yet.
+    pub fn load(&self) -> Vec<UserSeed> {
+        golly::load_dir(&self.path, CATEGORY).unwrap_or_default()
+    }
+
+    /// Whether anything in the directory changed since the last look, looking
+    /// again at most once every [`CHECK_INTERVAL`].
+    pub fn poll(&mut self) -> bool {
+        if self.checked.elapsed() < CHECK_INTERVAL {
+            return false;
+        }
+        self.checked = Instant::now();
+        let files = files(&self.path);
+        match files == self.files {
+            true => false,
+            false => {
+                self.files = files;
+                true
+            }
+        }
+    }
+}
+
+/// Every file under `dir`, sorted, with when it was last changed and its
+/// size, or none if it can't be read.
+fn files(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let mut files = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        match metadata.is_dir() {
+            true => files.extend(self::files(&entry.path())),
+            false => files.push((entry.path(), metadata.modified().ok(), metadata.len())),
+        }
+    }
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll() {
+        let path = std::env::temp_dir().join(format!("{}-patterns", std::process::id()));
+        let mut dir = PatternDir::new(path.clone());
+        assert!(dir.load().is_empty());
+
+        let guns = path.join("Guns");
+        std::fs::create_dir_all(&guns).unwrap();
+        std::fs::write(path.join("glider.cells"), ".O\n..O\nOOO\n").unwrap();
+        std::fs::write(guns.join("notes.txt"), "to do").unwrap();
+        // not looked at again until a second has gone by
+        assert!(!dir.poll());
+        dir.checked -= CHECK_INTERVAL;
+        assert!(dir.poll());
+        dir.checked -= CHECK_INTERVAL;
+        assert!(!dir.poll());
+
+        let seeds = dir.load();
+        std::fs::remove_file(guns.join("notes.txt")).unwrap();
+        dir.checked -= CHECK_INTERVAL;
+        assert!(dir.poll());
+        std::fs::remove_dir_all(&path).unwrap();
+
+        let names: Vec<(&str, &str)> = seeds
+            .iter()
+            .map(|seed| (seed.category.as_str(), seed.name()))
+            .collect();
+        assert_eq!(names, vec![(CATEGORY, "glider")]);
+    }
+}
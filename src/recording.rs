@@ -0,0 +1,344 @@
+use crate::{
+    compress,
+    grid::{Cell, Grid},
+    rule::Rule,
+};
+use clap::Args as ClapArgs;
+use std::{
+    collections::HashSet,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+const HEADER: &str = "#Life recording";
+
+/// Options for the `replay` subcommand.
+#[derive(Debug, Clone, PartialEq, ClapArgs)]
+pub struct ReplayArgs {
+    /// Recording made with --record
+    #[arg(value_name = "FILE")]
+    pub path: PathBuf,
+}
+
+/// The cells born and died from one frame of a recording to the next.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    pub born: Vec<Cell>,
+    pub died: Vec<Cell>,
+}
+
+impl Frame {
+    /// The changes from `before` to `after`, sorted so the same run is
+    /// always written the same way.
+    pub fn between(before: &HashSet<Cell>, after: &HashSet<Cell>) -> Frame {
+        let sorted = |cells: &HashSet<Cell>, others: &HashSet<Cell>| {
+            let mut cells: Vec<Cell> = cells.difference(others).copied().collect();
+            cells.sort_by_key(|&(x, y)| (y, x));
+            cells
+        };
+        Frame {
+            born: sorted(after, before),
+            died: sorted(before, after),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.born.is_empty() && self.died.is_empty()
+    }
+
+    /// Makes the changes, keeping the cells from before as the previous
+    /// generation, as a tick would.
+    pub fn apply(&self, grid: &mut Grid) {
+        grid.previous = grid.cells.clone();
+        for cell in &self.died {
+            grid.remove_cell(cell);
+        }
+        for cell in &self.born {
+            grid.add_cell(*cell);
+        }
+        grid.born = self.born.iter().copied().collect();
+        grid.died = self.died.iter().copied().collect();
+    }
+}
+
+/// A run of the board: its size and rule, and what changed in every frame,
+/// the first one bringing in the cells it started with.
+///
+/// Stored as text, one frame per line with a `+x,y` for each birth and a
+/// `-x,y` for each death, and a blank line for a frame that starts empty:
+///
+/// ```txt
+/// #Life recording
+/// rule B3/S23
+/// board 80 40
+/// +11,11 +11,12 +11,13
+/// -11,11 -11,13 +10,12 +12,12
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    pub rule: Rule,
+    pub width: usize,
+    pub height: usize,
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn read<R: BufRead>(reader: R) -> std::io::Result<Recording> {
+        let invalid = |line: &str| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid recording line {:?}", line),
+            )
+        };
+
+        let mut recording = Recording {
+            rule: Rule::default(),
+            width: 0,
+            height: 0,
+            frames: Vec::new(),
+        };
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(rule) = line.strip_prefix("rule ") {
+                recording.rule = rule.parse().map_err(|_| invalid(line))?;
+                continue;
+            }
+
+            if let Some(size) = line.strip_prefix("board ") {
+                let (width, height) = size.split_once(' ').ok_or_else(|| invalid(line))?;
+                recording.width = width.trim().parse().map_err(|_| invalid(line))?;
+                recording.height = height.trim().parse().map_err(|_| invalid(line))?;
+                continue;
+            }
+
+            let mut frame = Frame::default();
+            for change in line.split_whitespace() {
+                let (sign, cell) = change.split_at_checked(1).ok_or_else(|| invalid(line))?;
+                let (x, y) = cell.split_once(',').ok_or_else(|| invalid(line))?;
+                let cell = (
+                    x.parse().map_err(|_| invalid(line))?,
+                    y.parse().map_err(|_| invalid(line))?,
+                );
+                match sign {
+                    "+" => frame.born.push(cell),
+                    "-" => frame.died.push(cell),
+                    _ => return Err(invalid(line)),
+                }
+            }
+            recording.frames.push(frame);
+        }
+
+        Ok(recording)
+    }
+
+    /// Loads from a file, decompressed if it ends in `.gz` or `.zst`.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Recording> {
+        Recording::read(compress::open(path)?)
+    }
+}
+
+/// Writes a recording as the run goes, adding a frame whenever the live
+/// cells change.
+pub struct Recorder<W: Write> {
+    writer: W,
+    /// The cells as of the last frame written.
+    cells: HashSet<Cell>,
+}
+
+impl<W: Write> std::fmt::Debug for Recorder<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder")
+            .field("cells", &self.cells.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    /// Starts a recording of the grid, with its current cells as the first frame.
+    pub fn new(mut writer: W, grid: &Grid) -> std::io::Result<Recorder<W>> {
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "rule {}", grid.rule)?;
+        writeln!(writer, "board {} {}", grid.width, grid.height)?;
+
+        let mut recorder = Recorder {
+            writer,
+            cells: HashSet::new(),
+        };
+        recorder.write_frame(&Frame::between(&HashSet::new(), &grid.cells))?;
+        recorder.cells = grid.cells.clone();
+        Ok(recorder)
+    }
+
+    /// Writes a frame if the cells changed since the last one.
+    pub fn capture(&mut self, cells: &HashSet<Cell>) -> std::io::Result<()> {
+        let frame = Frame::between(&self.cells, cells);
+        if frame.is_empty() {
+            return Ok(());
+        }
+        self.write_frame(&frame)?;
+        self.cells = cells.clone();
+        Ok(())
+    }
+
+    fn write_frame(&mut self, frame: &Frame) -> std::io::Result<()> {
+        let changes = frame
+            .born
+            .iter()
+            .map(|(x, y)| format!("+{},{}", x, y))
+            .chain(frame.died.iter().map(|(x, y)| format!("-{},{}", x, y)));
+        writeln!(self.writer, "{}", changes.collect::<Vec<_>>().join(" "))
+    }
+}
+
+impl Recorder<compress::Writer> {
+    /// Starts recording to a file, compressed if it ends in `.gz` or `.zst`.
+    pub fn create<P: AsRef<Path>>(path: P, grid: &Grid) -> std::io::Result<Self> {
+        Recorder::new(compress::create(path)?, grid)
+    }
+
+    pub fn finish(self) -> std::io::Result<()> {
+        self.writer.finish()
+    }
+}
+
+/// Plays a recording back on a grid, one frame at a time.
+#[derive(Debug)]
+pub struct Playback {
+    recording: Recording,
+    /// How many frames have been applied.
+    position: usize,
+}
+
+impl Playback {
+    /// Sets the grid up the way the recording started.
+    pub fn start(recording: Recording, grid: &mut Grid) -> Playback {
+        grid.clear();
+        if recording.width > 0 && recording.height > 0 {
+            grid.resize(recording.width, recording.height);
+        }
+        grid.rule = recording.rule;
+
+        let mut playback = Playback {
+            recording,
+            position: 0,
+        };
+        playback.step(grid);
+        playback
+    }
+
+    /// Applies the next frame, returning false once there are none left.
+    pub fn step(&mut self, grid: &mut Grid) -> bool {
+        let Some(frame) = self.recording.frames.get(self.position) else {
+            return false;
+        };
+        frame.apply(grid);
+        grid.generation = self.position;
+        self.position += 1;
+        true
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn len(&self) -> usize {
+        self.recording.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recording.frames.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::Oscillator;
+
+    #[test]
+    fn test_write() {
+        let mut grid = Grid::new(5, 5);
+        grid.seed(Oscillator::Blinker, (1, 1));
+
+        let mut bytes = Vec::new();
+        let mut recorder = Recorder::new(&mut bytes, &grid).unwrap();
+        grid.tick();
+        recorder.capture(&grid.cells).unwrap();
+        // nothing changed, so no frame
+        recorder.capture(&grid.cells).unwrap();
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            concat!(
+                "#Life recording\n",
+                "rule B3/S23\n",
+                "board 5 5\n",
+                "+1,1 +2,1 +3,1\n",
+                "+2,0 +2,2 -1,1 -3,1\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_playback() {
+        let mut grid = Grid::new(20, 20);
+        grid.rule = "B36/S23".parse().unwrap();
+        grid.seed(crate::seed::Spaceship::Glider, (3, 3));
+
+        let mut bytes = Vec::new();
+        let mut recorder = Recorder::new(&mut bytes, &grid).unwrap();
+        let mut generations = vec![grid.cells.clone()];
+        for _ in 0..8 {
+            grid.tick();
+            recorder.capture(&grid.cells).unwrap();
+            generations.push(grid.cells.clone());
+        }
+
+        let recording = Recording::read(bytes.as_slice()).unwrap();
+        assert_eq!(recording.rule, grid.rule);
+        assert_eq!((recording.width, recording.height), (20, 20));
+
+        let mut replayed = Grid::new(5, 5);
+        let mut playback = Playback::start(recording, &mut replayed);
+        assert_eq!(replayed.width, 20);
+        assert_eq!(replayed.rule, grid.rule);
+        assert_eq!(replayed.cells, generations[0]);
+        for generation in &generations[1..] {
+            assert!(playback.step(&mut replayed));
+            assert_eq!(&replayed.cells, generation);
+        }
+        assert!(!playback.step(&mut replayed));
+        assert_eq!(playback.position(), playback.len());
+        assert_eq!(replayed.generation, 8);
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(Recording::read("rule B3/X23\n".as_bytes()).is_err());
+        assert!(Recording::read("board 10\n".as_bytes()).is_err());
+        assert!(Recording::read("+1\n".as_bytes()).is_err());
+        assert!(Recording::read("*1,2\n".as_bytes()).is_err());
+        assert!(Recording::read("+1,a\n".as_bytes()).is_err());
+        assert!(Recording::read("é1,2\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_starts_empty() {
+        let mut grid = Grid::new(5, 5);
+        let mut bytes = Vec::new();
+        let mut recorder = Recorder::new(&mut bytes, &grid).unwrap();
+        grid.add_cell((2, 2));
+        recorder.capture(&grid.cells).unwrap();
+
+        let recording = Recording::read(bytes.as_slice()).unwrap();
+        assert_eq!(recording.frames.len(), 2);
+        assert!(recording.frames[0].is_empty());
+    }
+}
@@ -0,0 +1,378 @@
+use crate::{grid::Shade, theme::Theme};
+use clap::ValueEnum;
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+};
+
+/// Characters for cells fading out under a Generations rule, from just died
+/// to nearly gone.
+const ASCII_FADES: [char; 3] = ['%', '=', '-'];
+/// Characters for where cells died, from just now to long ago.
+const ASCII_TRAILS: [char; 3] = ['*', ':', ','];
+/// Characters for squares of cells zoomed out, by quarters alive.
+const ASCII_DENSITIES: [char; 5] = ['.', ':', '*', '%', '#'];
+/// The bit for each dot of a braille character, by row and then column.
+pub const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+/// The braille character with no dots raised, the others counting up from it.
+pub const BRAILLE_BLANK: u32 = 0x2800;
+/// How many columns a cell can be set to take where each character shows
+/// one: narrow, or about square in most fonts.
+pub const CELL_WIDTHS: RangeInclusive<u16> = 1..=2;
+
+/// How the board's shades are drawn in the terminal's characters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Renderer {
+    /// Each cell two columns wide, in emoji or the theme's colors.
+    #[default]
+    Blocks,
+    /// Each cell a plain character one column wide, like `#` or `.`, for
+    /// terminals and fonts that garble the emoji.
+    Ascii,
+    /// Two cells across and four down in each character, as its dots.
+    Braille,
+    /// Two cells down in each character, as its top and bottom halves, which
+    /// makes them as square as blocks in half the width.
+    HalfBlock,
+}
+
+impl Display for Renderer {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Renderer::Blocks => write!(f, "blocks"),
+            Renderer::Ascii => write!(f, "ascii"),
+            Renderer::Braille => write!(f, "braille"),
+            Renderer::HalfBlock => write!(f, "half-block"),
+        }
+    }
+}
+
+impl Renderer {
+    /// The next one in the cycle: blocks, half-block, braille, ascii.
+    pub fn next(self) -> Renderer {
+        match self {
+            Renderer::Blocks => Renderer::HalfBlock,
+            Renderer::HalfBlock => Renderer::Braille,
+            Renderer::Braille => Renderer::Ascii,
+            Renderer::Ascii => Renderer::Blocks,
+        }
+    }
+
+    /// How many of the terminal's columns each character of the board takes,
+    /// `cell_width` if it's set and a character shows a single cell.
+    pub fn columns(&self, cell_width: Option<u16>) -> u16 {
+        match self {
+            Renderer::Blocks => cell_width.unwrap_or(2),
+            Renderer::Ascii => cell_width.unwrap_or(1),
+            Renderer::Braille | Renderer::HalfBlock => 1,
+        }
+    }
+
+    /// How many of the board's cells, or squares of them zoomed out, each
+    /// character shows across and down.
+    pub fn cells(&self) -> (usize, usize) {
+        match self {
+            Renderer::Blocks | Renderer::Ascii => (1, 1),
+            Renderer::Braille => (2, 4),
+            Renderer::HalfBlock => (1, 2),
+        }
+    }
+
+    /// The board in `theme`'s colors, a line of spans for each row of
+    /// characters, each as wide as [`Renderer::columns`] says.
+    pub fn lines(
+        &self,
+        theme: &Theme,
+        shades: &[Vec<Shade>],
+        cell_width: Option<u16>,
+    ) -> Vec<Line<'static>> {
+        let columns = self.columns(cell_width) as usize;
+        let (across, down) = self.cells();
+        shades
+            .chunks(down)
+            .map(|rows| {
+                let width = rows[0].len().div_ceil(across);
+                line((0..width).map(|column| match self {
+                    Renderer::Blocks => theme.block(rows[0][column], columns),
+                    Renderer::Ascii => ascii(theme, rows[0][column], columns),
+                    Renderer::Braille => braille(theme, rows, column),
+                    Renderer::HalfBlock => half_block(theme, rows, column),
+                }))
+            })
+            .collect()
+    }
+}
+
+/// A line of characters, those drawn the same way run together in a span.
+fn line(characters: impl Iterator<Item = (String, Style)>) -> Line<'static> {
+    let mut spans: Vec<Span> = Vec::new();
+    for (text, style) in characters {
+        match spans.last_mut() {
+            Some(span) if span.style == style => span.content.to_mut().push_str(&text),
+            _ => spans.push(Span::styled(text, style)),
+        }
+    }
+    Line::from(spans)
+}
+
+/// A shade as a plain character, in its color over the dead cells',
+/// repeated to fill `columns` but for the dots and counts.
+fn ascii(theme: &Theme, shade: Shade, columns: usize) -> (String, Style) {
+    let glyph = match shade {
+        Shade::Dead => '.',
+        Shade::Alive | Shade::Born | Shade::Age(_) | Shade::Color(_) | Shade::ElectronHead => '#',
+        Shade::Preview => 'o',
+        Shade::Overlap => 'X',
+        Shade::Died => 'x',
+        Shade::Onion => '`',
+        Shade::Bounds => '~',
+        Shade::Selected => '/',
+        Shade::WatchedAlive => '@',
+        Shade::WatchedDead => 'O',
+        Shade::Wire => '=',
+        Shade::Dying(index) => ASCII_FADES[index],
+        Shade::Trail(index) => ASCII_TRAILS[index],
+        Shade::Density(quarters) => ASCII_DENSITIES[quarters],
+        Shade::Guide { column, row } => match (column, row) {
+            (true, true) => '+',
+            (true, false) => '|',
+            _ => '-',
+        },
+        Shade::Neighbors(count) => char::from_digit(count as u32, 10).unwrap_or('+'),
+    };
+    // the dots would vanish in the color they're drawn over
+    let color = match shade {
+        Shade::Dead => theme.faint,
+        shade => theme.color(shade),
+    };
+    let text = match shade {
+        Shade::Dead | Shade::Neighbors(_) => format!("{:>columns$}", glyph),
+        _ => glyph.to_string().repeat(columns),
+    };
+    (text, Style::default().fg(color).bg(theme.dead))
+}
+
+/// The cells at `column` in the two `rows` as the top and bottom halves of a
+/// character, each in its shade's color.
+fn half_block(theme: &Theme, rows: &[Vec<Shade>], column: usize) -> (String, Style) {
+    let top = theme.color(rows[0][column]);
+    let bottom = rows
+        .get(1)
+        .map_or(theme.dead, |row| theme.color(row[column]));
+    let (glyph, style) = match (top == theme.dead, bottom == theme.dead) {
+        (true, true) => (" ", Style::default().bg(theme.dead)),
+        _ if top == bottom => ("█", Style::default().fg(top).bg(theme.dead)),
+        (true, false) => ("▄", Style::default().fg(bottom).bg(theme.dead)),
+        _ => ("▀", Style::default().fg(top).bg(bottom)),
+    };
+    (glyph.to_string(), style)
+}
+
+/// Which shades raise a braille dot, and how much their color counts in
+/// one character with others: a seed about to be placed over the rest.
+fn prominence(shade: Shade) -> Option<usize> {
+    match shade {
+        Shade::Wire => Some(0),
+        Shade::Alive
+        | Shade::Born
+        | Shade::Age(_)
+        | Shade::Color(_)
+        | Shade::ElectronHead
+        | Shade::Density(_) => Some(1),
+        Shade::WatchedAlive => Some(2),
+        Shade::Preview => Some(3),
+        Shade::Overlap => Some(4),
+        _ => None,
+    }
+}
+
+/// The braille character for the 2 by 4 cells at `column` among `rows`, its
+/// dots in the color of the most prominent of them. Marks drawn on dead
+/// cells, like trails and neighbor counts, don't show, but for selected
+/// ones and the frame around live cells, drawn behind the dots, and guide
+/// lines where there are none.
+fn braille(theme: &Theme, rows: &[Vec<Shade>], column: usize) -> (String, Style) {
+    let mut dots = 0u8;
+    let mut lead: Option<(usize, Shade)> = None;
+    let mut background = theme.dead;
+    let mut guide = None;
+    for (row, shades) in rows.iter().enumerate() {
+        for (x, &shade) in shades.iter().skip(column * 2).take(2).enumerate() {
+            match (prominence(shade), shade) {
+                (Some(prominence), _) => {
+                    dots |= BRAILLE_DOTS[row][x];
+                    if lead.is_none_or(|(most, _)| prominence > most) {
+                        lead = Some((prominence, shade));
+                    }
+                }
+                (None, Shade::Selected | Shade::Bounds) => background = theme.color(shade),
+                (None, Shade::Guide { column, row }) => guide = guide.or(Some((column, row))),
+                _ => {}
+            }
+        }
+    }
+
+    match (lead, guide) {
+        (Some((_, shade)), _) => (
+            char::from_u32(BRAILLE_BLANK + dots as u32)
+                .unwrap_or(' ')
+                .to_string(),
+            Style::default().fg(theme.color(shade)).bg(background),
+        ),
+        (None, Some(guide)) => (
+            match guide {
+                (true, true) => String::from("┼"),
+                (true, false) => String::from("│"),
+                _ => String::from("─"),
+            },
+            Style::default().fg(theme.faint).bg(background),
+        ),
+        (None, None) => (String::from(" "), Style::default().bg(background)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lines() {
+        let themes = theme::built_in();
+        let [emoji, classic] = [&themes[0], &themes[1]];
+        let shades = vec![
+            vec![Shade::Dead, Shade::Alive, Shade::Alive],
+            vec![
+                Shade::Guide {
+                    column: true,
+                    row: false,
+                },
+                Shade::Neighbors(3),
+                Shade::Preview,
+            ],
+        ];
+        assert_eq!(
+            text(&Renderer::Blocks.lines(emoji, &shades, None)),
+            vec!["⬜⬛⬛", "│ ３🟦"]
+        );
+
+        let lines = Renderer::Blocks.lines(classic, &shades, None);
+        let styles: Vec<Style> = lines[0].spans.iter().map(|span| span.style).collect();
+        assert_eq!(
+            styles,
+            vec![
+                Style::default().bg(classic.dead),
+                Style::default().bg(classic.alive)
+            ]
+        );
+        assert_eq!(text(&lines), vec!["      ", "│  3  "]);
+
+        let lines = Renderer::Ascii.lines(classic, &shades, None);
+        assert_eq!(text(&lines), vec![".##", "|3o"]);
+        assert_eq!(
+            lines[0].spans[1].style,
+            Style::default().fg(classic.alive).bg(classic.dead)
+        );
+    }
+
+    #[test]
+    fn test_cell_width() {
+        let themes = theme::built_in();
+        let [emoji, classic] = [&themes[0], &themes[1]];
+        let shades = vec![
+            vec![Shade::Dead, Shade::Alive, Shade::WatchedAlive],
+            vec![
+                Shade::Guide {
+                    column: true,
+                    row: true,
+                },
+                Shade::Neighbors(3),
+                Shade::Density(4),
+            ],
+        ];
+        assert_eq!(Renderer::Blocks.columns(Some(1)), 1);
+        assert_eq!(Renderer::Ascii.columns(Some(2)), 2);
+        assert_eq!(Renderer::Braille.columns(Some(2)), 1);
+
+        // emoji don't fit one column, so the theme's colors are drawn
+        let lines = Renderer::Blocks.lines(emoji, &shades, Some(1));
+        assert_eq!(text(&lines), vec!["  o", "┼3█"]);
+        assert_eq!(lines[0].spans[1].style, Style::default().bg(emoji.alive));
+        assert_eq!(
+            text(&Renderer::Blocks.lines(classic, &shades, Some(1))),
+            text(&lines)
+        );
+        assert_eq!(
+            text(&Renderer::Ascii.lines(classic, &shades, Some(2))),
+            vec![" .##@@", "++ 3##"]
+        );
+    }
+
+    #[test]
+    fn test_braille() {
+        let classic = &theme::built_in()[1];
+        let (dead, alive, guide) = (
+            Shade::Dead,
+            Shade::Alive,
+            Shade::Guide {
+                column: true,
+                row: false,
+            },
+        );
+        let shades = vec![
+            vec![alive, dead, dead, dead, guide, dead],
+            vec![dead, alive, dead, dead, guide, dead],
+            vec![dead, dead, dead, Shade::Preview, guide, dead],
+            vec![alive, alive, dead, dead, guide, dead],
+            // a row short of a whole character
+            vec![alive, dead, dead, dead, dead, dead],
+        ];
+        let lines = Renderer::Braille.lines(classic, &shades, None);
+        assert_eq!(text(&lines), vec!["⣑⠠│", "⠁  "]);
+        // the seed about to be placed in its own color
+        let styles: Vec<Style> = lines[0].spans.iter().map(|span| span.style).collect();
+        assert_eq!(
+            styles,
+            vec![
+                Style::default().fg(classic.alive).bg(classic.dead),
+                Style::default().fg(classic.preview).bg(classic.dead),
+                Style::default().fg(classic.faint).bg(classic.dead),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_half_block() {
+        let classic = &theme::built_in()[1];
+        let (dead, alive) = (Shade::Dead, Shade::Alive);
+        let shades = vec![
+            vec![alive, alive, dead, dead, Shade::Preview],
+            vec![alive, dead, alive, dead, alive],
+            vec![dead, alive, dead, dead, dead],
+        ];
+        let lines = Renderer::HalfBlock.lines(classic, &shades, None);
+        assert_eq!(text(&lines), vec!["█▀▄ ▀", " ▀   "]);
+        assert_eq!(
+            lines[0].spans.last().unwrap().style,
+            Style::default().fg(classic.preview).bg(classic.alive)
+        );
+    }
+}
@@ -0,0 +1,652 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// The most states a Generations rule can have, as in Golly.
+pub const MAX_STATES: usize = 256;
+/// The furthest a Larger than Life neighborhood can reach. Golly goes up to
+/// 500, but every live cell visits each cell in reach on every tick, which is
+/// already slow at 20.
+pub const MAX_RADIUS: usize = 10;
+
+/// Well-known rules and their names.
+pub const PRESETS: [(&str, &str); 28] = [
+    ("Life", "B3/S23"),
+    ("HighLife", "B36/S23"),
+    ("Seeds", "B2/S"),
+    ("Day & Night", "B3678/S34678"),
+    ("Life Without Death", "B3/S012345678"),
+    ("Maze", "B3/S12345"),
+    ("Mazectric", "B3/S1234"),
+    ("Replicator", "B1357/S1357"),
+    ("2x2", "B36/S125"),
+    ("34 Life", "B34/S34"),
+    ("Diamoeba", "B35678/S5678"),
+    ("Morley", "B368/S245"),
+    ("Anneal", "B4678/S35678"),
+    ("Coral", "B3/S45678"),
+    ("Amoeba", "B357/S1358"),
+    ("Serviettes", "B234/S"),
+    ("Brian's Brain", "B2/S/C3"),
+    ("Star Wars", "B2/S345/C4"),
+    ("Fireworks", "B13/S2/C21"),
+    ("Bosco's Rule", "R5,C0,M1,S34..58,B34..45,NM"),
+    ("Majority", "R4,C0,M1,S41..81,B41..81,NM"),
+    ("Waffle", "R7,C0,M1,S100..200,B75..170,NM"),
+    ("Wireworld", "WireWorld"),
+    ("Immigration", "Immigration"),
+    ("QuadLife", "QuadLife"),
+    ("Rule 30", "W30"),
+    ("Rule 90", "W90"),
+    ("Rule 110", "W110"),
+];
+
+/// Which cells around a cell count as its neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Neighborhood {
+    /// How many cells away neighbors can be.
+    pub radius: usize,
+    /// Only count cells within `radius` steps along the axes, a diamond,
+    /// instead of the whole square around the cell.
+    pub von_neumann: bool,
+}
+
+/// The eight cells around a cell.
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Neighborhood {
+            radius: 1,
+            von_neumann: false,
+        }
+    }
+}
+
+impl Neighborhood {
+    /// Whether the cell this far away is a neighbor. A cell isn't its own.
+    pub fn contains(&self, dx: i64, dy: i64) -> bool {
+        let radius = self.radius as i64;
+        (dx, dy) != (0, 0)
+            && match self.von_neumann {
+                true => dx.abs() + dy.abs() <= radius,
+                false => dx.abs() <= radius && dy.abs() <= radius,
+            }
+    }
+}
+
+/// The part of a Larger than Life rule that Life-like rules can't express.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LargerThanLife {
+    pub neighborhood: Neighborhood,
+    /// Whether a live cell counts itself towards surviving.
+    pub middle: bool,
+    /// The lowest and highest neighbor counts that bring a dead cell to life.
+    pub birth: (usize, usize),
+    /// The lowest and highest neighbor counts that keep a live cell alive.
+    pub survival: (usize, usize),
+}
+
+/// A Life-like rule, indexed by live neighbor count (0 to 8), or a
+/// Generations rule when cells fade out through more than two states, or a
+/// Larger than Life one with a bigger neighborhood, or Wireworld, or a
+/// one-dimensional elementary automaton, or Life with colored cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    /// Neighbor counts that bring a dead cell to life.
+    pub birth: [bool; 9],
+    /// Neighbor counts that keep a live cell alive.
+    pub survival: [bool; 9],
+    /// How many states a cell goes through, counting alive and dead. Past 2,
+    /// cells that die spend a tick in each state in between, can't be born
+    /// and don't count as neighbors.
+    pub states: usize,
+    /// For Larger than Life rules, whose ranges take the place of `birth`
+    /// and `survival`.
+    pub larger: Option<LargerThanLife>,
+    /// Cells are only born on wire, as in Wireworld, where live cells are
+    /// electron heads and dying ones their tails.
+    pub wireworld: bool,
+    /// For elementary automata, the Wolfram code whose bits say which
+    /// neighborhoods of three cells bring the cell below to life.
+    pub wolfram: Option<u8>,
+    /// How many colors live cells come in, 2 for Immigration and 4 for
+    /// QuadLife. Newborns take the color most of their parents have, or in
+    /// QuadLife, the one none of three differently colored parents has.
+    pub colors: usize,
+}
+
+/// Conway's Game of Life, B3/S23.
+impl Default for Rule {
+    fn default() -> Self {
+        let mut rule = Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+            states: 2,
+            larger: None,
+            wireworld: false,
+            wolfram: None,
+            colors: 1,
+        };
+        rule.birth[3] = true;
+        rule.survival[2] = true;
+        rule.survival[3] = true;
+        rule
+    }
+}
+
+/// Formats the rule as a B/S rulestring, e.g. `B3/S23`, adding the number of
+/// states for Generations rules, e.g. `B2/S/C3`. Larger than Life rules are
+/// written the way Golly does, e.g. `R5,C0,M1,S34..58,B34..45,NM`, and
+/// Wireworld as `WireWorld`, elementary automata as in Golly, e.g. `W110`,
+/// and the colored variants of Life by name.
+impl Display for Rule {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self.colors {
+            2 => return write!(f, "Immigration"),
+            4 => return write!(f, "QuadLife"),
+            _ => {}
+        }
+        if let Some(number) = self.wolfram {
+            return write!(f, "W{}", number);
+        }
+        if self.wireworld {
+            return write!(f, "WireWorld");
+        }
+        if let Some(larger) = &self.larger {
+            return write!(
+                f,
+                "R{},C{},M{},S{}..{},B{}..{},N{}",
+                larger.neighborhood.radius,
+                match self.states {
+                    2 => 0,
+                    states => states,
+                },
+                larger.middle as usize,
+                larger.survival.0,
+                larger.survival.1,
+                larger.birth.0,
+                larger.birth.1,
+                match larger.neighborhood.von_neumann {
+                    true => 'N',
+                    false => 'M',
+                }
+            );
+        }
+
+        write!(f, "B")?;
+        for count in (0..9).filter(|count| self.birth[*count]) {
+            write!(f, "{}", count)?;
+        }
+        write!(f, "/S")?;
+        for count in (0..9).filter(|count| self.survival[*count]) {
+            write!(f, "{}", count)?;
+        }
+        if self.states > 2 {
+            write!(f, "/C{}", self.states)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a B/S rulestring, e.g. `B36/S23`, a Generations one as B/S/C, e.g.
+/// `B2/S/C3`, or S/B/C, e.g. `/2/3`, a Larger than Life one, e.g.
+/// `R5,C0,M1,S34..58,B34..45,NM`, an elementary automaton's Wolfram code,
+/// e.g. `wolfram:110` or `W110`, or the name of one of the [`PRESETS`], e.g.
+/// `highlife`.
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(rulestring: &str) -> Result<Self, Self::Err> {
+        match normalize(rulestring).as_str() {
+            "wireworld" => return Ok(Rule::wireworld()),
+            "immigration" => return Ok(Rule::colored(2)),
+            "quadlife" => return Ok(Rule::colored(4)),
+            _ => {}
+        }
+        if let Some(rule) = Rule::preset(rulestring) {
+            return Ok(rule);
+        }
+        if let Some(number) = parse_wolfram(rulestring.trim()) {
+            return number.map(Rule::wolfram).ok_or_else(|| {
+                format!(
+                    "invalid rule {:?}, Wolfram codes go from 0 to 255",
+                    rulestring
+                )
+            });
+        }
+
+        let invalid = || {
+            format!(
+                "invalid rule {:?}, expected e.g. B3/S23 or highlife",
+                rulestring
+            )
+        };
+
+        if rulestring.trim().starts_with(['R', 'r']) {
+            return parse_larger_than_life(rulestring.trim()).ok_or_else(invalid);
+        }
+
+        let parts: Vec<&str> = rulestring.trim().split('/').collect();
+        let (birth, survival, states) = match parts[..] {
+            [birth, survival] => (birth, survival, "2"),
+            [birth, survival, states] if birth.starts_with(['B', 'b']) => {
+                (birth, survival, states.trim_start_matches(['C', 'c']))
+            }
+            // Generations rules are also written survival first, without letters
+            [survival, birth, states] => (birth, survival, states),
+            _ => return Err(invalid()),
+        };
+        let (birth, survival) = match parts.len() {
+            2 => (
+                birth.strip_prefix(['B', 'b']).ok_or_else(invalid)?,
+                survival.strip_prefix(['S', 's']).ok_or_else(invalid)?,
+            ),
+            _ => (
+                birth.trim_start_matches(['B', 'b']),
+                survival.trim_start_matches(['S', 's']),
+            ),
+        };
+
+        Ok(Rule {
+            birth: parse_counts(birth).ok_or_else(invalid)?,
+            survival: parse_counts(survival).ok_or_else(invalid)?,
+            states: match states.parse() {
+                Ok(states) if (2..=MAX_STATES).contains(&states) => states,
+                _ => return Err(invalid()),
+            },
+            larger: None,
+            wireworld: false,
+            wolfram: None,
+            colors: 1,
+        })
+    }
+}
+
+/// Parses the number in `wolfram:110` or `W110`, if the rulestring is
+/// written that way.
+fn parse_wolfram(rulestring: &str) -> Option<Option<u8>> {
+    let number = match rulestring.split_once(':') {
+        Some((prefix, number)) if prefix.eq_ignore_ascii_case("wolfram") => number,
+        Some(_) => return None,
+        None => rulestring.strip_prefix(['W', 'w'])?,
+    };
+    match number.chars().all(|ch| ch.is_ascii_digit()) && !number.is_empty() {
+        true => Some(number.parse().ok()),
+        false => None,
+    }
+}
+
+/// Parses comma-separated fields like `R5`, `C0`, `M1`, `S34..58`, `B34..45`
+/// and `NM`, where the radius and both ranges are required.
+fn parse_larger_than_life(rulestring: &str) -> Option<Rule> {
+    let (mut radius, mut birth, mut survival) = (None, None, None);
+    let (mut states, mut middle, mut von_neumann) = (2, false, false);
+
+    for field in rulestring.split(',') {
+        let mut chars = field.trim().chars();
+        let key = chars.next()?.to_ascii_uppercase();
+        let value = chars.as_str();
+        match key {
+            'R' => {
+                radius = Some(
+                    value
+                        .parse()
+                        .ok()
+                        .filter(|r| (1..=MAX_RADIUS).contains(r))?,
+                )
+            }
+            'C' => {
+                states = match value.parse().ok()? {
+                    0 | 1 => 2,
+                    states if states <= MAX_STATES => states,
+                    _ => return None,
+                }
+            }
+            'M' => middle = value.parse::<u8>().ok().filter(|middle| *middle <= 1)? == 1,
+            'S' => survival = Some(parse_range(value)?),
+            'B' => birth = Some(parse_range(value)?),
+            'N' => {
+                von_neumann = match value {
+                    "M" | "m" => false,
+                    "N" | "n" => true,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(Rule {
+        birth: [false; 9],
+        survival: [false; 9],
+        states,
+        wireworld: false,
+        wolfram: None,
+        colors: 1,
+        larger: Some(LargerThanLife {
+            neighborhood: Neighborhood {
+                radius: radius?,
+                von_neumann,
+            },
+            middle,
+            birth: birth?,
+            survival: survival?,
+        }),
+    })
+}
+
+/// Parses a range of neighbor counts like `34..58`.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    let (low, high) = range.split_once("..")?;
+    let (low, high) = (low.parse().ok()?, high.parse().ok()?);
+    match low <= high {
+        true => Some((low, high)),
+        false => None,
+    }
+}
+
+/// Parses a run of neighbor count digits like `23`.
+fn parse_counts(digits: &str) -> Option<[bool; 9]> {
+    let mut counts = [false; 9];
+    for digit in digits.chars() {
+        match digit.to_digit(10) {
+            Some(count) if count < 9 => counts[count as usize] = true,
+            _ => return None,
+        }
+    }
+    Some(counts)
+}
+
+/// Lowercases a preset name and drops everything but letters and digits, so
+/// `Day & Night` and `day-and-night` match.
+fn normalize(name: &str) -> String {
+    name.replace('&', "and")
+        .chars()
+        .filter(|ch| ch.is_ascii_alphanumeric())
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect()
+}
+
+impl Rule {
+    /// Wireworld: wire with an electron head on either of one or two of its
+    /// neighbors gets a head, heads become tails, and tails turn back into wire.
+    pub fn wireworld() -> Rule {
+        let mut counts = [false; 9];
+        counts[1] = true;
+        counts[2] = true;
+        Rule {
+            birth: counts,
+            survival: [false; 9],
+            states: 3,
+            larger: None,
+            wireworld: true,
+            wolfram: None,
+            colors: 1,
+        }
+    }
+
+    /// The elementary automaton with this Wolfram code.
+    pub fn wolfram(number: u8) -> Rule {
+        Rule {
+            birth: [false; 9],
+            survival: [false; 9],
+            states: 2,
+            larger: None,
+            wireworld: false,
+            wolfram: Some(number),
+            colors: 1,
+        }
+    }
+
+    /// Life with cells in this many colors.
+    fn colored(colors: usize) -> Rule {
+        Rule {
+            colors,
+            ..Rule::default()
+        }
+    }
+
+    /// Whether the rule is made of birth and survival counts alone, which
+    /// other kinds of rule either replace or can't be changed without.
+    pub fn is_life_like(&self) -> bool {
+        self.larger.is_none() && !self.wireworld && self.wolfram.is_none() && self.colors == 1
+    }
+
+    /// The preset called `name`, ignoring case, spaces and punctuation.
+    pub fn preset(name: &str) -> Option<Rule> {
+        let name = normalize(name);
+        PRESETS
+            .iter()
+            .find(|(preset, _)| normalize(preset) == name)
+            .and_then(|(_, rulestring)| rulestring.parse().ok())
+    }
+
+    /// Which of the [`PRESETS`] this is, if any.
+    pub fn name(&self) -> Option<&'static str> {
+        self.preset_index().map(|index| PRESETS[index].0)
+    }
+
+    /// The preset after this one, or the first one for a rule that isn't a preset.
+    pub fn next_preset(&self) -> Rule {
+        let index = self
+            .preset_index()
+            .map_or(0, |index| (index + 1) % PRESETS.len());
+        PRESETS[index].1.parse().unwrap_or_default()
+    }
+
+    /// The preset before this one, or the last one for a rule that isn't a preset.
+    pub fn previous_preset(&self) -> Rule {
+        let index = self.preset_index().unwrap_or(0);
+        let index = (index + PRESETS.len() - 1) % PRESETS.len();
+        PRESETS[index].1.parse().unwrap_or_default()
+    }
+
+    fn preset_index(&self) -> Option<usize> {
+        PRESETS
+            .iter()
+            .position(|(_, rulestring)| rulestring.parse() == Ok(*self))
+    }
+
+    /// Which cells count as neighbors.
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.larger
+            .map_or(Neighborhood::default(), |larger| larger.neighborhood)
+    }
+
+    pub fn is_born(&self, neighbors: usize) -> bool {
+        match &self.larger {
+            Some(larger) => (larger.birth.0..=larger.birth.1).contains(&neighbors),
+            None => self.birth.get(neighbors) == Some(&true),
+        }
+    }
+
+    /// Whether a live cell with this many live neighbors, itself not
+    /// included, stays alive.
+    pub fn survives(&self, neighbors: usize) -> bool {
+        match &self.larger {
+            Some(larger) => (larger.survival.0..=larger.survival.1)
+                .contains(&(neighbors + larger.middle as usize)),
+            None => self.survival.get(neighbors) == Some(&true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_conway() {
+        let rule = Rule::default();
+
+        assert!(rule.is_born(3));
+        assert!(!rule.is_born(2));
+        assert!(rule.survives(2));
+        assert!(rule.survives(3));
+        assert!(!rule.survives(4));
+    }
+
+    #[test]
+    fn test_display() {
+        let mut rule = Rule::default();
+        assert_eq!(rule.to_string(), "B3/S23");
+
+        rule.birth[6] = true;
+        assert_eq!(rule.to_string(), "B36/S23");
+
+        rule.survival = [false; 9];
+        assert_eq!(rule.to_string(), "B36/S");
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("B3/S23".parse(), Ok(Rule::default()));
+        assert_eq!("b3/s32".parse(), Ok(Rule::default()));
+
+        let highlife: Rule = "B36/S23".parse().unwrap();
+        assert!(highlife.is_born(6));
+
+        let seeds: Rule = "B2/S".parse().unwrap();
+        assert!(seeds.is_born(2));
+        assert_eq!(seeds.survival, [false; 9]);
+    }
+
+    #[test]
+    fn test_parse_generations() {
+        let brians_brain: Rule = "B2/S/C3".parse().unwrap();
+        assert_eq!(brians_brain.states, 3);
+        assert!(brians_brain.is_born(2));
+        assert_eq!(brians_brain.survival, [false; 9]);
+        assert_eq!(brians_brain.to_string(), "B2/S/C3");
+
+        assert_eq!("/2/3".parse(), Ok(brians_brain));
+        assert_eq!("b2/s/3".parse(), Ok(brians_brain));
+        assert_eq!("345/2/4".parse(), "B2/S345/C4".parse::<Rule>());
+        assert_eq!("B3/S23/C2".parse(), Ok(Rule::default()));
+
+        assert!("B2/S/C1".parse::<Rule>().is_err());
+        assert!("B2/S/C257".parse::<Rule>().is_err());
+        assert!("B2/S/C3/4".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_parse_larger_than_life() {
+        let bosco: Rule = "R5,C0,M1,S34..58,B34..45,NM".parse().unwrap();
+        assert_eq!(bosco.to_string(), "R5,C0,M1,S34..58,B34..45,NM");
+        assert_eq!(bosco.neighborhood().radius, 5);
+        assert!(!bosco.neighborhood().von_neumann);
+        assert!(bosco.is_born(34) && bosco.is_born(45));
+        assert!(!bosco.is_born(33) && !bosco.is_born(46));
+        // the cell itself counts towards survival
+        assert!(bosco.survives(33) && bosco.survives(57));
+        assert!(!bosco.survives(32) && !bosco.survives(58));
+
+        let fading: Rule = "r2,c3,m0,s1..4,b2..2,nn".parse().unwrap();
+        assert_eq!(fading.to_string(), "R2,C3,M0,S1..4,B2..2,NN");
+        assert_eq!(fading.states, 3);
+        assert!(fading.neighborhood().von_neumann);
+
+        assert!("R0,C0,M0,S1..2,B1..2,NM".parse::<Rule>().is_err());
+        assert!("R10,C0,M1,S1..2,B1..2,NM".parse::<Rule>().is_ok());
+        assert!("R11,C0,M1,S1..2,B1..2,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M0,S1..2,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M0,S4..2,B1..2,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M2,S1..2,B1..2,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M0,S1..2,B1..2,NX".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let moore = Neighborhood {
+            radius: 2,
+            von_neumann: false,
+        };
+        let von_neumann = Neighborhood {
+            radius: 2,
+            von_neumann: true,
+        };
+
+        assert!(!moore.contains(0, 0));
+        assert!(moore.contains(2, -2));
+        assert!(!moore.contains(3, 0));
+        assert!(von_neumann.contains(1, -1) && von_neumann.contains(0, 2));
+        assert!(!von_neumann.contains(2, -1));
+    }
+
+    #[test]
+    fn test_parse_wireworld() {
+        let wireworld: Rule = "WireWorld".parse().unwrap();
+        assert!(wireworld.wireworld);
+        assert_eq!(wireworld.to_string(), "WireWorld");
+        assert_eq!(wireworld.name(), Some("Wireworld"));
+        assert_eq!("wire-world".parse(), Ok(wireworld));
+        assert_ne!("B12/S/C3".parse(), Ok(wireworld));
+    }
+
+    #[test]
+    fn test_parse_wolfram() {
+        let rule: Rule = "wolfram:110".parse().unwrap();
+        assert_eq!(rule.wolfram, Some(110));
+        assert_eq!(rule.to_string(), "W110");
+        assert_eq!(rule.name(), Some("Rule 110"));
+        assert_eq!("W110".parse(), Ok(rule));
+        assert_eq!("Wolfram:30".parse(), Ok(Rule::wolfram(30)));
+        assert_eq!("w0".parse(), Ok(Rule::wolfram(0)));
+
+        assert!("wolfram:256".parse::<Rule>().is_err());
+        assert!("wolfram:".parse::<Rule>().is_err());
+        assert!("wolfram:x".parse::<Rule>().is_err());
+        assert!("W".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_parse_colors() {
+        let immigration: Rule = "immigration".parse().unwrap();
+        assert_eq!(immigration.colors, 2);
+        assert!(immigration.is_born(3) && immigration.survives(2));
+        assert_eq!(immigration.to_string(), "Immigration");
+
+        let quadlife: Rule = "Quad-Life".parse().unwrap();
+        assert_eq!(quadlife.colors, 4);
+        assert_eq!(quadlife.name(), Some("QuadLife"));
+        assert!(!quadlife.is_life_like());
+        assert_ne!(quadlife, Rule::default());
+    }
+
+    #[test]
+    fn test_presets() {
+        for (name, rulestring) in PRESETS {
+            let rule: Rule = rulestring.parse().unwrap();
+            assert_eq!(rule.name(), Some(name));
+            assert_eq!(name.parse(), Ok(rule));
+        }
+
+        assert_eq!("highlife".parse(), "B36/S23".parse::<Rule>());
+        assert_eq!("day-and-night".parse(), "B3678/S34678".parse::<Rule>());
+        assert_eq!(
+            "LIFE WITHOUT DEATH".parse(),
+            "B3/S012345678".parse::<Rule>()
+        );
+        assert_eq!("B36/S2".parse::<Rule>().unwrap().name(), None);
+    }
+
+    #[test]
+    fn test_cycle_presets() {
+        let life = Rule::default();
+        assert_eq!(life.next_preset().name(), Some("HighLife"));
+        assert_eq!(life.previous_preset().name(), Some("Rule 110"));
+        assert_eq!(life.previous_preset().next_preset(), life);
+
+        let unnamed: Rule = "B36/S2".parse().unwrap();
+        assert_eq!(unnamed.next_preset(), life);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("".parse::<Rule>().is_err());
+        assert!("lifelike".parse::<Rule>().is_err());
+        assert!("B3S23".parse::<Rule>().is_err());
+        assert!("B9/S23".parse::<Rule>().is_err());
+        assert!("S23/B3".parse::<Rule>().is_err());
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{compress, grid::Cell, grid::Grid, rule::Rule};
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+    path::Path,
+};
+
+const HEADER: &str = "#Life savestate";
+
+/// Everything needed to restore a board: its rule, generation, live cells and
+/// their colors, the cells still fading out under a Generations rule and
+/// Wireworld's wire.
+///
+/// Stored as text, one `x y` pair per live cell, followed by its color under a
+/// rule with colors, `dying x y state` per dying one and `wire x y` per wire
+/// cell:
+///
+/// ```txt
+/// #Life savestate
+/// rule B3/S23
+/// generation 42
+/// 10 12
+/// 11 12 1
+/// dying 12 12 2
+/// wire 13 12
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Savestate {
+    pub rule: Rule,
+    pub generation: usize,
+    pub cells: Vec<Cell>,
+    pub colors: HashMap<Cell, usize>,
+    pub dying: Vec<(Cell, usize)>,
+    pub wires: Vec<Cell>,
+}
+
+impl Savestate {
+    pub fn from_grid(grid: &Grid) -> Savestate {
+        let mut cells: Vec<Cell> = grid.cells.iter().copied().collect();
+        cells.sort_by_key(|&(x, y)| (y, x));
+        let mut dying: Vec<(Cell, usize)> = grid.dying.iter().map(|(c, s)| (*c, *s)).collect();
+        dying.sort_by_key(|&((x, y), _)| (y, x));
+        let mut wires: Vec<Cell> = grid.wires.iter().copied().collect();
+        wires.sort_by_key(|&(x, y)| (y, x));
+
+        Savestate {
+            rule: grid.rule,
+            generation: grid.generation,
+            cells,
+            colors: grid.colors.clone(),
+            dying,
+            wires,
+        }
+    }
+
+    /// Replaces the grid's cells, rule and generation with the saved ones.
+    pub fn restore(&self, grid: &mut Grid) {
+        grid.clear();
+        grid.rule = self.rule;
+        grid.generation = self.generation;
+        for cell in &self.cells {
+            grid.add_cell(*cell);
+        }
+        grid.colors = self.colors.clone();
+        grid.dying = self.dying.iter().copied().collect();
+        grid.wires = self.wires.iter().copied().collect();
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "{}", HEADER)?;
+        writeln!(writer, "rule {}", self.rule)?;
+        writeln!(writer, "generation {}", self.generation)?;
+        for (x, y) in &self.cells {
+            match self.colors.get(&(*x, *y)) {
+                Some(color) => writeln!(writer, "{} {} {}", x, y, color)?,
+                None => writeln!(writer, "{} {}", x, y)?,
+            }
+        }
+        for ((x, y), state) in &self.dying {
+            writeln!(writer, "dying {} {} {}", x, y, state)?;
+        }
+        for (x, y) in &self.wires {
+            writeln!(writer, "wire {} {}", x, y)?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: BufRead>(reader: R) -> std::io::Result<Savestate> {
+        let invalid = |line: &str| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid savestate line {:?}", line),
+            )
+        };
+
+        let mut savestate = Savestate {
+            rule: Rule::default(),
+            generation: 0,
+            cells: Vec::new(),
+            colors: HashMap::new(),
+            dying: Vec::new(),
+            wires: Vec::new(),
+        };
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(rule) = line.strip_prefix("rule ") {
+                savestate.rule = rule.parse().map_err(|_| invalid(line))?;
+                continue;
+            }
+
+            if let Some(generation) = line.strip_prefix("generation ") {
+                savestate.generation = generation.trim().parse().map_err(|_| invalid(line))?;
+                continue;
+            }
+
+            if let Some(fields) = line.strip_prefix("dying ") {
+                let fields: Vec<&str> = fields.split_whitespace().collect();
+                let [x, y, state] = fields[..] else {
+                    return Err(invalid(line));
+                };
+                let cell = (
+                    x.parse().map_err(|_| invalid(line))?,
+                    y.parse().map_err(|_| invalid(line))?,
+                );
+                let state = state.parse().map_err(|_| invalid(line))?;
+                savestate.dying.push((cell, state));
+                continue;
+            }
+
+            if let Some(wire) = line.strip_prefix("wire ") {
+                savestate
+                    .wires
+                    .push(parse_cell(wire).ok_or_else(|| invalid(line))?);
+                continue;
+            }
+
+            let cell = match parse_cell(line) {
+                Some(cell) => cell,
+                None => {
+                    let (cell, color) = line.rsplit_once(' ').ok_or_else(|| invalid(line))?;
+                    let cell = parse_cell(cell).ok_or_else(|| invalid(line))?;
+                    let color = color.parse().map_err(|_| invalid(line))?;
+                    savestate.colors.insert(cell, color);
+                    cell
+                }
+            };
+            savestate.cells.push(cell);
+        }
+
+        Ok(savestate)
+    }
+
+    /// Saves to a file, compressed if it ends in `.gz` or `.zst`.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut writer = compress::create(path)?;
+        self.write(&mut writer)?;
+        writer.finish()
+    }
+
+    /// Loads from a file, decompressed if it ends in `.gz` or `.zst`.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Savestate> {
+        Savestate::read(compress::open(path)?)
+    }
+}
+
+/// Parses an `x y` pair.
+fn parse_cell(text: &str) -> Option<Cell> {
+    let (x, y) = text.split_once(' ')?;
+    Some((x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{seed::Spaceship, wireworld::Circuit};
+
+    #[test]
+    fn test_write() {
+        let mut grid = Grid::new(5, 5);
+        grid.add_cell((2, 1));
+        grid.add_cell((1, 0));
+        grid.rule = "B36/S23".parse().unwrap();
+
+        let mut bytes = Vec::new();
+        Savestate::from_grid(&grid).write(&mut bytes).unwrap();
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            "#Life savestate\nrule B36/S23\ngeneration 0\n1 0\n2 1\n"
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_generations() {
+        let mut grid = Grid::new(10, 10);
+        grid.rule = "B3/S23/C4".parse().unwrap();
+        grid.seed(Spaceship::Glider, (3, 3));
+        grid.tick();
+        grid.tick();
+        assert!(!grid.dying.is_empty());
+
+        let mut bytes = Vec::new();
+        Savestate::from_grid(&grid).write(&mut bytes).unwrap();
+        let mut restored = Grid::new(10, 10);
+        Savestate::read(&bytes[..]).unwrap().restore(&mut restored);
+
+        assert_eq!(restored.generation, 2);
+        assert_eq!(restored.cells, grid.cells);
+        assert_eq!(restored.dying, grid.dying);
+    }
+
+    #[test]
+    fn test_save_and_load_colors() {
+        let mut grid = Grid::new(10, 10);
+        grid.rule = "Immigration".parse().unwrap();
+        grid.seed(Spaceship::Glider, (1, 1));
+        grid.color = 1;
+        grid.seed(Spaceship::Glider, (5, 5));
+        grid.tick();
+
+        let mut bytes = Vec::new();
+        Savestate::from_grid(&grid).write(&mut bytes).unwrap();
+        let mut restored = Grid::new(10, 10);
+        Savestate::read(&bytes[..]).unwrap().restore(&mut restored);
+
+        assert_eq!(restored.cells, grid.cells);
+        assert_eq!(restored.colors, grid.colors);
+        assert!(restored.colors.values().any(|&color| color == 1));
+    }
+
+    #[test]
+    fn test_save_and_load_wireworld() {
+        let mut grid = Grid::new(20, 20);
+        grid.rule = Rule::wireworld();
+        grid.seed(Circuit::Clock, (3, 3));
+        grid.tick();
+        assert!(!grid.cells.is_empty() && !grid.dying.is_empty());
+
+        let mut bytes = Vec::new();
+        Savestate::from_grid(&grid).write(&mut bytes).unwrap();
+        let mut restored = Grid::new(20, 20);
+        Savestate::read(&bytes[..]).unwrap().restore(&mut restored);
+
+        assert_eq!(restored.wires, grid.wires);
+        assert_eq!(restored.cells, grid.cells);
+        assert_eq!(restored.dying, grid.dying);
+
+        grid.tick();
+        restored.tick();
+        assert_eq!(restored.cells, grid.cells);
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        assert!(Savestate::read("rule B3/X23\n".as_bytes()).is_err());
+        assert!(Savestate::read("1 a\n".as_bytes()).is_err());
+        assert!(Savestate::read("12\n".as_bytes()).is_err());
+        assert!(Savestate::read("generation -1\n".as_bytes()).is_err());
+        assert!(Savestate::read("dying 1 2\n".as_bytes()).is_err());
+        assert!(Savestate::read("wire 1\n".as_bytes()).is_err());
+        assert!(Savestate::read("1 2 red\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let mut grid = Grid::new(10, 10);
+        grid.seed(Spaceship::Glider, (3, 3));
+        grid.rule = "B36/S23".parse().unwrap();
+        let savestate = Savestate::from_grid(&grid);
+
+        for extension in ["txt", "gz", "zst"] {
+            let path = std::env::temp_dir().join(format!(
+                "{}-savestate.{}",
+                std::process::id(),
+                extension
+            ));
+            savestate.save(&path).unwrap();
+            let loaded = Savestate::load(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            let mut restored = Grid::new(10, 10);
+            loaded.restore(&mut restored);
+            assert_eq!(restored.cells, grid.cells);
+            assert_eq!(restored.rule, grid.rule);
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{
+    compress,
+    grid::{Boundary, Cell, Grid},
+    pattern::Pattern,
+    seed::{IsSeed, Orientation, Oriented, Seed},
+};
+use serde::Deserialize;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// A full starting layout: board settings plus the patterns placed on it.
+///
+/// Written in TOML (or JSON, for `.json` files):
+///
+/// ```toml
+/// width = 120
+/// height = 60
+/// rule = "B3/S23"
+/// boundary = "dead"
+///
+/// [[patterns]]
+/// seed = "glider"
+/// at = [10, 10]
+/// rotate = 90
+/// flip = "horizontal"
+///
+/// # four blocks in a row, 10 cells apart
+/// [[patterns]]
+/// seed = "block"
+/// at = [10, 40]
+/// count = 4
+/// spacing = [10, 0]
+///
+/// # a pattern file, next to the scene file, or an apgcode
+/// [[patterns]]
+/// seed = "gosper-gun.rle"
+/// at = [40, 5]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scene {
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub rule: Option<String>,
+    pub boundary: Option<Boundary>,
+    #[serde(default)]
+    pub patterns: Vec<Placement>,
+    /// Where pattern files named in the scene are looked for: the directory
+    /// the scene was loaded from.
+    #[serde(skip)]
+    pub dir: PathBuf,
+}
+
+/// A seed placed somewhere on the board, once or in a row of copies.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Placement {
+    /// A built-in seed by name, a pattern file or an apgcode.
+    pub seed: String,
+    pub at: Cell,
+    /// Clockwise rotation in degrees, a multiple of 90.
+    #[serde(default)]
+    pub rotate: u16,
+    pub flip: Option<Flip>,
+    /// How many copies to place [default: 1].
+    pub count: Option<usize>,
+    /// Offset between copies [default: the seed's width + 2, 0].
+    pub spacing: Option<Cell>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Flip {
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+impl Placement {
+    fn orientation(&self) -> Result<Orientation, String> {
+        if !self.rotate.is_multiple_of(90) {
+            return Err(format!(
+                "{}: rotation must be a multiple of 90, got {}",
+                self.seed, self.rotate
+            ));
+        }
+
+        Ok(Orientation {
+            quarter_turns: (self.rotate / 90 % 4) as u8,
+            flip_horizontal: matches!(self.flip, Some(Flip::Horizontal | Flip::Both)),
+            flip_vertical: matches!(self.flip, Some(Flip::Vertical | Flip::Both)),
+        })
+    }
+
+    /// The seed named, looking for pattern files in `dir` first, as the
+    /// `--pattern` option does in the working directory.
+    fn seed(&self, dir: &Path) -> Result<Box<dyn IsSeed>, String> {
+        let path = dir.join(&self.seed);
+        Ok(match Seed::from_name(&self.seed) {
+            Some(seed) if !path.exists() => Box::new(seed),
+            _ if path.exists() => Box::new(Pattern::load(path)?),
+            _ => Box::new(Pattern::load_or_decode(&self.seed)?),
+        })
+    }
+
+    fn place(&self, grid: &mut Grid, dir: &Path) -> Result<(), String> {
+        let orientation = self.orientation()?;
+        let seed = self.seed(dir)?;
+        let seed = Oriented {
+            seed: &*seed,
+            orientation,
+        };
+        let spacing = self.spacing.unwrap_or_else(|| {
+            let cells = seed.cells((0, 0));
+            let left = cells.iter().map(|cell| cell.0).min().unwrap_or_default();
+            let right = cells.iter().map(|cell| cell.0).max().unwrap_or_default();
+            (right - left + 3, 0)
+        });
+
+        for copy in 0..self.count.unwrap_or(1) as i64 {
+            let origin = (self.at.0 + copy * spacing.0, self.at.1 + copy * spacing.1);
+            grid.seed(&seed, origin);
+        }
+        Ok(())
+    }
+}
+
+impl Scene {
+    /// Reads a TOML or JSON scene, decompressing `.gz` and `.zst` files.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Scene, String> {
+        let path = path.as_ref();
+        let describe = |error: &dyn std::fmt::Display| format!("{}: {}", path.display(), error);
+
+        let mut text = String::new();
+        compress::open(path)
+            .and_then(|mut reader| reader.read_to_string(&mut text))
+            .map_err(|error| describe(&error))?;
+
+        let mut scene: Scene = match compress::format_extension(path) {
+            Some("json") => serde_json::from_str(&text).map_err(|error| describe(&error)),
+            _ => toml::from_str(&text).map_err(|error| describe(&error)),
+        }?;
+        scene.dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        Ok(scene)
+    }
+
+    /// Applies the scene's settings to the grid and places its patterns.
+    pub fn apply(&self, grid: &mut Grid) -> Result<(), String> {
+        if let Some(rule) = &self.rule {
+            grid.rule = rule.parse()?;
+        }
+        if let Some(boundary) = self.boundary {
+            grid.boundary = boundary;
+        }
+        grid.resize(
+            self.width.unwrap_or(grid.width),
+            self.height.unwrap_or(grid.height),
+        );
+
+        for placement in &self.patterns {
+            placement.place(grid, &self.dir)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_toml_scene() {
+        let scene: Scene = toml::from_str(
+            r#"
+            width = 20
+            height = 10
+            rule = "B36/S23"
+            boundary = "dead"
+
+            [[patterns]]
+            seed = "blinker"
+            at = [5, 5]
+            rotate = 90
+
+            [[patterns]]
+            seed = "block"
+            at = [0, 0]
+            "#,
+        )
+        .unwrap();
+
+        let mut grid = Grid::new(80, 40);
+        scene.apply(&mut grid).unwrap();
+
+        assert_eq!((grid.width, grid.height), (20, 10));
+        assert_eq!(grid.rule.to_string(), "B36/S23");
+        assert_eq!(grid.boundary, Boundary::Dead);
+        assert_eq!(
+            grid.cells,
+            HashSet::from([(5, 5), (5, 6), (5, 7), (0, 0), (1, 0), (0, 1), (1, 1)])
+        );
+    }
+
+    #[test]
+    fn test_json_scene() {
+        let scene: Scene = serde_json::from_str(
+            r#"{"boundary": "klein", "patterns": [{"seed": "glider", "at": [3, 3], "flip": "both"}]}"#,
+        )
+        .unwrap();
+
+        let mut grid = Grid::new(10, 10);
+        scene.apply(&mut grid).unwrap();
+
+        assert_eq!((grid.width, grid.height), (10, 10));
+        assert_eq!(grid.boundary, Boundary::Klein);
+        assert_eq!(grid.cells.len(), 5);
+        assert!(grid.cells.contains(&(4, 2)));
+    }
+
+    #[test]
+    fn test_repeated_placements() {
+        let scene: Scene = toml::from_str(
+            r#"
+            [[patterns]]
+            seed = "blinker"
+            at = [0, 0]
+            count = 3
+
+            [[patterns]]
+            seed = "xs4_33"
+            at = [0, 10]
+            count = 2
+            spacing = [0, 4]
+            "#,
+        )
+        .unwrap();
+
+        let mut grid = Grid::new(40, 40);
+        scene.apply(&mut grid).unwrap();
+
+        // blinkers 5 cells apart by default, then blocks from their apgcode
+        let blinkers = [0, 5, 10].map(|x| [(x, 0), (x + 1, 0), (x + 2, 0)]);
+        let blocks = [10, 14].map(|y| [(0, y), (1, y), (0, y + 1), (1, y + 1)]);
+        let expected: HashSet<Cell> = blinkers
+            .into_iter()
+            .flatten()
+            .chain(blocks.into_iter().flatten())
+            .collect();
+        assert_eq!(grid.cells, expected);
+    }
+
+    #[test]
+    fn test_pattern_files_next_to_scene() {
+        let dir = std::env::temp_dir().join(format!("{}-scene", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("diagonal.rle"), "x = 2, y = 2\nbo$ob!\n").unwrap();
+        std::fs::write(
+            dir.join("scene.toml"),
+            "[[patterns]]\nseed = \"diagonal.rle\"\nat = [3, 3]\n",
+        )
+        .unwrap();
+        let mut grid = Grid::new(10, 10);
+        let applied = Scene::load(dir.join("scene.toml")).and_then(|scene| scene.apply(&mut grid));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        applied.unwrap();
+        assert_eq!(grid.cells, HashSet::from([(4, 3), (3, 4)]));
+    }
+
+    #[test]
+    fn test_invalid_scenes() {
+        let mut grid = Grid::new(10, 10);
+
+        let unknown: Scene = toml::from_str("[[patterns]]\nseed = \"nope\"\nat = [0, 0]").unwrap();
+        assert!(unknown.apply(&mut grid).is_err());
+
+        let crooked: Scene =
+            toml::from_str("[[patterns]]\nseed = \"glider\"\nat = [0, 0]\nrotate = 45").unwrap();
+        assert!(crooked.apply(&mut grid).is_err());
+
+        let bad_rule: Scene = toml::from_str("rule = \"B3\"").unwrap();
+        assert!(bad_rule.apply(&mut grid).is_err());
+
+        assert!(toml::from_str::<Scene>("colour = \"red\"").is_err());
+    }
+}
@@ -0,0 +1,814 @@
+use crate::{
+    census, cli,
+    cycle::{CycleDetector, Outcome},
+    escape::EscapeTracker,
+    grid::{BoundingBox, Cell, Grid},
+    leaderboard::{Entry, Leaderboard},
+    pattern::Pattern,
+    soup::Soup,
+};
+use clap::Args as ClapArgs;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use rand::{rngs::StdRng, SeedableRng};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The side of the square soups searched, as apgsearch does.
+pub const SOUP_SIZE: usize = 16;
+const SOUP_DENSITY: f64 = 0.5;
+/// Soups still changing after this many generations are given up on.
+pub const MAX_GENERATIONS: usize = 20_000;
+/// The objects every few soups leave behind, so they aren't worth noting.
+const COMMON_OBJECTS: [&str; 13] = [
+    "Block",
+    "Blinker",
+    "Beehive",
+    "Loaf",
+    "Boat",
+    "Ship",
+    "Tub",
+    "Pond",
+    "Glider",
+    "Long boat",
+    "Toad",
+    "Beacon",
+    "Barge",
+];
+
+/// How often the dashboard is redrawn.
+const REFRESH: Duration = Duration::from_millis(250);
+/// How many of the latest notable soups the dashboard keeps.
+const RECENT_NOTABLE: usize = 100;
+/// How many seeds of soups that left each object are kept, as apgsearch does.
+const SAMPLE_SOUPS: usize = 10;
+
+/// Options for the `search` subcommand.
+#[derive(Debug, Clone, PartialEq, ClapArgs)]
+pub struct SearchArgs {
+    /// How many soups to run
+    #[arg(long, value_name = "N", default_value_t = 1000)]
+    pub soups: u64,
+
+    /// Seed of the first soup, each one after using the next [default: the one after the last soup searched]
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Soups that take at least this many generations to settle are noted as methuselahs
+    #[arg(long, value_name = "GENS", default_value_t = 5000)]
+    pub methuselah: usize,
+
+    /// Where notable soups are appended, as RLE
+    #[arg(long, value_name = "FILE", default_value = "search.rle")]
+    pub log: PathBuf,
+
+    /// Where the totals of every search so far are kept and added to, as JSON
+    #[arg(long, value_name = "FILE", default_value = "search.json")]
+    pub results: PathBuf,
+
+    /// How many soups to run at once [default: one for each core]
+    #[arg(long, value_name = "N")]
+    pub threads: Option<usize>,
+}
+
+/// Options for the `submit` subcommand.
+#[derive(Debug, Clone, PartialEq, ClapArgs)]
+pub struct SubmitArgs {
+    /// The totals kept by `search`
+    #[arg(long, value_name = "FILE", default_value = "search.json")]
+    pub results: PathBuf,
+
+    /// Where to write the haul [default: standard output]
+    #[arg(long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+/// How a soup turned out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoupResult {
+    pub seed: u64,
+    /// The generation it settled in, or `None` if it hadn't after
+    /// [`MAX_GENERATIONS`].
+    pub lifespan: Option<usize>,
+    /// How many cells were left on the board, escaped spaceships not counted.
+    pub population: usize,
+    /// What it left behind, escaped spaceships included, most common first.
+    pub objects: Vec<(&'static str, usize)>,
+}
+
+impl SoupResult {
+    /// Why the soup is worth a look, if it is: living long, never settling,
+    /// or leaving something uncommon behind.
+    pub fn notable(&self, methuselah: usize) -> Option<String> {
+        let rare: Vec<&str> = self
+            .objects
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| !COMMON_OBJECTS.contains(name))
+            .collect();
+        match self.lifespan {
+            None => Some(format!("unsettled after {} gens", MAX_GENERATIONS)),
+            Some(lifespan) if lifespan >= methuselah => {
+                Some(format!("methuselah, settled at gen {}", lifespan))
+            }
+            _ if !rare.is_empty() => Some(format!("left {}", rare.join(", "))),
+            _ => None,
+        }
+    }
+}
+
+/// The random soup a seed makes.
+pub fn soup(seed: u64) -> Soup {
+    Soup::random(
+        &mut StdRng::seed_from_u64(seed),
+        SOUP_SIZE,
+        SOUP_SIZE,
+        SOUP_DENSITY,
+    )
+}
+
+/// Runs soups to the end, keeping what it needs from one to the next.
+#[derive(Debug)]
+pub struct Searcher {
+    catalog: HashMap<Vec<Cell>, &'static str>,
+    cycles: CycleDetector,
+    /// Takes gliders and other spaceships off the board as they fly away,
+    /// so the rest can be seen to settle.
+    escapes: EscapeTracker,
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Searcher {
+            catalog: census::catalog(),
+            cycles: CycleDetector::default(),
+            escapes: EscapeTracker::new(true),
+        }
+    }
+}
+
+impl Searcher {
+    /// Runs the soup a seed makes on an open board until it settles.
+    pub fn run(&mut self, seed: u64) -> SoupResult {
+        let mut grid = Grid::new(SOUP_SIZE, SOUP_SIZE);
+        grid.seed(soup(seed), (0, 0));
+
+        let mut lifespan = None;
+        while grid.generation < MAX_GENERATIONS {
+            grid.tick();
+            self.escapes.check(&mut grid);
+            lifespan = match self.cycles.check(&grid) {
+                Some(Outcome::DiedOut { generation }) => Some(generation),
+                Some(Outcome::Stabilized { generation, .. }) => Some(generation),
+                None => continue,
+            };
+            break;
+        }
+
+        let mut objects: HashMap<&'static str, usize> = census::count(&grid.cells, &self.catalog)
+            .into_iter()
+            .collect();
+        for (name, count) in self.escapes.counts() {
+            *objects.entry(name).or_default() += count;
+        }
+        let mut objects: Vec<(&'static str, usize)> = objects.into_iter().collect();
+        objects.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        SoupResult {
+            seed,
+            lifespan,
+            population: grid.cells.len(),
+            objects,
+        }
+    }
+}
+
+/// Soups running on threads of their own, each thread taking the next seed
+/// when it finishes a soup, until they're all taken or the search is stopped.
+/// Results come back in the order the soups finish.
+#[derive(Debug)]
+pub struct Workers {
+    results: Receiver<SoupResult>,
+    stop: Arc<AtomicBool>,
+}
+
+impl Workers {
+    pub fn spawn(seeds: Range<u64>, threads: usize) -> Workers {
+        let (sender, results) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let next = Arc::new(AtomicU64::new(seeds.start));
+
+        for _ in 0..threads.max(1) {
+            let (sender, stop, next) = (sender.clone(), stop.clone(), next.clone());
+            let end = seeds.end;
+            thread::spawn(move || {
+                let mut searcher = Searcher::default();
+                while !stop.load(Ordering::Relaxed) {
+                    let Ok(seed) =
+                        next.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |seed| {
+                            (seed < end).then_some(seed + 1)
+                        })
+                    else {
+                        break;
+                    };
+                    if sender.send(searcher.run(seed)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Workers { results, stop }
+    }
+
+    /// Waits up to `timeout` for the next soup to finish. Once every soup is
+    /// done, or every one started since stopping is, returns
+    /// [`RecvTimeoutError::Disconnected`].
+    pub fn next_timeout(&self, timeout: Duration) -> Result<SoupResult, RecvTimeoutError> {
+        self.results.recv_timeout(timeout)
+    }
+
+    /// Lets the soups already started finish, without starting any more.
+    /// Since seeds are taken in order, the ones run are still all the seeds
+    /// up to the last one.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    pub fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+}
+
+impl Iterator for Workers {
+    type Item = SoupResult;
+
+    fn next(&mut self) -> Option<SoupResult> {
+        self.results.recv().ok()
+    }
+}
+
+/// The objects found across all the soups searched, kept from one search to
+/// the next.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tally {
+    pub soups: u64,
+    pub notable: u64,
+    /// The seed after the last soup searched, where the next search starts.
+    pub next_seed: u64,
+    objects: BTreeMap<String, usize>,
+    /// The seeds of the first few soups that left each object.
+    samples: BTreeMap<String, Vec<u64>>,
+}
+
+impl Tally {
+    /// Reads the tally kept at `path`, or starts a new one if there's none.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Tally> {
+        match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|error| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid search results {}: {}",
+                        path.as_ref().display(),
+                        error
+                    ),
+                )
+            }),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Tally::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let text = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, text + "\n")
+    }
+
+    pub fn add(&mut self, result: &SoupResult, notable: bool) {
+        self.soups += 1;
+        self.notable += notable as u64;
+        self.next_seed = self.next_seed.max(result.seed + 1);
+        for (name, count) in &result.objects {
+            *self.objects.entry(name.to_string()).or_default() += count;
+            let samples = self.samples.entry(name.to_string()).or_default();
+            if samples.len() < SAMPLE_SOUPS {
+                samples.push(result.seed);
+            }
+        }
+    }
+
+    /// How many of each object were found, most common first.
+    pub fn objects(&self) -> Vec<(&str, usize)> {
+        let mut objects: Vec<(&str, usize)> = self
+            .objects
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        objects.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        objects
+    }
+}
+
+/// Writes the tally as a Catagolue haul: the census of every object by its
+/// apgcode, and the seeds of a few soups that left each one. Objects with no
+/// apgcode, like the ones counted as "Other", are left out.
+///
+/// Catagolue checks soups by making them again from a root string hashed
+/// with SHA-256, and these soups are made by seeding a random number
+/// generator instead, so the haul is for comparing against its census and
+/// feeding tools that read the format, not for uploading.
+pub fn write_haul<W: Write>(tally: &Tally, mut output: W) -> std::io::Result<()> {
+    let census: Vec<(&str, usize)> = tally
+        .objects()
+        .into_iter()
+        .filter_map(|(name, count)| census::apgcode(name).map(|code| (code, count)))
+        .collect();
+
+    writeln!(
+        output,
+        "@VERSION cli-game-of-life {}",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    writeln!(output, "@RULE b3s23")?;
+    writeln!(output, "@SYMMETRY C1")?;
+    writeln!(output, "@NUM_SOUPS {}", tally.soups)?;
+    writeln!(
+        output,
+        "@NUM_OBJECTS {}",
+        census.iter().map(|(_, count)| count).sum::<usize>()
+    )?;
+    writeln!(output)?;
+    writeln!(output, "@CENSUS TABLE")?;
+    for (code, count) in &census {
+        writeln!(output, "{} {}", code, count)?;
+    }
+    writeln!(output)?;
+    writeln!(output, "@SAMPLE_SOUPIDS")?;
+    for (name, samples) in &tally.samples {
+        if let Some(code) = census::apgcode(name) {
+            let seeds: Vec<String> = samples.iter().map(|seed| seed.to_string()).collect();
+            writeln!(output, "{} {}", code, seeds.join(" "))?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the totals kept by earlier searches as a haul, to a file or the output.
+pub fn submit<W: Write>(args: &SubmitArgs, output: W) -> std::io::Result<()> {
+    if !args.results.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("no search results in {}", args.results.display()),
+        ));
+    }
+    let tally = Tally::load(&args.results)?;
+    match &args.output {
+        Some(path) => write_haul(&tally, std::io::BufWriter::new(File::create(path)?)),
+        None => write_haul(&tally, output),
+    }
+}
+
+/// Writes a notable soup as RLE, named after its seed and what it did.
+fn log_soup<W: Write>(mut log: W, seed: u64, reason: &str) -> std::io::Result<()> {
+    let bounds = BoundingBox {
+        x: 0,
+        y: 0,
+        width: SOUP_SIZE,
+        height: SOUP_SIZE,
+    };
+    let mut grid = Grid::new(SOUP_SIZE, SOUP_SIZE);
+    grid.seed(soup(seed), (0, 0));
+    let mut pattern = Pattern::from_cells(&grid.cells, bounds);
+    pattern.name = Some(format!("Soup {}: {}", seed, reason));
+    pattern.rule = Some(grid.rule.to_string());
+    pattern.write_rle(&mut log)?;
+    log.flush()
+}
+
+/// A search under way, gathering what the workers find into the tally kept
+/// between searches and the log of notable soups.
+#[derive(Debug)]
+struct Session<'a> {
+    args: &'a SearchArgs,
+    log: File,
+    tally: Tally,
+    leaderboard: Leaderboard,
+    leaderboard_path: PathBuf,
+    threads: usize,
+    seeds: Range<u64>,
+    /// The soups and notable ones found this search.
+    soups: u64,
+    notable: u64,
+    /// The latest notable soups, oldest first.
+    recent: Vec<String>,
+    start: Instant,
+}
+
+impl<'a> Session<'a> {
+    fn start(args: &'a SearchArgs) -> std::io::Result<(Session<'a>, Workers)> {
+        let tally = Tally::load(&args.results)?;
+        let leaderboard_path = Leaderboard::path()?;
+        let leaderboard = Leaderboard::load(&leaderboard_path)?;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&args.log)?;
+        let threads = args
+            .threads
+            .unwrap_or_else(|| thread::available_parallelism().map_or(1, |threads| threads.get()));
+        let first = args.seed.unwrap_or(tally.next_seed);
+        let seeds = first..first.saturating_add(args.soups);
+
+        let workers = Workers::spawn(seeds.clone(), threads);
+        let session = Session {
+            args,
+            log,
+            tally,
+            leaderboard,
+            leaderboard_path,
+            threads,
+            seeds,
+            soups: 0,
+            notable: 0,
+            recent: Vec::new(),
+            start: Instant::now(),
+        };
+        Ok((session, workers))
+    }
+
+    /// Adds a finished soup, logging it if it's notable and returning why.
+    fn record(&mut self, result: &SoupResult) -> std::io::Result<Option<String>> {
+        let reason = result.notable(self.args.methuselah);
+        self.soups += 1;
+        self.tally.add(result, reason.is_some());
+        if let Some(lifespan) = result.lifespan {
+            self.leaderboard.offer(Entry {
+                seed: result.seed,
+                lifespan,
+                population: result.population,
+            });
+        }
+        let Some(reason) = reason else {
+            return Ok(None);
+        };
+
+        log_soup(&mut self.log, result.seed, &reason)?;
+        let line = format!("Soup {}: {}", result.seed, reason);
+        self.notable += 1;
+        self.recent.push(line.clone());
+        if self.recent.len() > RECENT_NOTABLE {
+            self.recent.remove(0);
+        }
+        Ok(Some(line))
+    }
+
+    /// How many threads are running soups, in words.
+    fn threads(&self) -> String {
+        match self.threads {
+            1 => String::from("1 thread"),
+            threads => format!("{} threads", threads),
+        }
+    }
+
+    /// The top of the leaderboard, in words.
+    fn longest(&self) -> Option<String> {
+        self.leaderboard.entries().first().map(|entry| {
+            format!(
+                "soup {}, settled at gen {} with {} cells",
+                entry.seed, entry.lifespan, entry.population
+            )
+        })
+    }
+
+    fn speed(&self) -> f64 {
+        self.soups as f64 / self.start.elapsed().as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Keeps the tally for the next search and writes how it went, with how
+    /// many of each object every search so far has found.
+    fn finish<W: Write>(self, mut output: W) -> std::io::Result<()> {
+        self.tally.save(&self.args.results)?;
+        self.leaderboard.save(&self.leaderboard_path)?;
+        writeln!(
+            output,
+            "{} soups in {:.1}s ({:.0} soups/s on {}), {} notable, logged to {}",
+            self.soups,
+            self.start.elapsed().as_secs_f64(),
+            self.speed(),
+            self.threads(),
+            self.notable,
+            self.args.log.display()
+        )?;
+        writeln!(
+            output,
+            "{} soups searched in all, kept in {}",
+            self.tally.soups,
+            self.args.results.display()
+        )?;
+        if let Some(longest) = self.longest() {
+            writeln!(
+                output,
+                "Longest-lived: {}, leaderboard kept in {}",
+                longest,
+                self.leaderboard_path.display()
+            )?;
+        }
+        writeln!(output, "{:<24} {:>10}", "Object", "Count")?;
+        for (name, count) in self.tally.objects() {
+            writeln!(output, "{:<24} {:>10}", name, count)?;
+        }
+        Ok(())
+    }
+
+    fn draw(&self, frame: &mut Frame, stopping: bool) {
+        let [summary, found] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(9), Constraint::Min(0)])
+            .split(frame.size())
+        else {
+            return;
+        };
+        let [objects, notable] = *Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(found)
+        else {
+            return;
+        };
+
+        let lines = [
+            format!(
+                "Soups    {} this search, {} in all",
+                self.soups, self.tally.soups
+            ),
+            format!("Speed    {:.0} soups/s on {}", self.speed(), self.threads()),
+            format!(
+                "Notable  {} this search, {} in all, logged to {}",
+                self.notable,
+                self.tally.notable,
+                self.args.log.display()
+            ),
+            format!(
+                "Seeds    {} to {}",
+                self.seeds.start,
+                self.seeds.end.saturating_sub(1)
+            ),
+            format!(
+                "Longest  {}",
+                self.longest()
+                    .unwrap_or_else(|| String::from("none settled yet"))
+            ),
+            String::new(),
+            String::from(match stopping {
+                true => "Stopping once the soups running settle...",
+                false => "Q to stop",
+            }),
+        ];
+        frame.render_widget(
+            Paragraph::new(lines.join("\n")).block(
+                Block::default()
+                    .title(" Soup search ")
+                    .borders(Borders::ALL),
+            ),
+            summary,
+        );
+
+        let rows = inner_height(objects);
+        let table: Vec<String> = self
+            .tally
+            .objects()
+            .into_iter()
+            .take(rows)
+            .map(|(name, count)| format!("{:<20} {:>10}", name, count))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(table.join("\n")).block(
+                Block::default()
+                    .title(" Objects found ")
+                    .borders(Borders::ALL),
+            ),
+            objects,
+        );
+
+        let rows = inner_height(notable);
+        let skip = self.recent.len().saturating_sub(rows);
+        frame.render_widget(
+            Paragraph::new(self.recent[skip..].join("\n")).block(
+                Block::default()
+                    .title(" Notable soups ")
+                    .borders(Borders::ALL),
+            ),
+            notable,
+        );
+    }
+}
+
+/// How many lines fit inside a bordered block.
+fn inner_height(area: Rect) -> usize {
+    area.height.saturating_sub(2) as usize
+}
+
+/// Runs the soups on every core, noting the notable ones as they're found,
+/// then writes how many of each object they and every search before left.
+pub fn run<W: Write>(args: &SearchArgs, mut output: W) -> std::io::Result<()> {
+    let (mut session, workers) = Session::start(args)?;
+    for result in workers {
+        if let Some(line) = session.record(&result)? {
+            writeln!(output, "{}", line)?;
+        }
+    }
+    session.finish(output)
+}
+
+/// Runs the search like [`run`], showing how fast it's going and what it's
+/// found so far as it goes. Q, Esc or Ctrl+C stop it early.
+pub fn dashboard(args: &SearchArgs) -> std::io::Result<()> {
+    let (mut session, workers) = Session::start(args)?;
+    let mut terminal = cli::setup(false)?;
+    let watched = watch(&mut session, &workers, &mut terminal);
+    cli::teardown(false)?;
+    watched?;
+    session.finish(std::io::stdout().lock())
+}
+
+fn watch<B: ratatui::backend::Backend>(
+    session: &mut Session,
+    workers: &Workers,
+    terminal: &mut ratatui::Terminal<B>,
+) -> std::io::Result<()> {
+    loop {
+        terminal.draw(|frame| session.draw(frame, workers.stopped()))?;
+
+        let deadline = Instant::now() + REFRESH;
+        loop {
+            match workers.next_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(result) => {
+                    session.record(&result)?;
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return Ok(()),
+            }
+        }
+
+        while event::poll(Duration::ZERO)? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_c || matches!(key.code, KeyCode::Char('q' | 'Q') | KeyCode::Esc) {
+                    workers.stop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_soups_are_reproducible() {
+        let mut searcher = Searcher::default();
+        let first = searcher.run(3);
+        let again = Searcher::default().run(3);
+
+        assert_eq!(first, again);
+        assert!(first.lifespan.is_some());
+        // every soup is run from its own start
+        assert_eq!(searcher.run(3), first);
+    }
+
+    #[test]
+    fn test_notable() {
+        let result = |lifespan, objects| SoupResult {
+            seed: 0,
+            lifespan,
+            population: 0,
+            objects,
+        };
+
+        assert_eq!(result(Some(200), vec![("Block", 3)]).notable(1000), None);
+        assert_eq!(
+            result(Some(1500), vec![("Block", 3)]).notable(1000),
+            Some(String::from("methuselah, settled at gen 1500"))
+        );
+        assert_eq!(
+            result(Some(200), vec![("Block", 3), ("Pulsar", 1)]).notable(1000),
+            Some(String::from("left Pulsar"))
+        );
+        assert!(result(None, Vec::new()).notable(1000).is_some());
+    }
+
+    #[test]
+    fn test_log_soup() {
+        let mut bytes = Vec::new();
+        log_soup(&mut bytes, 3, "left Pulsar").unwrap();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("#N Soup 3: left Pulsar\nx = 16, y = 16, rule = B3/S23\n"));
+
+        let pattern = Pattern::parse_rle(&text).unwrap();
+        let mut grid = Grid::new(SOUP_SIZE, SOUP_SIZE);
+        grid.seed(soup(3), (0, 0));
+        assert_eq!(pattern.cells.len(), grid.cells.len());
+    }
+
+    #[test]
+    fn test_tally() {
+        let mut tally = Tally::default();
+        let result = SoupResult {
+            seed: 0,
+            lifespan: Some(100),
+            population: 20,
+            objects: vec![("Block", 2), ("Blinker", 1)],
+        };
+        tally.add(&result, false);
+        tally.add(&result, true);
+
+        assert_eq!((tally.soups, tally.notable, tally.next_seed), (2, 1, 1));
+        assert_eq!(tally.objects(), vec![("Block", 4), ("Blinker", 2)]);
+    }
+
+    #[test]
+    fn test_tally_save_and_load() {
+        let path = std::env::temp_dir().join(format!("{}-search.json", std::process::id()));
+        assert_eq!(Tally::load(&path).unwrap(), Tally::default());
+
+        let mut tally = Tally::default();
+        let result = SoupResult {
+            seed: 41,
+            lifespan: Some(100),
+            population: 20,
+            objects: vec![("Block", 2), ("Glider", 1)],
+        };
+        tally.add(&result, true);
+        tally.save(&path).unwrap();
+        let loaded = Tally::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, tally);
+        assert_eq!(loaded.next_seed, 42);
+    }
+
+    #[test]
+    fn test_write_haul() {
+        let mut tally = Tally::default();
+        for seed in [3, 5] {
+            tally.add(
+                &SoupResult {
+                    seed,
+                    lifespan: Some(100),
+                    population: 20,
+                    objects: vec![("Block", 2), ("Glider", 1), ("Other", 1)],
+                },
+                false,
+            );
+        }
+
+        let mut bytes = Vec::new();
+        write_haul(&tally, &mut bytes).unwrap();
+        let haul = String::from_utf8(bytes).unwrap();
+        assert!(haul.starts_with("@VERSION cli-game-of-life "));
+        assert!(haul.ends_with(concat!(
+            "@RULE b3s23\n",
+            "@SYMMETRY C1\n",
+            "@NUM_SOUPS 2\n",
+            "@NUM_OBJECTS 6\n",
+            "\n",
+            "@CENSUS TABLE\n",
+            "xs4_33 4\n",
+            "xq4_153 2\n",
+            "\n",
+            "@SAMPLE_SOUPIDS\n",
+            "xs4_33 3 5\n",
+            "xq4_153 3 5\n",
+        )));
+    }
+
+    #[test]
+    fn test_workers_run_every_seed_once() {
+        let mut results: Vec<SoupResult> = Workers::spawn(10..16, 3).collect();
+        results.sort_by_key(|result| result.seed);
+
+        let seeds: Vec<u64> = results.iter().map(|result| result.seed).collect();
+        assert_eq!(seeds, (10..16).collect::<Vec<u64>>());
+        assert_eq!(results[2], Searcher::default().run(12));
+    }
+}
@@ -1,19 +1,80 @@
-use crate::grid::Cell;
+use crate::{
+    grid::Cell,
+    wireworld::{Circuit, Electron},
+};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
 /// A trait for seeding a grid with a pattern of cells.
 pub trait IsSeed: std::fmt::Debug {
     fn cells(&self, origin: Cell) -> Vec<Cell>;
+
+    /// The Wireworld electrons on some of the seed's cells. In Wireworld,
+    /// the cells of seeds without any are laid as bare wire.
+    fn electrons(&self, _origin: Cell) -> Vec<(Cell, Electron)> {
+        Vec::new()
+    }
+}
+
+impl<S: IsSeed + ?Sized> IsSeed for &S {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        (**self).cells(origin)
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        (**self).electrons(origin)
+    }
 }
 
 /// All the possible seeds.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Seed {
     Cell(Cell),
     Still(Still),
     Oscillator(Oscillator),
     Spaceship(Spaceship),
+    Methuselah(Methuselah),
+    Gun(Gun),
+    Eater(Eater),
+    Circuit(Circuit),
 }
 
+/// Every built-in seed, kept together by category in the order they're
+/// cycled through.
+pub const SEEDS: [Seed; 26] = [
+    Seed::Cell((0, 0)),
+    // Still lifes are patterns that do not change from one generation to the next.
+    Seed::Still(Still::Block),
+    Seed::Still(Still::Beehive),
+    Seed::Still(Still::Loaf),
+    Seed::Still(Still::Boat),
+    Seed::Still(Still::Tub),
+    // Oscillators are patterns that return to their original configuration
+    Seed::Oscillator(Oscillator::Blinker),
+    Seed::Oscillator(Oscillator::Toad),
+    Seed::Oscillator(Oscillator::Beacon),
+    Seed::Oscillator(Oscillator::Pulsar),
+    Seed::Oscillator(Oscillator::PentaDecathlon),
+    // Spaceships are patterns that translate themselves across the grid.
+    Seed::Spaceship(Spaceship::Glider),
+    Seed::Spaceship(Spaceship::LwSpaceship),
+    Seed::Spaceship(Spaceship::MwSpaceship),
+    Seed::Spaceship(Spaceship::HwSpaceship),
+    Seed::Methuselah(Methuselah::RPentomino),
+    Seed::Methuselah(Methuselah::Acorn),
+    Seed::Methuselah(Methuselah::Diehard),
+    Seed::Gun(Gun::GosperGliderGun),
+    Seed::Gun(Gun::SimkinGliderGun),
+    Seed::Eater(Eater::Eater1),
+    Seed::Eater(Eater::Eater2),
+    // Wireworld circuits, laid as wire with their electrons.
+    Seed::Circuit(Circuit::Clock),
+    Seed::Circuit(Circuit::Diode),
+    Seed::Circuit(Circuit::OrGate),
+    Seed::Circuit(Circuit::XorGate),
+];
+
 impl IsSeed for Seed {
     fn cells(&self, origin: Cell) -> Vec<Cell> {
         match self {
@@ -21,12 +82,287 @@ impl IsSeed for Seed {
             Seed::Still(still) => still.cells(origin),
             Seed::Oscillator(oscillator) => oscillator.cells(origin),
             Seed::Spaceship(spaceship) => spaceship.cells(origin),
+            Seed::Methuselah(methuselah) => methuselah.cells(origin),
+            Seed::Gun(gun) => gun.cells(origin),
+            Seed::Eater(eater) => eater.cells(origin),
+            Seed::Circuit(circuit) => circuit.cells(origin),
+        }
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        match self {
+            Seed::Circuit(circuit) => circuit.electrons(origin),
+            _ => Vec::new(),
         }
     }
 }
 
+impl Seed {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Seed::Cell(_) => "Cell",
+            Seed::Still(Still::Block) => "Block",
+            Seed::Still(Still::Beehive) => "Beehive",
+            Seed::Still(Still::Loaf) => "Loaf",
+            Seed::Still(Still::Boat) => "Boat",
+            Seed::Still(Still::Tub) => "Tub",
+            Seed::Oscillator(Oscillator::Blinker) => "Blinker",
+            Seed::Oscillator(Oscillator::Toad) => "Toad",
+            Seed::Oscillator(Oscillator::Beacon) => "Beacon",
+            Seed::Oscillator(Oscillator::Pulsar) => "Pulsar",
+            Seed::Oscillator(Oscillator::PentaDecathlon) => "Penta-decathlon",
+            Seed::Spaceship(Spaceship::Glider) => "Glider",
+            Seed::Spaceship(Spaceship::LwSpaceship) => "LWSS",
+            Seed::Spaceship(Spaceship::MwSpaceship) => "MWSS",
+            Seed::Spaceship(Spaceship::HwSpaceship) => "HWSS",
+            Seed::Methuselah(Methuselah::RPentomino) => "R-pentomino",
+            Seed::Methuselah(Methuselah::Acorn) => "Acorn",
+            Seed::Methuselah(Methuselah::Diehard) => "Diehard",
+            Seed::Gun(Gun::GosperGliderGun) => "Gosper glider gun",
+            Seed::Gun(Gun::SimkinGliderGun) => "Simkin glider gun",
+            Seed::Eater(Eater::Eater1) => "Eater 1",
+            Seed::Eater(Eater::Eater2) => "Eater 2",
+            Seed::Circuit(Circuit::Clock) => "Clock",
+            Seed::Circuit(Circuit::Diode) => "Diode",
+            Seed::Circuit(Circuit::OrGate) => "OR gate",
+            Seed::Circuit(Circuit::XorGate) => "XOR gate",
+        }
+    }
+
+    /// The kind of pattern it is, as the seed browser groups them.
+    pub fn category(&self) -> &'static str {
+        match self {
+            Seed::Cell(_) => "Cells",
+            Seed::Still(_) => "Still lifes",
+            Seed::Oscillator(_) => "Oscillators",
+            Seed::Spaceship(_) => "Spaceships",
+            Seed::Methuselah(_) => "Methuselahs",
+            Seed::Gun(_) => "Guns",
+            Seed::Eater(_) => "Eaters",
+            Seed::Circuit(_) => "Circuits",
+        }
+    }
+
+    /// What's known about the seed, for the seed browser.
+    pub fn about(&self) -> About {
+        let about = |description| About {
+            description,
+            ..Default::default()
+        };
+        let found = |discoverer, year, description| About {
+            discoverer: Some(discoverer),
+            year: Some(year),
+            description,
+            ..Default::default()
+        };
+        match self {
+            Seed::Cell(_) => {
+                about("A single cell, which dies alone but sparks an electron on Wireworld wire.")
+            }
+            Seed::Still(Still::Block) => {
+                about("The most common still life, four cells each with three neighbors.")
+            }
+            Seed::Still(Still::Beehive) => about("The second most common still life."),
+            Seed::Still(Still::Loaf) => about("A common still life, often left behind by soups."),
+            Seed::Still(Still::Boat) => about("The only still life with five cells."),
+            Seed::Still(Still::Tub) => {
+                about("Four cells around an empty one, with too many neighbors to be born.")
+            }
+            Seed::Oscillator(Oscillator::Blinker) => About {
+                period: Some(2),
+                ..about("The smallest and most common oscillator, a row of three turning.")
+            },
+            Seed::Oscillator(Oscillator::Toad) => About {
+                period: Some(2),
+                ..found(
+                    "Simon Norton",
+                    1970,
+                    "Two offset rows of three, breathing in and out.",
+                )
+            },
+            Seed::Oscillator(Oscillator::Beacon) => About {
+                period: Some(2),
+                ..found(
+                    "John Conway",
+                    1970,
+                    "Two blocks touching at a corner, where two cells blink.",
+                )
+            },
+            Seed::Oscillator(Oscillator::Pulsar) => About {
+                period: Some(3),
+                ..found("John Conway", 1970, "The most common period 3 oscillator.")
+            },
+            Seed::Oscillator(Oscillator::PentaDecathlon) => About {
+                period: Some(15),
+                ..found(
+                    "John Conway",
+                    1970,
+                    "Grows from a row of ten cells, coming back to it every 15 generations.",
+                )
+            },
+            Seed::Spaceship(Spaceship::Glider) => About {
+                period: Some(4),
+                speed: Some("c/4"),
+                ..found(
+                    "Richard K. Guy",
+                    1969,
+                    "The smallest and most common spaceship, and the first found.",
+                )
+            },
+            Seed::Spaceship(Spaceship::LwSpaceship) => About {
+                period: Some(4),
+                speed: Some("c/2"),
+                ..found(
+                    "John Conway",
+                    1970,
+                    "The lightweight spaceship, the smallest that moves straight across.",
+                )
+            },
+            Seed::Spaceship(Spaceship::MwSpaceship) => About {
+                period: Some(4),
+                speed: Some("c/2"),
+                ..found(
+                    "John Conway",
+                    1970,
+                    "The middleweight spaceship, a cell longer than the lightweight one.",
+                )
+            },
+            Seed::Spaceship(Spaceship::HwSpaceship) => About {
+                period: Some(4),
+                speed: Some("c/2"),
+                ..found(
+                    "John Conway",
+                    1970,
+                    "The heavyweight spaceship, the longest of the three.",
+                )
+            },
+            Seed::Methuselah(Methuselah::RPentomino) => About {
+                discoverer: Some("John Conway"),
+                ..about(
+                    "Five cells that take 1103 generations to settle, throwing off six gliders.",
+                )
+            },
+            Seed::Methuselah(Methuselah::Acorn) => found(
+                "Charles Corderman",
+                1971,
+                "Seven cells that take 5206 generations to settle into 633.",
+            ),
+            Seed::Methuselah(Methuselah::Diehard) => {
+                about("Seven cells that vanish completely after 130 generations.")
+            }
+            Seed::Gun(Gun::GosperGliderGun) => About {
+                period: Some(30),
+                ..found(
+                    "Bill Gosper",
+                    1970,
+                    "The first gun found, which showed that patterns can grow forever.",
+                )
+            },
+            Seed::Gun(Gun::SimkinGliderGun) => About {
+                period: Some(120),
+                ..found(
+                    "Michael Simkin",
+                    2015,
+                    "A small gun, firing a glider every 120 generations.",
+                )
+            },
+            Seed::Eater(Eater::Eater1) => found(
+                "Bill Gosper",
+                1971,
+                "The fishhook, which eats gliders that hit it right and repairs itself.",
+            ),
+            Seed::Eater(Eater::Eater2) => {
+                about("Another eater, which eats gliders and repairs itself.")
+            }
+            Seed::Circuit(Circuit::Clock) => About {
+                period: Some(8),
+                ..about("An electron going round a loop, sending one down the wire every 8 ticks.")
+            },
+            Seed::Circuit(Circuit::Diode) => about("Lets electrons through from the left only."),
+            Seed::Circuit(Circuit::OrGate) => {
+                about("Two inputs through diodes joining into one output.")
+            }
+            Seed::Circuit(Circuit::XorGate) => {
+                about("Outputs an electron when exactly one input gets one at the same time.")
+            }
+        }
+    }
+
+    /// Looks a seed up by name, ignoring case, spaces, dashes and underscores.
+    pub fn from_name(name: &str) -> Option<Seed> {
+        let name: String = name
+            .chars()
+            .filter(|ch| !matches!(ch, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_lowercase();
+
+        Some(match name.as_str() {
+            "cell" => Seed::Cell((0, 0)),
+            "block" => Seed::Still(Still::Block),
+            "beehive" => Seed::Still(Still::Beehive),
+            "loaf" => Seed::Still(Still::Loaf),
+            "boat" => Seed::Still(Still::Boat),
+            "tub" => Seed::Still(Still::Tub),
+            "blinker" => Seed::Oscillator(Oscillator::Blinker),
+            "toad" => Seed::Oscillator(Oscillator::Toad),
+            "beacon" => Seed::Oscillator(Oscillator::Beacon),
+            "pulsar" => Seed::Oscillator(Oscillator::Pulsar),
+            "pentadecathlon" => Seed::Oscillator(Oscillator::PentaDecathlon),
+            "glider" => Seed::Spaceship(Spaceship::Glider),
+            "lwss" => Seed::Spaceship(Spaceship::LwSpaceship),
+            "mwss" => Seed::Spaceship(Spaceship::MwSpaceship),
+            "hwss" => Seed::Spaceship(Spaceship::HwSpaceship),
+            "rpentomino" => Seed::Methuselah(Methuselah::RPentomino),
+            "acorn" => Seed::Methuselah(Methuselah::Acorn),
+            "diehard" => Seed::Methuselah(Methuselah::Diehard),
+            "gosperglidergun" => Seed::Gun(Gun::GosperGliderGun),
+            "simkinglidergun" => Seed::Gun(Gun::SimkinGliderGun),
+            "eater1" => Seed::Eater(Eater::Eater1),
+            "eater2" => Seed::Eater(Eater::Eater2),
+            "clock" => Seed::Circuit(Circuit::Clock),
+            "diode" => Seed::Circuit(Circuit::Diode),
+            "orgate" => Seed::Circuit(Circuit::OrGate),
+            "xorgate" => Seed::Circuit(Circuit::XorGate),
+            _ => return None,
+        })
+    }
+}
+
+/// What's known about a built-in seed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct About {
+    /// Who found it, where they're known.
+    pub discoverer: Option<&'static str>,
+    pub year: Option<u16>,
+    /// How many generations it takes to come back as it was.
+    pub period: Option<usize>,
+    /// How fast a spaceship moves, as a fraction of the speed of light.
+    pub speed: Option<&'static str>,
+    pub description: &'static str,
+}
+
+impl About {
+    /// Who found it and when, its period and speed, as much as is known, as
+    /// in `found by Richard K. Guy in 1969, period 4, moves at c/4`.
+    pub fn summary(&self) -> String {
+        let found = match (self.discoverer, self.year) {
+            (Some(discoverer), Some(year)) => Some(format!("found by {} in {}", discoverer, year)),
+            (Some(discoverer), None) => Some(format!("found by {}", discoverer)),
+            (None, Some(year)) => Some(format!("found in {}", year)),
+            (None, None) => None,
+        };
+        let period = self.period.map(|period| format!("period {}", period));
+        let speed = self.speed.map(|speed| format!("moves at {}", speed));
+        [found, period, speed]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
 /// Still lifes are patterns that do not change from one generation to the next.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Still {
     Block,
     Beehive,
@@ -37,7 +373,7 @@ pub enum Still {
 
 /// Oscillators are patterns that return to their original configuration
 /// after a finite number of generations.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Oscillator {
     Blinker,
     Toad,
@@ -47,7 +383,7 @@ pub enum Oscillator {
 }
 
 /// Spaceships are patterns that translate themselves across the grid.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum Spaceship {
     Glider,
     LwSpaceship,
@@ -55,6 +391,274 @@ pub enum Spaceship {
     HwSpaceship,
 }
 
+/// Methuselahs are small patterns that take a long time to settle.
+#[derive(Debug, Clone, Copy)]
+pub enum Methuselah {
+    /// Settles after 1103 generations, throwing off six gliders.
+    RPentomino,
+    /// Settles after 5206 generations, with 633 cells.
+    Acorn,
+    /// Dies out completely after 130 generations.
+    Diehard,
+}
+
+/// Guns are patterns that keep shooting out spaceships.
+#[derive(Debug, Clone, Copy)]
+pub enum Gun {
+    /// A glider every 30 generations, the first gun found.
+    GosperGliderGun,
+    /// A glider every 120 generations, from a gun found in 2015.
+    SimkinGliderGun,
+}
+
+/// Eaters are still lifes that destroy what runs into them, then repair
+/// themselves.
+#[derive(Debug, Clone, Copy)]
+pub enum Eater {
+    /// The fishhook, the most common eater.
+    Eater1,
+    Eater2,
+}
+
+/// A rotation and reflection applied to a seed around its origin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Orientation {
+    /// Clockwise quarter turns, applied after flipping.
+    pub quarter_turns: u8,
+    /// Mirrors left and right.
+    pub flip_horizontal: bool,
+    /// Mirrors top and bottom.
+    pub flip_vertical: bool,
+}
+
+impl Orientation {
+    pub fn apply(&self, (x, y): Cell) -> Cell {
+        let x = if self.flip_horizontal { -x } else { x };
+        let y = if self.flip_vertical { -y } else { y };
+
+        match self.quarter_turns % 4 {
+            0 => (x, y),
+            1 => (-y, x),
+            2 => (-x, -y),
+            _ => (y, -x),
+        }
+    }
+
+    /// This orientation followed by `quarter_turns` more clockwise turns.
+    pub fn turned(self, quarter_turns: u8) -> Orientation {
+        let upright = self.normalized();
+        Orientation {
+            quarter_turns: (upright.quarter_turns + quarter_turns % 4) % 4,
+            ..upright
+        }
+    }
+
+    /// This orientation followed by mirroring left and right, or top and
+    /// bottom when not `horizontally`. Mirroring reverses the turns before it.
+    pub fn flipped(self, horizontally: bool) -> Orientation {
+        let upright = self.normalized();
+        let turns = 4 - upright.quarter_turns + if horizontally { 0 } else { 2 };
+        Orientation {
+            quarter_turns: turns % 4,
+            flip_horizontal: !upright.flip_horizontal,
+            flip_vertical: false,
+        }
+    }
+
+    /// The same orientation flipped left and right at most, since mirroring
+    /// top and bottom is mirroring left and right turned halfway round.
+    fn normalized(self) -> Orientation {
+        match self.flip_vertical {
+            false => Orientation {
+                quarter_turns: self.quarter_turns % 4,
+                ..self
+            },
+            true => Orientation {
+                quarter_turns: (self.quarter_turns + 2) % 4,
+                flip_horizontal: !self.flip_horizontal,
+                flip_vertical: false,
+            },
+        }
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        let upright = self.normalized();
+        match (upright.flip_horizontal, upright.quarter_turns) {
+            (false, 0) => write!(f, "upright"),
+            (false, turns) => write!(f, "turned {}°", turns as u32 * 90),
+            (true, 0) => write!(f, "flipped left to right"),
+            (true, 2) => write!(f, "flipped top to bottom"),
+            (true, turns) => write!(f, "flipped, turned {}°", turns as u32 * 90),
+        }
+    }
+}
+
+/// A seed placed with an orientation.
+#[derive(Debug)]
+pub struct Oriented<S> {
+    pub seed: S,
+    pub orientation: Orientation,
+}
+
+impl<S: IsSeed> IsSeed for Oriented<S> {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        self.seed
+            .cells((0, 0))
+            .into_iter()
+            .map(|offset| {
+                let (dx, dy) = self.orientation.apply(offset);
+                (origin.0 + dx, origin.1 + dy)
+            })
+            .collect()
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        self.seed
+            .electrons((0, 0))
+            .into_iter()
+            .map(|(offset, electron)| {
+                let (dx, dy) = self.orientation.apply(offset);
+                ((origin.0 + dx, origin.1 + dy), electron)
+            })
+            .collect()
+    }
+}
+
+/// An oriented seed moved back so its box starts where the upright seed's
+/// does, wherever the turns took its cells.
+#[derive(Debug)]
+pub struct InPlace<S>(pub Oriented<S>);
+
+impl<S: IsSeed> InPlace<S> {
+    /// Where the oriented seed goes to start where the upright one would.
+    fn origin(&self, origin: Cell) -> Cell {
+        let corner = |cells: Vec<Cell>| {
+            let left = cells.iter().map(|cell| cell.0).min().unwrap_or_default();
+            let top = cells.iter().map(|cell| cell.1).min().unwrap_or_default();
+            (left, top)
+        };
+        let upright = corner(self.0.seed.cells((0, 0)));
+        let oriented = corner(self.0.cells((0, 0)));
+        (
+            origin.0 + upright.0 - oriented.0,
+            origin.1 + upright.1 - oriented.1,
+        )
+    }
+}
+
+impl<S: IsSeed> IsSeed for InPlace<S> {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        self.0.cells(self.origin(origin))
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        self.0.electrons(self.origin(origin))
+    }
+}
+
+/// How cells drawn by hand are reflected across axes of symmetry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Symmetry {
+    /// Cells are placed just once.
+    #[default]
+    None,
+    /// Mirrored left to right, across an up and down axis.
+    Horizontal,
+    /// Mirrored top to bottom, across a side to side axis.
+    Vertical,
+    /// Mirrored across both axes, into four copies.
+    FourFold,
+    /// Mirrored across the diagonal running down to the right.
+    Diagonal,
+}
+
+impl Display for Symmetry {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            Symmetry::None => write!(f, "none"),
+            Symmetry::Horizontal => write!(f, "horizontal"),
+            Symmetry::Vertical => write!(f, "vertical"),
+            Symmetry::FourFold => write!(f, "four-fold"),
+            Symmetry::Diagonal => write!(f, "diagonal"),
+        }
+    }
+}
+
+impl Symmetry {
+    /// The next one in the cycle: none, horizontal, vertical, four-fold,
+    /// diagonal.
+    pub fn next(self) -> Symmetry {
+        match self {
+            Symmetry::None => Symmetry::Horizontal,
+            Symmetry::Horizontal => Symmetry::Vertical,
+            Symmetry::Vertical => Symmetry::FourFold,
+            Symmetry::FourFold => Symmetry::Diagonal,
+            Symmetry::Diagonal => Symmetry::None,
+        }
+    }
+
+    /// A cell and its reflections across axes crossing at `center`, which is
+    /// in half cells, so the axes can run between cells as well as through
+    /// them. Cells on an axis are their own reflection, and repeated.
+    pub fn images(self, (x, y): Cell, (cx, cy): Cell) -> Vec<Cell> {
+        match self {
+            Symmetry::None => vec![(x, y)],
+            Symmetry::Horizontal => vec![(x, y), (cx - x, y)],
+            Symmetry::Vertical => vec![(x, y), (x, cy - y)],
+            Symmetry::FourFold => vec![(x, y), (cx - x, y), (x, cy - y), (cx - x, cy - y)],
+            Symmetry::Diagonal => {
+                // the axis only runs through whole or half cells both ways
+                // when the center is, otherwise it's nudged up and left
+                let shift = (cx - cy).div_euclid(2);
+                vec![(x, y), (y + shift, x - shift)]
+            }
+        }
+    }
+}
+
+/// A seed placed along with its reflections under a symmetry.
+#[derive(Debug)]
+pub struct Mirrored<S> {
+    pub seed: S,
+    pub symmetry: Symmetry,
+    /// Where the axes of symmetry cross, in half cells.
+    pub center: Cell,
+}
+
+impl<S: IsSeed> IsSeed for Mirrored<S> {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        let mut cells: Vec<Cell> = self
+            .seed
+            .cells(origin)
+            .into_iter()
+            .flat_map(|cell| self.symmetry.images(cell, self.center))
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        let mut electrons: Vec<(Cell, Electron)> = self
+            .seed
+            .electrons(origin)
+            .into_iter()
+            .flat_map(|(cell, electron)| {
+                self.symmetry
+                    .images(cell, self.center)
+                    .into_iter()
+                    .map(move |image| (image, electron))
+            })
+            .collect();
+        electrons.sort_unstable_by_key(|(cell, _)| *cell);
+        electrons.dedup_by_key(|(cell, _)| *cell);
+        electrons
+    }
+}
+
 /// Seeds a grid with a single cell.
 impl IsSeed for Cell {
     fn cells(&self, origin: Cell) -> Vec<Cell> {
@@ -73,20 +677,20 @@ impl IsSeed for Still {
             // * *
             Still::Block => vec![
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0, origin.1.saturating_add(1)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(1)),
+                (origin.0 + 1, origin.1),
+                (origin.0, origin.1 + 1),
+                (origin.0 + 1, origin.1 + 1),
             ],
             //   o *
             // *     *
             //   * *
             Still::Beehive => vec![
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0.saturating_sub(1), origin.1.saturating_add(1)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(1)),
-                (origin.0, origin.1.saturating_add(2)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(2)),
+                (origin.0 + 1, origin.1),
+                (origin.0 - 1, origin.1 + 1),
+                (origin.0 + 2, origin.1 + 1),
+                (origin.0, origin.1 + 2),
+                (origin.0 + 1, origin.1 + 2),
             ],
             //   o *
             // *     *
@@ -94,31 +698,31 @@ impl IsSeed for Still {
             //     *
             Still::Loaf => vec![
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0.saturating_sub(1), origin.1.saturating_add(1)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(1)),
-                (origin.0, origin.1.saturating_add(2)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(3)),
+                (origin.0 + 1, origin.1),
+                (origin.0 - 1, origin.1 + 1),
+                (origin.0 + 2, origin.1 + 1),
+                (origin.0, origin.1 + 2),
+                (origin.0 + 2, origin.1 + 2),
+                (origin.0 + 1, origin.1 + 3),
             ],
             // o *
             // *   *
             //   *
             Still::Boat => vec![
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0, origin.1.saturating_add(1)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(1)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(2)),
+                (origin.0 + 1, origin.1),
+                (origin.0, origin.1 + 1),
+                (origin.0 + 2, origin.1 + 1),
+                (origin.0 + 1, origin.1 + 2),
             ],
             //   o
             // *   *
             //   *
             Still::Tub => vec![
                 origin,
-                (origin.0.saturating_sub(1), origin.1.saturating_add(1)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(1)),
-                (origin.0, origin.1.saturating_add(2)),
+                (origin.0 - 1, origin.1 + 1),
+                (origin.0 + 1, origin.1 + 1),
+                (origin.0, origin.1 + 2),
             ],
         }
     }
@@ -132,20 +736,16 @@ impl IsSeed for Oscillator {
     fn cells(&self, origin: Cell) -> Vec<Cell> {
         match self {
             // o * *
-            Oscillator::Blinker => vec![
-                origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0.saturating_add(2), origin.1),
-            ],
+            Oscillator::Blinker => vec![origin, (origin.0 + 1, origin.1), (origin.0 + 2, origin.1)],
             //   o * *
             // * * *
             Oscillator::Toad => vec![
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0.saturating_add(2), origin.1),
-                (origin.0.saturating_sub(1), origin.1.saturating_add(1)),
-                (origin.0, origin.1.saturating_add(1)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(1)),
+                (origin.0 + 1, origin.1),
+                (origin.0 + 2, origin.1),
+                (origin.0 - 1, origin.1 + 1),
+                (origin.0, origin.1 + 1),
+                (origin.0 + 1, origin.1 + 1),
             ],
             // o *
             // * *
@@ -154,14 +754,14 @@ impl IsSeed for Oscillator {
             Oscillator::Beacon => vec![
                 // top left
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0, origin.1.saturating_add(1)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(1)),
+                (origin.0 + 1, origin.1),
+                (origin.0, origin.1 + 1),
+                (origin.0 + 1, origin.1 + 1),
                 // bottom right
-                (origin.0.saturating_add(2), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(3)),
+                (origin.0 + 2, origin.1 + 2),
+                (origin.0 + 2, origin.1 + 3),
+                (origin.0 + 3, origin.1 + 2),
+                (origin.0 + 3, origin.1 + 3),
             ],
             //    0 1 2 3 4 5 6 7 8 9 0 1 2
             // 0      o * *       * * *
@@ -180,65 +780,65 @@ impl IsSeed for Oscillator {
             Oscillator::Pulsar => vec![
                 // line 0
                 origin,
-                (origin.0.saturating_add(1), origin.1),
-                (origin.0.saturating_add(2), origin.1),
-                (origin.0.saturating_add(6), origin.1),
-                (origin.0.saturating_add(7), origin.1),
-                (origin.0.saturating_add(8), origin.1),
+                (origin.0 + 1, origin.1),
+                (origin.0 + 2, origin.1),
+                (origin.0 + 6, origin.1),
+                (origin.0 + 7, origin.1),
+                (origin.0 + 8, origin.1),
                 // line 1 (empty)
                 // line 2
-                (origin.0.saturating_sub(2), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(5), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(10), origin.1.saturating_add(2)),
+                (origin.0 - 2, origin.1 + 2),
+                (origin.0 + 3, origin.1 + 2),
+                (origin.0 + 5, origin.1 + 2),
+                (origin.0 + 10, origin.1 + 2),
                 // line 3
-                (origin.0.saturating_sub(2), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(5), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(10), origin.1.saturating_add(3)),
+                (origin.0 - 2, origin.1 + 3),
+                (origin.0 + 3, origin.1 + 3),
+                (origin.0 + 5, origin.1 + 3),
+                (origin.0 + 10, origin.1 + 3),
                 // line 4
-                (origin.0.saturating_sub(2), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(5), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(10), origin.1.saturating_add(4)),
+                (origin.0 - 2, origin.1 + 4),
+                (origin.0 + 3, origin.1 + 4),
+                (origin.0 + 5, origin.1 + 4),
+                (origin.0 + 10, origin.1 + 4),
                 // line 5
-                (origin.0, origin.1.saturating_add(5)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(5)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(5)),
-                (origin.0.saturating_add(6), origin.1.saturating_add(5)),
-                (origin.0.saturating_add(7), origin.1.saturating_add(5)),
-                (origin.0.saturating_add(8), origin.1.saturating_add(5)),
+                (origin.0, origin.1 + 5),
+                (origin.0 + 1, origin.1 + 5),
+                (origin.0 + 2, origin.1 + 5),
+                (origin.0 + 6, origin.1 + 5),
+                (origin.0 + 7, origin.1 + 5),
+                (origin.0 + 8, origin.1 + 5),
                 // line 6 (empty)
                 // line 7
-                (origin.0, origin.1.saturating_add(7)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(7)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(7)),
-                (origin.0.saturating_add(6), origin.1.saturating_add(7)),
-                (origin.0.saturating_add(7), origin.1.saturating_add(7)),
-                (origin.0.saturating_add(8), origin.1.saturating_add(7)),
+                (origin.0, origin.1 + 7),
+                (origin.0 + 1, origin.1 + 7),
+                (origin.0 + 2, origin.1 + 7),
+                (origin.0 + 6, origin.1 + 7),
+                (origin.0 + 7, origin.1 + 7),
+                (origin.0 + 8, origin.1 + 7),
                 // line 8
-                (origin.0.saturating_sub(2), origin.1.saturating_add(8)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(8)),
-                (origin.0.saturating_add(5), origin.1.saturating_add(8)),
-                (origin.0.saturating_add(10), origin.1.saturating_add(8)),
+                (origin.0 - 2, origin.1 + 8),
+                (origin.0 + 3, origin.1 + 8),
+                (origin.0 + 5, origin.1 + 8),
+                (origin.0 + 10, origin.1 + 8),
                 // line 9
-                (origin.0.saturating_sub(2), origin.1.saturating_add(9)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(9)),
-                (origin.0.saturating_add(5), origin.1.saturating_add(9)),
-                (origin.0.saturating_add(10), origin.1.saturating_add(9)),
+                (origin.0 - 2, origin.1 + 9),
+                (origin.0 + 3, origin.1 + 9),
+                (origin.0 + 5, origin.1 + 9),
+                (origin.0 + 10, origin.1 + 9),
                 // line 10
-                (origin.0.saturating_sub(2), origin.1.saturating_add(10)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(10)),
-                (origin.0.saturating_add(5), origin.1.saturating_add(10)),
-                (origin.0.saturating_add(10), origin.1.saturating_add(10)),
+                (origin.0 - 2, origin.1 + 10),
+                (origin.0 + 3, origin.1 + 10),
+                (origin.0 + 5, origin.1 + 10),
+                (origin.0 + 10, origin.1 + 10),
                 // line 11 (empty)
                 // line 12
-                (origin.0, origin.1.saturating_add(12)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(12)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(12)),
-                (origin.0.saturating_add(6), origin.1.saturating_add(12)),
-                (origin.0.saturating_add(7), origin.1.saturating_add(12)),
-                (origin.0.saturating_add(8), origin.1.saturating_add(12)),
+                (origin.0, origin.1 + 12),
+                (origin.0 + 1, origin.1 + 12),
+                (origin.0 + 2, origin.1 + 12),
+                (origin.0 + 6, origin.1 + 12),
+                (origin.0 + 7, origin.1 + 12),
+                (origin.0 + 8, origin.1 + 12),
             ],
             // simplest of its 15 forms
             //   o
@@ -253,17 +853,17 @@ impl IsSeed for Oscillator {
             //   *
             Oscillator::PentaDecathlon => vec![
                 origin,
-                (origin.0, origin.1.saturating_add(1)),
-                (origin.0.saturating_sub(1), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(2)),
-                (origin.0, origin.1.saturating_add(3)),
-                (origin.0, origin.1.saturating_add(4)),
-                (origin.0, origin.1.saturating_add(5)),
-                (origin.0, origin.1.saturating_add(6)),
-                (origin.0.saturating_sub(1), origin.1.saturating_add(7)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(7)),
-                (origin.0, origin.1.saturating_add(8)),
-                (origin.0, origin.1.saturating_add(9)),
+                (origin.0, origin.1 + 1),
+                (origin.0 - 1, origin.1 + 2),
+                (origin.0 + 1, origin.1 + 2),
+                (origin.0, origin.1 + 3),
+                (origin.0, origin.1 + 4),
+                (origin.0, origin.1 + 5),
+                (origin.0, origin.1 + 6),
+                (origin.0 - 1, origin.1 + 7),
+                (origin.0 + 1, origin.1 + 7),
+                (origin.0, origin.1 + 8),
+                (origin.0, origin.1 + 9),
             ],
         }
     }
@@ -281,10 +881,10 @@ impl IsSeed for Spaceship {
             // * * *
             Spaceship::Glider => vec![
                 origin,
-                (origin.0.saturating_sub(1), origin.1.saturating_add(1)),
-                (origin.0.saturating_sub(1), origin.1.saturating_add(2)),
-                (origin.0, origin.1.saturating_add(2)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(2)),
+                (origin.0 - 1, origin.1 + 1),
+                (origin.0 - 1, origin.1 + 2),
+                (origin.0, origin.1 + 2),
+                (origin.0 + 1, origin.1 + 2),
             ],
             // 0 1 2 3 4
             //   o     *
@@ -294,17 +894,17 @@ impl IsSeed for Spaceship {
             Spaceship::LwSpaceship => vec![
                 // line 0
                 origin,
-                (origin.0.saturating_add(3), origin.1),
+                (origin.0 + 3, origin.1),
                 // line 1
-                (origin.0.saturating_sub(1), origin.1.saturating_add(1)),
+                (origin.0 - 1, origin.1 + 1),
                 // line 2
-                (origin.0.saturating_sub(1), origin.1.saturating_add(2)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(2)),
+                (origin.0 - 1, origin.1 + 2),
+                (origin.0 + 3, origin.1 + 2),
                 // line 3
-                (origin.0.saturating_sub(1), origin.1.saturating_add(3)),
-                (origin.0, origin.1.saturating_add(3)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(3)),
+                (origin.0 - 1, origin.1 + 3),
+                (origin.0, origin.1 + 3),
+                (origin.0 + 1, origin.1 + 3),
+                (origin.0 + 2, origin.1 + 3),
             ],
             // 0 1 2 3 4 5
             //     o
@@ -316,19 +916,19 @@ impl IsSeed for Spaceship {
                 // line 0
                 origin,
                 // line 1
-                (origin.0.saturating_sub(2), origin.1.saturating_add(1)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(1)),
+                (origin.0 - 2, origin.1 + 1),
+                (origin.0 + 2, origin.1 + 1),
                 // line 2
-                (origin.0.saturating_add(3), origin.1.saturating_add(2)),
+                (origin.0 + 3, origin.1 + 2),
                 // line 3
-                (origin.0.saturating_sub(2), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(3)),
+                (origin.0 - 2, origin.1 + 3),
+                (origin.0 + 3, origin.1 + 3),
                 // line 4
-                (origin.0.saturating_sub(1), origin.1.saturating_add(4)),
-                (origin.0, origin.1.saturating_add(4)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(4)),
+                (origin.0 - 1, origin.1 + 4),
+                (origin.0, origin.1 + 4),
+                (origin.0 + 1, origin.1 + 4),
+                (origin.0 + 2, origin.1 + 4),
+                (origin.0 + 3, origin.1 + 4),
             ],
             // 0 1 2 3 4 5 6
             //     o *
@@ -339,27 +939,151 @@ impl IsSeed for Spaceship {
             Spaceship::HwSpaceship => vec![
                 // line 0
                 origin,
-                (origin.0.saturating_add(1), origin.1),
+                (origin.0 + 1, origin.1),
                 // line 1
-                (origin.0.saturating_sub(2), origin.1.saturating_add(1)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(1)),
+                (origin.0 - 2, origin.1 + 1),
+                (origin.0 + 3, origin.1 + 1),
                 // line 2
-                (origin.0.saturating_add(4), origin.1.saturating_add(2)),
+                (origin.0 + 4, origin.1 + 2),
                 // line 3
-                (origin.0.saturating_sub(2), origin.1.saturating_add(3)),
-                (origin.0.saturating_add(4), origin.1.saturating_add(3)),
+                (origin.0 - 2, origin.1 + 3),
+                (origin.0 + 4, origin.1 + 3),
                 // line 4
-                (origin.0.saturating_sub(1), origin.1.saturating_add(4)),
-                (origin.0, origin.1.saturating_add(4)),
-                (origin.0.saturating_add(1), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(2), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(3), origin.1.saturating_add(4)),
-                (origin.0.saturating_add(4), origin.1.saturating_add(4)),
+                (origin.0 - 1, origin.1 + 4),
+                (origin.0, origin.1 + 4),
+                (origin.0 + 1, origin.1 + 4),
+                (origin.0 + 2, origin.1 + 4),
+                (origin.0 + 3, origin.1 + 4),
+                (origin.0 + 4, origin.1 + 4),
             ],
         }
     }
 }
 
+impl Methuselah {
+    fn art(&self) -> &'static str {
+        match self {
+            Methuselah::RPentomino => {
+                "
+.OO
+OO
+.O"
+            }
+            Methuselah::Acorn => {
+                "
+.O
+...O
+OO..OOO"
+            }
+            Methuselah::Diehard => {
+                "
+......O
+OO
+.O...OOO"
+            }
+        }
+    }
+}
+
+impl Gun {
+    fn art(&self) -> &'static str {
+        match self {
+            Gun::GosperGliderGun => {
+                "
+........................O
+......................O.O
+............OO......OO............OO
+...........O...O....OO............OO
+OO........O.....O...OO
+OO........O...O.OO....O.O
+..........O.....O.......O
+...........O...O
+............OO"
+            }
+            Gun::SimkinGliderGun => {
+                "
+OO.....OO
+OO.....OO
+.
+....OO
+....OO
+.
+.
+.
+.
+......................OO.OO
+.....................O.....O
+.....................O......O..OO
+.....................OOO...O...OO
+..........................O
+.
+.
+.
+....................OO
+....................O
+.....................OOO
+.......................O"
+            }
+        }
+    }
+}
+
+impl Eater {
+    fn art(&self) -> &'static str {
+        match self {
+            Eater::Eater1 => {
+                "
+OO
+O.O
+..O
+..OO"
+            }
+            Eater::Eater2 => {
+                "
+OO.O
+OO.OOO
+......O
+OO.OOO
+.O.O
+.O.O
+..O"
+            }
+        }
+    }
+}
+
+/// The cells of a pattern drawn with `O` for live cells, from the drawing's
+/// top-left corner at `origin`.
+fn drawn(art: &str, origin: Cell) -> Vec<Cell> {
+    art.trim_start()
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.char_indices()
+                .filter(|(_, ch)| *ch == 'O')
+                .map(move |(x, _)| (origin.0 + x as i64, origin.1 + y as i64))
+        })
+        .collect()
+}
+
+impl IsSeed for Methuselah {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        drawn(self.art(), origin)
+    }
+}
+
+impl IsSeed for Gun {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        drawn(self.art(), origin)
+    }
+}
+
+impl IsSeed for Eater {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        drawn(self.art(), origin)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -612,6 +1336,244 @@ mod tests {
         assert_eq!(grid.cells, expected_cells);
     }
 
+    #[test]
+    fn test_seed_names() {
+        for index in 0..26 {
+            let seed = Seed::from_name(match index {
+                0 => "cell",
+                1 => "Block",
+                2 => "BEEHIVE",
+                3 => "loaf",
+                4 => "boat",
+                5 => "tub",
+                6 => "blinker",
+                7 => "toad",
+                8 => "beacon",
+                9 => "pulsar",
+                10 => "penta-decathlon",
+                11 => "glider",
+                12 => "lwss",
+                13 => "MWSS",
+                14 => "hwss",
+                15 => "R-pentomino",
+                16 => "acorn",
+                17 => "diehard",
+                18 => "Gosper glider gun",
+                19 => "simkin_glider_gun",
+                20 => "eater 1",
+                21 => "Eater2",
+                22 => "clock",
+                23 => "Diode",
+                24 => "or gate",
+                _ => "XOR_gate",
+            })
+            .unwrap();
+            assert_eq!(Seed::from_name(seed.name()).unwrap().name(), seed.name());
+        }
+
+        assert!(Seed::from_name("gosper").is_none());
+    }
+
+    #[test]
+    fn test_about() {
+        let glider = Seed::Spaceship(Spaceship::Glider).about();
+        assert_eq!(
+            glider.summary(),
+            "found by Richard K. Guy in 1969, period 4, moves at c/4"
+        );
+        assert_eq!(
+            Seed::Methuselah(Methuselah::RPentomino).about().summary(),
+            "found by John Conway"
+        );
+        assert_eq!(Seed::Still(Still::Block).about().summary(), "");
+        for seed in SEEDS {
+            assert!(!seed.about().description.is_empty(), "{}", seed.name());
+        }
+    }
+
+    #[test]
+    fn test_guns_fire_gliders() {
+        for (gun, period) in [(Gun::GosperGliderGun, 30), (Gun::SimkinGliderGun, 120)] {
+            let mut grid = Grid::new(120, 120);
+            grid.seed(gun, (40, 40));
+            assert_eq!(grid.cells.len(), 36);
+            // five more cells each time a glider leaves
+            for population in [41, 46] {
+                for _ in 0..period {
+                    grid.tick();
+                }
+                assert_eq!(grid.cells.len(), population);
+            }
+        }
+    }
+
+    #[test]
+    fn test_diehard_dies() {
+        let mut grid = Grid::new(60, 60);
+        grid.seed(Methuselah::Diehard, (25, 25));
+        for _ in 0..129 {
+            grid.tick();
+        }
+        assert!(!grid.cells.is_empty());
+        grid.tick();
+        assert!(grid.cells.is_empty());
+    }
+
+    #[test]
+    fn test_eaters_eat_gliders() {
+        for eater in [Eater::Eater1, Eater::Eater2] {
+            let mut grid = Grid::new(40, 40);
+            grid.seed(eater, (20, 20));
+            let still = grid.cells.clone();
+            grid.tick();
+            assert_eq!(grid.cells, still);
+
+            // a glider heading down and right, straight at it
+            for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+                grid.add_cell((x + 8, y + 8));
+            }
+            for _ in 0..80 {
+                grid.tick();
+            }
+            assert_eq!(grid.cells, still);
+        }
+    }
+
+    #[test]
+    fn test_oriented_glider() {
+        let mut grid = Grid::new(7, 7);
+        grid.seed(
+            Oriented {
+                seed: Spaceship::Glider,
+                orientation: Orientation {
+                    flip_horizontal: true,
+                    ..Default::default()
+                },
+            },
+            (3, 2),
+        );
+
+        #[rustfmt::skip]
+        let expected_cells = HashSet::from([
+            (3, 2),
+                    (4, 3),
+            (2, 4), (3, 4), (4, 4)
+        ]);
+
+        assert_eq!(grid.cells, expected_cells);
+    }
+
+    #[test]
+    fn test_oriented_rotation() {
+        let mut grid = Grid::new(7, 7);
+        grid.seed(
+            Oriented {
+                seed: Oscillator::Blinker,
+                orientation: Orientation {
+                    quarter_turns: 1,
+                    ..Default::default()
+                },
+            },
+            (2, 2),
+        );
+
+        #[rustfmt::skip]
+        let expected_cells = HashSet::from([
+            (2, 2),
+            (2, 3),
+            (2, 4)
+        ]);
+
+        assert_eq!(grid.cells, expected_cells);
+    }
+
+    #[test]
+    fn test_oriented_near_edge() {
+        let mut grid = Grid::new(7, 7);
+        grid.seed(
+            Oriented {
+                seed: Spaceship::Glider,
+                orientation: Orientation {
+                    quarter_turns: 2,
+                    ..Default::default()
+                },
+            },
+            (4, 4),
+        );
+
+        #[rustfmt::skip]
+        let expected_cells = HashSet::from([
+            (3, 2), (4, 2), (5, 2),
+                            (5, 3),
+                    (4, 4)
+        ]);
+
+        assert_eq!(grid.cells, expected_cells);
+    }
+
+    #[test]
+    fn test_in_place() {
+        let mut grid = Grid::new(7, 7);
+        grid.seed(
+            InPlace(Oriented {
+                seed: Spaceship::Glider,
+                orientation: Orientation {
+                    quarter_turns: 2,
+                    ..Default::default()
+                },
+            }),
+            (2, 2),
+        );
+
+        // in the same box as the upright glider, which starts left of it
+        #[rustfmt::skip]
+        let expected_cells = HashSet::from([
+            (1, 2), (2, 2), (3, 2),
+                            (3, 3),
+                    (2, 4)
+        ]);
+
+        assert_eq!(grid.cells, expected_cells);
+    }
+
+    #[test]
+    fn test_orientation_composes() {
+        let glider = Spaceship::Glider.cells((0, 0));
+        let turn = |cells: &[Cell]| -> Vec<Cell> { cells.iter().map(|&(x, y)| (-y, x)).collect() };
+        let flip_horizontal =
+            |cells: &[Cell]| -> Vec<Cell> { cells.iter().map(|&(x, y)| (-x, y)).collect() };
+        let flip_vertical =
+            |cells: &[Cell]| -> Vec<Cell> { cells.iter().map(|&(x, y)| (x, -y)).collect() };
+        let oriented = |orientation: Orientation| -> Vec<Cell> {
+            glider.iter().map(|cell| orientation.apply(*cell)).collect()
+        };
+
+        // a turn, a flip each way and another turn, one at a time
+        let orientation = Orientation::default().turned(1);
+        assert_eq!(oriented(orientation), turn(&glider));
+        let orientation = orientation.flipped(true);
+        assert_eq!(oriented(orientation), flip_horizontal(&turn(&glider)));
+        let orientation = orientation.flipped(false);
+        assert_eq!(
+            oriented(orientation),
+            flip_vertical(&flip_horizontal(&turn(&glider)))
+        );
+        let orientation = orientation.turned(1);
+        assert_eq!(
+            oriented(orientation),
+            turn(&flip_vertical(&flip_horizontal(&turn(&glider))))
+        );
+        // both flips make a half turn, which with the two quarter turns is
+        // back where it started
+        assert_eq!(orientation.to_string(), "upright");
+
+        assert_eq!(
+            Orientation::default().flipped(false).to_string(),
+            "flipped top to bottom"
+        );
+        assert_eq!(Orientation::default().turned(3).to_string(), "turned 270°");
+    }
+
     #[test]
     fn test_single_cell() {
         let mut grid = Grid::new(5, 5);
@@ -624,4 +1586,35 @@ mod tests {
 
         assert_eq!(grid.cells, expected_cells);
     }
+
+    #[test]
+    fn test_mirrored() {
+        let mirrored = |symmetry, center| Mirrored {
+            seed: (0, 0),
+            symmetry,
+            center,
+        };
+
+        // axes between the middle two cells of an 8x8 board
+        assert_eq!(
+            mirrored(Symmetry::FourFold, (7, 7)).cells((1, 2)),
+            vec![(1, 2), (1, 5), (6, 2), (6, 5)]
+        );
+        // and through the middle cell of a 7x7 one, where cells on an axis
+        // aren't repeated
+        assert_eq!(
+            mirrored(Symmetry::Horizontal, (6, 6)).cells((3, 1)),
+            vec![(3, 1)]
+        );
+        assert_eq!(
+            mirrored(Symmetry::Vertical, (6, 6)).cells((3, 1)),
+            vec![(3, 1), (3, 5)]
+        );
+        // the diagonal through the middle of a board wider than it's tall
+        assert_eq!(
+            mirrored(Symmetry::Diagonal, (9, 5)).cells((4, 0)),
+            vec![(2, 2), (4, 0)]
+        );
+        assert_eq!(mirrored(Symmetry::None, (9, 5)).cells((4, 0)), vec![(4, 0)]);
+    }
 }
@@ -0,0 +1,121 @@
+use crate::grid::{BoundingBox, Cell};
+use crate::seed::{IsSeed, Orientation, Oriented};
+use rand::Rng;
+
+/// A random arrangement of cells filling a rectangle.
+#[derive(Debug, Clone)]
+pub struct Soup {
+    /// Cell offsets from the top-left corner of the rectangle.
+    cells: Vec<Cell>,
+}
+
+impl Soup {
+    /// Fills a `width` by `height` rectangle, each cell alive with probability `density`.
+    pub fn random<R: Rng>(rng: &mut R, width: usize, height: usize, density: f64) -> Soup {
+        let mut cells = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if rng.gen_bool(density) {
+                    cells.push((x as i64, y as i64));
+                }
+            }
+        }
+        Soup { cells }
+    }
+}
+
+/// Where to place `count` copies of a seed at random on a `width` by `height`
+/// board, whole where they fit: each copy's orientation and origin. Copies are
+/// turned `orientation`, or without one, any of the eight ways at random.
+pub fn scatter<R: Rng, S: IsSeed>(
+    rng: &mut R,
+    seed: &S,
+    count: usize,
+    (width, height): (usize, usize),
+    orientation: Option<Orientation>,
+) -> Vec<(Orientation, Cell)> {
+    (0..count)
+        .map(|_| {
+            let orientation = orientation.unwrap_or_else(|| Orientation {
+                quarter_turns: rng.gen_range(0..4),
+                flip_horizontal: rng.gen(),
+                flip_vertical: false,
+            });
+            let cells = Oriented { seed, orientation }.cells((0, 0));
+            let bounds = BoundingBox::of(&cells).unwrap_or(BoundingBox {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            });
+            let x = rng.gen_range(0..=width.saturating_sub(bounds.width)) as i64;
+            let y = rng.gen_range(0..=height.saturating_sub(bounds.height)) as i64;
+            (orientation, (x - bounds.x, y - bounds.y))
+        })
+        .collect()
+}
+
+/// Seeds the soup with its top-left corner at the origin.
+impl IsSeed for Soup {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        self.cells
+            .iter()
+            .map(|cell| (origin.0 + cell.0, origin.1 + cell.1))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::seed::Spaceship;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_soup_fills_rectangle() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let soup = Soup::random(&mut rng, 4, 3, 1.0);
+
+        let mut grid = Grid::new(10, 10);
+        grid.seed(soup, (5, 5));
+
+        assert_eq!(grid.cells.len(), 12);
+        assert!(grid.cells.contains(&(5, 5)));
+        assert!(grid.cells.contains(&(8, 7)));
+    }
+
+    #[test]
+    fn test_scatter_lands_on_board() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let glider = Spaceship::Glider;
+
+        let copies = scatter(&mut rng, &glider, 30, (12, 8), None);
+        for (orientation, origin) in &copies {
+            let seed = Oriented {
+                seed: &glider,
+                orientation: *orientation,
+            };
+            assert!(seed
+                .cells(*origin)
+                .iter()
+                .all(|&(x, y)| (0..12).contains(&x) && (0..8).contains(&y)));
+        }
+        assert!(copies
+            .iter()
+            .any(|(orientation, _)| *orientation != copies[0].0));
+
+        let upright = scatter(&mut rng, &glider, 5, (12, 8), Some(Orientation::default()));
+        assert!(upright
+            .iter()
+            .all(|(orientation, _)| *orientation == Orientation::default()));
+    }
+
+    #[test]
+    fn test_soup_is_reproducible() {
+        let first = Soup::random(&mut StdRng::seed_from_u64(7), 16, 16, 0.5);
+        let second = Soup::random(&mut StdRng::seed_from_u64(7), 16, 16, 0.5);
+
+        assert_eq!(first.cells, second.cells);
+    }
+}
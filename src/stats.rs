@@ -0,0 +1,135 @@
+use crate::{compress, grid::Grid};
+use std::{collections::VecDeque, io::Write, path::Path};
+
+/// How many generations the population graph goes back.
+pub const POPULATION_HISTORY_LENGTH: usize = 500;
+
+/// The population of the most recent generations, oldest first, to plot how
+/// a run grows and shrinks.
+#[derive(Debug)]
+pub struct PopulationHistory {
+    populations: VecDeque<u64>,
+    capacity: usize,
+}
+
+impl Default for PopulationHistory {
+    fn default() -> Self {
+        PopulationHistory::new(POPULATION_HISTORY_LENGTH)
+    }
+}
+
+impl PopulationHistory {
+    pub fn new(capacity: usize) -> PopulationHistory {
+        PopulationHistory {
+            populations: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Adds a generation's population, dropping the oldest one when full.
+    pub fn record(&mut self, population: usize) {
+        if self.populations.len() == self.capacity {
+            self.populations.pop_front();
+        }
+        self.populations.push_back(population as u64);
+    }
+
+    /// Up to `count` of the latest populations, oldest first.
+    pub fn latest(&self, count: usize) -> Vec<u64> {
+        let skip = self.populations.len().saturating_sub(count);
+        self.populations.iter().skip(skip).copied().collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.populations.clear();
+    }
+}
+
+/// Writes a CSV row for each generation with its births, deaths and
+/// population, to chart elsewhere.
+pub struct StatsLog<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> std::fmt::Debug for StatsLog<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatsLog").finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> StatsLog<W> {
+    /// Starts the log with the grid's current generation.
+    pub fn new(mut writer: W, grid: &Grid) -> std::io::Result<StatsLog<W>> {
+        writeln!(writer, "generation,births,deaths,population")?;
+        let mut log = StatsLog { writer };
+        log.record(grid)?;
+        Ok(log)
+    }
+
+    /// Adds a row for the generation the grid just ticked to.
+    pub fn record(&mut self, grid: &Grid) -> std::io::Result<()> {
+        writeln!(
+            self.writer,
+            "{},{},{},{}",
+            grid.generation,
+            grid.born.len(),
+            grid.died.len(),
+            grid.cells.len()
+        )
+    }
+}
+
+impl StatsLog<compress::Writer> {
+    /// Starts logging to a file, compressed if it ends in `.gz` or `.zst`.
+    pub fn create<P: AsRef<Path>>(path: P, grid: &Grid) -> std::io::Result<Self> {
+        StatsLog::new(compress::create(path)?, grid)
+    }
+
+    pub fn finish(self) -> std::io::Result<()> {
+        self.writer.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::Oscillator;
+
+    #[test]
+    fn test_keeps_latest() {
+        let mut history = PopulationHistory::new(3);
+        for population in [5, 8, 13, 21] {
+            history.record(population);
+        }
+
+        assert_eq!(history.latest(10), vec![8, 13, 21]);
+        assert_eq!(history.latest(2), vec![13, 21]);
+
+        history.clear();
+        assert!(history.latest(10).is_empty());
+    }
+
+    #[test]
+    fn test_log() {
+        let mut grid = Grid::new(10, 10);
+        grid.seed(Oscillator::Blinker, (4, 4));
+        grid.add_cell((0, 0));
+
+        let mut bytes = Vec::new();
+        let mut log = StatsLog::new(&mut bytes, &grid).unwrap();
+        for _ in 0..2 {
+            grid.tick();
+            log.record(&grid).unwrap();
+        }
+
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            concat!(
+                "generation,births,deaths,population\n",
+                "0,0,0,4\n",
+                "1,2,3,3\n",
+                "2,2,2,3\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,656 @@
+use crate::{capability::ColorDepth, config::Config, grid::Shade};
+use ratatui::style::{Color, Style};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The theme a theme in the config file starts from, unless it names one.
+const DEFAULT_BASE: &str = "classic";
+/// Shades of a cell fading out under a Generations rule, from just died to
+/// nearly gone, drawn in the color of dead cells.
+const FADES: [&str; 3] = ["▓▓", "▒▒", "░░"];
+/// Squares of cells zoomed out in one column, by quarters alive.
+const NARROW_DENSITIES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+
+/// The colors the board and the bars around it are drawn in. Cells are
+/// solid blocks of color, but for `emoji`, which draws them as emoji with
+/// colors of their own, as the game always has.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Theme {
+    #[serde(skip)]
+    pub name: String,
+    pub emoji: bool,
+    #[serde(with = "color")]
+    pub dead: Color,
+    #[serde(with = "color")]
+    pub alive: Color,
+    /// The seed about to be placed.
+    #[serde(with = "color")]
+    pub preview: Color,
+    /// The seed about to be placed over live cells.
+    #[serde(with = "color")]
+    pub overlap: Color,
+    #[serde(with = "color")]
+    pub born: Color,
+    #[serde(with = "color")]
+    pub died: Color,
+    #[serde(with = "color")]
+    pub selected: Color,
+    /// The frame around the live cells.
+    #[serde(with = "color")]
+    pub bounds: Color,
+    #[serde(with = "color")]
+    pub watched: Color,
+    /// Guide lines, trails, neighbor counts and the last generation.
+    #[serde(with = "color")]
+    pub faint: Color,
+    /// Live cells under rules with colors, and Wireworld's electrons and
+    /// wire, as the second and third.
+    #[serde(with = "color")]
+    pub colors: [Color; 4],
+    /// Live cells by how long they've been alive, from just born to long
+    /// settled.
+    #[serde(with = "color")]
+    pub ages: [Color; 5],
+    #[serde(with = "color")]
+    pub title: Color,
+    #[serde(with = "color")]
+    pub title_bg: Color,
+    /// The line with the generation, population and rule.
+    #[serde(with = "color")]
+    pub stats: Color,
+    #[serde(with = "color")]
+    pub stats_bg: Color,
+    /// The line with what's going on and the messages.
+    #[serde(with = "color")]
+    pub status: Color,
+    #[serde(with = "color")]
+    pub status_bg: Color,
+    /// The keys along the bottom.
+    #[serde(with = "color")]
+    pub help: Color,
+    #[serde(with = "color")]
+    pub help_bg: Color,
+    /// Where the guide lines are, along the top and left.
+    #[serde(with = "color")]
+    pub label: Color,
+    #[serde(with = "color")]
+    pub label_bg: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        emoji()
+    }
+}
+
+/// The board in emoji, with blue bars.
+fn emoji() -> Theme {
+    Theme {
+        name: String::from("emoji"),
+        emoji: true,
+        title: Color::Reset,
+        title_bg: Color::Blue,
+        stats: Color::White,
+        stats_bg: Color::DarkGray,
+        status: Color::White,
+        status_bg: Color::Blue,
+        help: Color::Black,
+        help_bg: Color::Gray,
+        label: Color::Black,
+        label_bg: Color::Gray,
+        ..classic()
+    }
+}
+
+/// White cells on black, in the terminal's own colors.
+fn classic() -> Theme {
+    Theme {
+        name: String::from("classic"),
+        emoji: false,
+        dead: Color::Black,
+        alive: Color::White,
+        preview: Color::Blue,
+        overlap: Color::LightRed,
+        born: Color::LightYellow,
+        died: Color::Red,
+        selected: Color::DarkGray,
+        bounds: Color::DarkGray,
+        watched: Color::LightRed,
+        faint: Color::DarkGray,
+        colors: [Color::Red, Color::Blue, Color::Yellow, Color::Green],
+        ages: [
+            Color::LightYellow,
+            Color::Yellow,
+            Color::LightRed,
+            Color::Magenta,
+            Color::White,
+        ],
+        title: Color::White,
+        title_bg: Color::Black,
+        stats: Color::Black,
+        stats_bg: Color::Gray,
+        status: Color::White,
+        status_bg: Color::DarkGray,
+        help: Color::Black,
+        help_bg: Color::Gray,
+        label: Color::Black,
+        label_bg: Color::Gray,
+    }
+}
+
+/// Green on black, like an old monochrome monitor.
+fn phosphor() -> Theme {
+    let green = |level: u8| Color::Rgb(level / 4, level, level / 4);
+    Theme {
+        name: String::from("phosphor"),
+        emoji: false,
+        dead: green(12),
+        alive: green(255),
+        preview: green(110),
+        overlap: Color::Rgb(220, 255, 120),
+        born: Color::Rgb(200, 255, 200),
+        died: green(70),
+        selected: green(45),
+        bounds: green(35),
+        watched: Color::Rgb(255, 255, 160),
+        faint: green(90),
+        colors: [green(255), green(190), green(140), green(100)],
+        ages: [
+            Color::Rgb(200, 255, 200),
+            green(255),
+            green(210),
+            green(170),
+            green(130),
+        ],
+        title: green(255),
+        title_bg: green(30),
+        stats: green(12),
+        stats_bg: green(170),
+        status: green(255),
+        status_bg: green(30),
+        help: green(12),
+        help_bg: green(120),
+        label: green(12),
+        label_bg: green(170),
+    }
+}
+
+/// Ethan Schoonover's Solarized, dark.
+fn solarized() -> Theme {
+    let [base03, base02, base01, base1, base2] = [
+        Color::Rgb(0x00, 0x2b, 0x36),
+        Color::Rgb(0x07, 0x36, 0x42),
+        Color::Rgb(0x58, 0x6e, 0x75),
+        Color::Rgb(0x93, 0xa1, 0xa1),
+        Color::Rgb(0xee, 0xe8, 0xd5),
+    ];
+    let [yellow, orange, red, magenta, violet, blue, cyan, green] = [
+        Color::Rgb(0xb5, 0x89, 0x00),
+        Color::Rgb(0xcb, 0x4b, 0x16),
+        Color::Rgb(0xdc, 0x32, 0x2f),
+        Color::Rgb(0xd3, 0x36, 0x82),
+        Color::Rgb(0x6c, 0x71, 0xc4),
+        Color::Rgb(0x26, 0x8b, 0xd2),
+        Color::Rgb(0x2a, 0xa1, 0x98),
+        Color::Rgb(0x85, 0x99, 0x00),
+    ];
+    Theme {
+        name: String::from("solarized"),
+        emoji: false,
+        dead: base03,
+        alive: base2,
+        preview: blue,
+        overlap: orange,
+        born: yellow,
+        died: red,
+        selected: base01,
+        bounds: base02,
+        watched: magenta,
+        faint: base01,
+        colors: [red, blue, yellow, green],
+        ages: [yellow, orange, red, magenta, violet],
+        title: base2,
+        title_bg: base02,
+        stats: base03,
+        stats_bg: base1,
+        status: base2,
+        status_bg: cyan,
+        help: base02,
+        help_bg: base1,
+        label: base03,
+        label_bg: base1,
+    }
+}
+
+/// Bright colors on black, as far apart as they go.
+fn high_contrast() -> Theme {
+    Theme {
+        name: String::from("high-contrast"),
+        emoji: false,
+        dead: Color::Black,
+        alive: Color::White,
+        preview: Color::LightCyan,
+        overlap: Color::LightRed,
+        born: Color::LightYellow,
+        died: Color::LightMagenta,
+        selected: Color::Blue,
+        bounds: Color::Yellow,
+        watched: Color::LightRed,
+        faint: Color::Gray,
+        colors: [
+            Color::LightRed,
+            Color::LightCyan,
+            Color::LightYellow,
+            Color::LightGreen,
+        ],
+        ages: [
+            Color::LightYellow,
+            Color::LightGreen,
+            Color::LightCyan,
+            Color::LightMagenta,
+            Color::White,
+        ],
+        title: Color::Black,
+        title_bg: Color::White,
+        stats: Color::White,
+        stats_bg: Color::Black,
+        status: Color::Black,
+        status_bg: Color::LightYellow,
+        help: Color::Black,
+        help_bg: Color::White,
+        label: Color::Black,
+        label_bg: Color::LightYellow,
+    }
+}
+
+/// Colors told apart without red and green, by blue against orange and
+/// yellow and by how bright they are, from Okabe and Ito's palette.
+fn deuteranopia() -> Theme {
+    let [orange, sky_blue, bluish_green, yellow, blue, vermillion, reddish_purple] = [
+        Color::Rgb(0xe6, 0x9f, 0x00),
+        Color::Rgb(0x56, 0xb4, 0xe9),
+        Color::Rgb(0x00, 0x9e, 0x73),
+        Color::Rgb(0xf0, 0xe4, 0x42),
+        Color::Rgb(0x00, 0x72, 0xb2),
+        Color::Rgb(0xd5, 0x5e, 0x00),
+        Color::Rgb(0xcc, 0x79, 0xa7),
+    ];
+    let gray = |level: u8| Color::Rgb(level, level, level);
+    Theme {
+        name: String::from("deuteranopia"),
+        emoji: false,
+        dead: gray(0x10),
+        alive: gray(0xf0),
+        preview: sky_blue,
+        overlap: orange,
+        born: yellow,
+        died: blue,
+        selected: gray(0x48),
+        bounds: gray(0x30),
+        watched: reddish_purple,
+        faint: gray(0x70),
+        colors: [vermillion, sky_blue, yellow, bluish_green],
+        ages: [yellow, orange, vermillion, reddish_purple, blue],
+        title: gray(0xf0),
+        title_bg: blue,
+        stats: gray(0x10),
+        stats_bg: sky_blue,
+        status: gray(0x10),
+        status_bg: orange,
+        help: gray(0x10),
+        help_bg: gray(0xb0),
+        label: gray(0x10),
+        label_bg: yellow,
+    }
+}
+
+/// As for deuteranopia, but for the reds that look dark, so none are left
+/// next to black: what's about to be placed is blue over white, or yellow
+/// where it runs into live cells.
+fn protanopia() -> Theme {
+    let [orange, sky_blue, yellow, blue] = [
+        Color::Rgb(0xe6, 0x9f, 0x00),
+        Color::Rgb(0x56, 0xb4, 0xe9),
+        Color::Rgb(0xf0, 0xe4, 0x42),
+        Color::Rgb(0x00, 0x72, 0xb2),
+    ];
+    Theme {
+        name: String::from("protanopia"),
+        overlap: yellow,
+        born: orange,
+        watched: Color::Rgb(0xff, 0xff, 0xff),
+        colors: [orange, sky_blue, yellow, blue],
+        ages: [yellow, orange, sky_blue, blue, Color::Rgb(0x60, 0x60, 0x60)],
+        ..deuteranopia()
+    }
+}
+
+/// The themes that come with the game, the first one the default.
+pub fn built_in() -> Vec<Theme> {
+    vec![
+        emoji(),
+        classic(),
+        phosphor(),
+        solarized(),
+        high_contrast(),
+        deuteranopia(),
+        protanopia(),
+    ]
+}
+
+/// The built-in themes followed by those defined in the config file, each
+/// from a theme it names as its `base`, or [`DEFAULT_BASE`], with the colors
+/// it changes. One with the name of a built-in theme takes its place.
+pub fn themes(defined: &BTreeMap<String, toml::Table>) -> Result<Vec<Theme>, String> {
+    let mut themes = built_in();
+    for (name, table) in defined {
+        let mut table = table.clone();
+        let base = match table.remove("base") {
+            Some(toml::Value::String(base)) => base,
+            Some(base) => {
+                return Err(format!(
+                    "{}: base should be a theme's name, not {}",
+                    name, base
+                ))
+            }
+            None => String::from(DEFAULT_BASE),
+        };
+        let base =
+            find(&themes, &base).ok_or_else(|| format!("{}: {}", name, unknown(&themes, &base)))?;
+        let mut colors = toml::Table::try_from(&themes[base]).map_err(|error| error.to_string())?;
+        colors.extend(table);
+        let mut theme: Theme = colors
+            .try_into()
+            .map_err(|error: toml::de::Error| format!("{}: {}", name, error.message()))?;
+        theme.name = name.clone();
+        match find(&themes, name) {
+            Some(index) => themes[index] = theme,
+            None => themes.push(theme),
+        }
+    }
+    Ok(themes)
+}
+
+/// The themes there are with those in `config.toml`.
+pub fn load() -> Result<Vec<Theme>, String> {
+    themes(&Config::load()?.themes).map_err(|error| format!("config.toml: themes.{}", error))
+}
+
+/// Where the theme named is among `themes`, in any case.
+pub fn find(themes: &[Theme], name: &str) -> Option<usize> {
+    themes
+        .iter()
+        .position(|theme| theme.name.eq_ignore_ascii_case(name))
+}
+
+/// Says there's no theme named that, and which there are.
+pub fn unknown(themes: &[Theme], name: &str) -> String {
+    let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+    format!("no theme {:?}, expected one of {}", name, names.join(", "))
+}
+
+impl Theme {
+    /// This theme in the colors a terminal with `depth` shows, each the
+    /// nearest one there is.
+    pub fn fit(mut self, depth: ColorDepth) -> Theme {
+        let colors = [
+            &mut self.dead,
+            &mut self.alive,
+            &mut self.preview,
+            &mut self.overlap,
+            &mut self.born,
+            &mut self.died,
+            &mut self.selected,
+            &mut self.bounds,
+            &mut self.watched,
+            &mut self.faint,
+            &mut self.title,
+            &mut self.title_bg,
+            &mut self.stats,
+            &mut self.stats_bg,
+            &mut self.status,
+            &mut self.status_bg,
+            &mut self.help,
+            &mut self.help_bg,
+            &mut self.label,
+            &mut self.label_bg,
+        ];
+        for color in colors
+            .into_iter()
+            .chain(&mut self.colors)
+            .chain(&mut self.ages)
+        {
+            *color = depth.nearest(*color);
+        }
+        self
+    }
+
+    /// The color a shade is drawn in.
+    pub fn color(&self, shade: Shade) -> Color {
+        match shade {
+            Shade::Dead => self.dead,
+            Shade::Alive | Shade::Density(_) => self.alive,
+            Shade::Preview => self.preview,
+            Shade::Overlap => self.overlap,
+            Shade::Born => self.born,
+            Shade::Died | Shade::Dying(_) => self.died,
+            Shade::Selected => self.selected,
+            Shade::Bounds => self.bounds,
+            Shade::WatchedAlive | Shade::WatchedDead => self.watched,
+            Shade::Age(index) => self.ages[index],
+            Shade::Color(index) => self.colors[index],
+            Shade::ElectronHead => self.colors[1],
+            Shade::Wire => self.colors[2],
+            Shade::Onion | Shade::Trail(_) | Shade::Guide { .. } | Shade::Neighbors(_) => {
+                self.faint
+            }
+        }
+    }
+
+    /// What's drawn for a shade `columns` wide, and in what colors. Emoji
+    /// only fit two columns, so one column is drawn in the colors instead.
+    pub fn block(&self, shade: Shade, columns: usize) -> (String, Style) {
+        if self.emoji && columns == 2 {
+            return (shade.to_string(), Style::default().fg(Color::White));
+        }
+        let text = |text: &str| text.chars().take(columns).collect::<String>();
+        let glyph = |shown: String| (shown, Style::default().fg(self.color(shade)).bg(self.dead));
+        let circle = match columns {
+            1 => "o",
+            _ => "()",
+        };
+        match shade {
+            Shade::WatchedAlive => (
+                String::from(circle),
+                Style::default().fg(self.watched).bg(self.alive),
+            ),
+            Shade::WatchedDead => glyph(String::from(circle)),
+            Shade::Onion => glyph(text("[]")),
+            Shade::Dying(index) => glyph(text(FADES[index])),
+            Shade::Density(quarters) if columns == 1 => glyph(NARROW_DENSITIES[quarters].into()),
+            Shade::Density(_) | Shade::Trail(_) | Shade::Guide { .. } => {
+                glyph(text(&shade.to_string()))
+            }
+            Shade::Neighbors(count) => glyph(format!("{:>columns$}", count)),
+            _ => (" ".repeat(columns), Style::default().bg(self.color(shade))),
+        }
+    }
+
+    pub fn title(&self) -> Style {
+        Style::default().fg(self.title).bg(self.title_bg)
+    }
+
+    pub fn stats(&self) -> Style {
+        Style::default().fg(self.stats).bg(self.stats_bg)
+    }
+
+    pub fn status(&self) -> Style {
+        Style::default().fg(self.status).bg(self.status_bg)
+    }
+
+    pub fn help(&self) -> Style {
+        Style::default().fg(self.help).bg(self.help_bg)
+    }
+
+    pub fn label(&self) -> Style {
+        Style::default().fg(self.label).bg(self.label_bg)
+    }
+}
+
+/// Colors written as they are in the config file, like `black`, `#00ff00`
+/// or `235` for one of the terminal's 256, alone or in a list.
+mod color {
+    use ratatui::style::Color;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub trait Colors: Sized {
+        fn write<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+        fn read<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+    }
+
+    fn parse<E: Error>(name: &str) -> Result<Color, E> {
+        name.parse().map_err(|_| {
+            E::custom(format!(
+                "invalid color {:?}, expected e.g. black, #00ff00 or 235",
+                name
+            ))
+        })
+    }
+
+    impl Colors for Color {
+        fn write<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.to_string().to_lowercase().serialize(serializer)
+        }
+
+        fn read<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            parse(&String::deserialize(deserializer)?)
+        }
+    }
+
+    impl<const N: usize> Colors for [Color; N] {
+        fn write<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let names: Vec<String> = self
+                .iter()
+                .map(|color| color.to_string().to_lowercase())
+                .collect();
+            names.serialize(serializer)
+        }
+
+        fn read<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let names = Vec::<String>::deserialize(deserializer)?;
+            let colors = names
+                .iter()
+                .map(|name| parse(name))
+                .collect::<Result<Vec<Color>, D::Error>>()?;
+            colors.try_into().map_err(|_| {
+                D::Error::custom(format!("expected {} colors, not {}", N, names.len()))
+            })
+        }
+    }
+
+    pub fn serialize<C: Colors, S: Serializer>(
+        colors: &C,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        colors.write(serializer)
+    }
+
+    pub fn deserialize<'de, C: Colors, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<C, D::Error> {
+        C::read(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defined(text: &str) -> BTreeMap<String, toml::Table> {
+        toml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_themes() {
+        let themes = themes(&defined(
+            r##"
+            [amber]
+            base = "phosphor"
+            alive = "#ffb000"
+            colors = ["red", "blue", "yellow", "208"]
+
+            [classic]
+            alive = "lightgreen"
+
+            [plain]
+            title-bg = "dark gray"
+            "##,
+        ))
+        .unwrap();
+        let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "emoji",
+                "classic",
+                "phosphor",
+                "solarized",
+                "high-contrast",
+                "deuteranopia",
+                "protanopia",
+                "amber",
+                "plain"
+            ]
+        );
+
+        let amber = &themes[find(&themes, "Amber").unwrap()];
+        assert_eq!(amber.alive, Color::Rgb(0xff, 0xb0, 0x00));
+        assert_eq!(amber.colors[3], Color::Indexed(208));
+        assert_eq!(amber.dead, phosphor().dead);
+        // from the classic theme as changed
+        assert_eq!(themes[1].alive, Color::LightGreen);
+        assert_eq!(themes[8].alive, Color::LightGreen);
+        assert_eq!(themes[8].title_bg, Color::DarkGray);
+
+        for (text, error) in [
+            ("[x]\nbase = \"sepia\"", "x: no theme \"sepia\""),
+            ("[x]\nalive = \"beige\"", "x: invalid color \"beige\""),
+            ("[x]\nages = [\"red\"]", "x: expected 5 colors, not 1"),
+            ("[x]\nglow = \"red\"", "x: unknown field `glow`"),
+        ] {
+            let found = super::themes(&defined(text)).unwrap_err();
+            assert!(found.starts_with(error), "{}", found);
+        }
+    }
+
+    #[test]
+    fn test_built_in() {
+        // as they'd be written in the config file, and read back
+        for theme in built_in() {
+            let table = toml::Table::try_from(&theme).unwrap();
+            let read: Theme = table.try_into().unwrap();
+            assert_eq!(
+                Theme {
+                    name: theme.name.clone(),
+                    ..read
+                },
+                theme
+            );
+        }
+    }
+
+    #[test]
+    fn test_fit() {
+        let theme = solarized();
+        assert_eq!(theme.clone().fit(ColorDepth::TrueColor), theme);
+        let fitted = theme.fit(ColorDepth::Ansi256);
+        assert_eq!(fitted.dead, Color::Indexed(234));
+        assert_eq!(fitted.colors[1], Color::Indexed(32));
+        let fitted = protanopia().fit(ColorDepth::Ansi16);
+        assert_eq!(
+            (fitted.alive, fitted.preview, fitted.overlap),
+            (Color::Gray, Color::LightBlue, Color::LightYellow)
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    args,
+    grid::{Cell, Grid},
+};
+use std::{
+    collections::HashSet,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// Something to watch for while the game runs, pausing it on the tick that
+/// gets there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Reaching this generation.
+    Generation(usize),
+    /// The population growing past this many cells.
+    Above(usize),
+    /// The population shrinking under this many cells.
+    Below(usize),
+    /// The population reaching exactly this many cells, as in `=0` to catch
+    /// everything dying out.
+    Population(usize),
+    /// This cell being born or dying.
+    Cell(Cell),
+}
+
+impl Breakpoint {
+    /// Whether the latest tick got here. Population breakpoints are only hit
+    /// on the tick that crosses them, so playing on doesn't stop again.
+    pub fn is_hit(&self, grid: &Grid) -> bool {
+        let (before, now) = (grid.previous.len(), grid.cells.len());
+        match *self {
+            Breakpoint::Generation(generation) => grid.generation == generation,
+            Breakpoint::Above(count) => before <= count && now > count,
+            Breakpoint::Below(count) => before >= count && now < count,
+            Breakpoint::Population(count) => before != count && now == count,
+            Breakpoint::Cell(cell) => grid.previous.contains(&cell) != grid.cells.contains(&cell),
+        }
+    }
+}
+
+/// The cells the breakpoints watch, to highlight on the board.
+pub fn watched(breakpoints: &[Breakpoint]) -> HashSet<Cell> {
+    breakpoints
+        .iter()
+        .filter_map(|breakpoint| match breakpoint {
+            Breakpoint::Cell(cell) => Some(*cell),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The first of the breakpoints the grid is at, if any.
+pub fn hit(breakpoints: &[Breakpoint], grid: &Grid) -> Option<Breakpoint> {
+    breakpoints
+        .iter()
+        .find(|breakpoint| breakpoint.is_hit(grid))
+        .copied()
+}
+
+impl Display for Breakpoint {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Breakpoint::Generation(generation) => write!(f, "gen {}", generation),
+            Breakpoint::Above(count) => write!(f, "pop > {}", count),
+            Breakpoint::Below(count) => write!(f, "pop < {}", count),
+            Breakpoint::Population(count) => write!(f, "pop = {}", count),
+            Breakpoint::Cell((x, y)) => write!(f, "cell {},{}", x, y),
+        }
+    }
+}
+
+/// Parses a generation number, as in `1103`, a population preceded by
+/// `>`, `<` or `=`, as in `>500`, or a cell's coordinates, as in `12,-3`.
+impl FromStr for Breakpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.contains(',') {
+            return args::parse_coordinates(s)
+                .map(Breakpoint::Cell)
+                .ok_or_else(|| format!("Invalid breakpoint {:?}", s));
+        }
+        let (breakpoint, count): (fn(usize) -> Breakpoint, &str) = match s {
+            _ if s.starts_with('>') => (Breakpoint::Above, &s[1..]),
+            _ if s.starts_with('<') => (Breakpoint::Below, &s[1..]),
+            _ if s.starts_with('=') => (Breakpoint::Population, &s[1..]),
+            _ => (Breakpoint::Generation, s),
+        };
+        count
+            .trim()
+            .parse()
+            .map(breakpoint)
+            .map_err(|_| format!("Invalid breakpoint {:?}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::Oscillator;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("1103".parse(), Ok(Breakpoint::Generation(1103)));
+        assert_eq!(" 5 ".parse(), Ok(Breakpoint::Generation(5)));
+        assert!("".parse::<Breakpoint>().is_err());
+        assert!("-1".parse::<Breakpoint>().is_err());
+        assert_eq!(Breakpoint::Generation(1103).to_string(), "gen 1103");
+
+        assert_eq!(">500".parse(), Ok(Breakpoint::Above(500)));
+        assert_eq!("< 10".parse(), Ok(Breakpoint::Below(10)));
+        assert_eq!("=0".parse(), Ok(Breakpoint::Population(0)));
+        assert!(">".parse::<Breakpoint>().is_err());
+        assert!("<>3".parse::<Breakpoint>().is_err());
+        assert_eq!(Breakpoint::Above(500).to_string(), "pop > 500");
+
+        assert_eq!("12,-3".parse(), Ok(Breakpoint::Cell((12, -3))));
+        assert!("12,".parse::<Breakpoint>().is_err());
+        assert_eq!(Breakpoint::Cell((12, -3)).to_string(), "cell 12,-3");
+    }
+
+    #[test]
+    fn test_generation() {
+        let mut grid = Grid::new(10, 10);
+        grid.seed(Oscillator::Blinker, (4, 4));
+        let breakpoints = [Breakpoint::Generation(3)];
+
+        let stops: Vec<usize> = (1..=6)
+            .filter(|_| {
+                grid.tick();
+                hit(&breakpoints, &grid).is_some()
+            })
+            .collect();
+        assert_eq!(stops, vec![3]);
+
+        // a cleared board starts counting again
+        grid.clear();
+        assert_eq!(grid.generation, 0);
+    }
+
+    #[test]
+    fn test_population() {
+        // a pre-block grows to a block of four and stays there
+        let mut grid = Grid::new(10, 10);
+        for cell in [(4, 4), (5, 4), (4, 5)] {
+            grid.add_cell(cell);
+        }
+        let breakpoints = [Breakpoint::Above(3)];
+        grid.tick();
+        assert_eq!(hit(&breakpoints, &grid), Some(Breakpoint::Above(3)));
+        grid.tick();
+        assert_eq!(hit(&breakpoints, &grid), None);
+
+        // a lone pair dies out in one tick
+        let mut grid = Grid::new(10, 10);
+        grid.add_cell((4, 4));
+        grid.add_cell((5, 4));
+        let breakpoints = [Breakpoint::Below(2), Breakpoint::Population(0)];
+        grid.tick();
+        assert_eq!(hit(&breakpoints, &grid), Some(Breakpoint::Below(2)));
+        assert!(Breakpoint::Population(0).is_hit(&grid));
+        grid.tick();
+        assert_eq!(hit(&breakpoints, &grid), None);
+    }
+
+    #[test]
+    fn test_cell() {
+        // a blinker's middle never changes, and its ends turn on and off
+        let mut grid = Grid::new(10, 10);
+        grid.seed(Oscillator::Blinker, (4, 4));
+        let breakpoints = [
+            Breakpoint::Cell((5, 4)),
+            Breakpoint::Cell((5, 3)),
+            Breakpoint::Cell((8, 8)),
+        ];
+        assert_eq!(watched(&breakpoints).len(), 3);
+
+        for _ in 0..3 {
+            grid.tick();
+            assert_eq!(hit(&breakpoints, &grid), Some(Breakpoint::Cell((5, 3))));
+        }
+    }
+}
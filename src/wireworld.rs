@@ -0,0 +1,178 @@
+use crate::{grid::Cell, seed::IsSeed};
+
+/// An electron on a Wireworld wire. Heads are the live cells and tails the
+/// dying ones, so the head moves on along the wire and the tail stops it
+/// from turning back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Electron {
+    Head,
+    Tail,
+}
+
+/// Wireworld circuits, drawn with `#` for wire, `H` for an electron's head
+/// and `t` for its tail. Signals go in on the left and come out on the right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Circuit {
+    /// An electron going round a loop, sending one down the wire every 8 ticks.
+    Clock,
+    /// Lets electrons through from the left only.
+    Diode,
+    /// Two inputs through diodes joining into one output.
+    OrGate,
+    /// Outputs an electron when exactly one input gets one at the same time,
+    /// since two that meet wipe each other out.
+    XorGate,
+}
+
+impl Circuit {
+    fn art(&self) -> &'static str {
+        match self {
+            Circuit::Clock => {
+                "
+.tH#.
+#...#
+.###.##########"
+            }
+            Circuit::Diode => {
+                "
+......##.....
+#######.#####
+......##....."
+            }
+            Circuit::OrGate => {
+                "
+......##.......
+#######.####...
+......##...#...
+...........#####
+......##...#...
+#######.####...
+......##......."
+            }
+            Circuit::XorGate => {
+                "
+......##........
+#######.###.....
+......##..#.....
+.........####...
+.........#..#####
+.........####...
+......##..#.....
+#######.###.....
+......##........"
+            }
+        }
+    }
+
+    /// Every cell of the drawing that isn't empty, with what's on it.
+    fn layout(&self, origin: Cell) -> impl Iterator<Item = (Cell, Option<Electron>)> {
+        self.art()
+            .trim_start()
+            .lines()
+            .enumerate()
+            .flat_map(move |(y, line)| {
+                line.chars().enumerate().filter_map(move |(x, ch)| {
+                    let cell = (origin.0 + x as i64, origin.1 + y as i64);
+                    match ch {
+                        '#' => Some((cell, None)),
+                        'H' => Some((cell, Some(Electron::Head))),
+                        't' => Some((cell, Some(Electron::Tail))),
+                        _ => None,
+                    }
+                })
+            })
+    }
+}
+
+/// Seeds the wire with its top-left corner at the origin.
+impl IsSeed for Circuit {
+    fn cells(&self, origin: Cell) -> Vec<Cell> {
+        self.layout(origin).map(|(cell, _)| cell).collect()
+    }
+
+    fn electrons(&self, origin: Cell) -> Vec<(Cell, Electron)> {
+        self.layout(origin)
+            .filter_map(|(cell, electron)| Some((cell, electron?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    fn wireworld() -> Grid {
+        let mut grid = Grid::new(40, 20);
+        grid.rule = "wireworld".parse().unwrap();
+        grid
+    }
+
+    /// Sparks the wire at each input, then ticks until `output` gets an
+    /// electron, returning how many ticks that took.
+    fn signal(grid: &mut Grid, inputs: &[Cell], output: Cell) -> Option<usize> {
+        for input in inputs {
+            grid.seed(*input, *input);
+        }
+        (1..=40).find(|_| {
+            grid.tick();
+            grid.cells.contains(&output)
+        })
+    }
+
+    #[test]
+    fn test_electron_follows_wire() {
+        let mut grid = wireworld();
+        grid.seed(Circuit::Diode, (0, 0));
+        assert_eq!(grid.wires.len(), 16);
+        assert!(grid.cells.is_empty());
+
+        assert_eq!(signal(&mut grid, &[(0, 1)], (12, 1)), Some(12));
+        // wire never changes
+        assert_eq!(grid.wires.len(), 16);
+    }
+
+    #[test]
+    fn test_diode_blocks_the_other_way() {
+        let mut grid = wireworld();
+        grid.seed(Circuit::Diode, (0, 0));
+
+        assert_eq!(signal(&mut grid, &[(12, 1)], (0, 1)), None);
+    }
+
+    #[test]
+    fn test_clock() {
+        let mut grid = wireworld();
+        grid.seed(Circuit::Clock, (0, 0));
+        assert_eq!(grid.cells, [(2, 0)].into());
+        assert_eq!(grid.dying, [((1, 0), 2)].into());
+
+        let pulses: Vec<usize> = (1..=30)
+            .filter(|_| {
+                grid.tick();
+                grid.cells.contains(&(14, 2))
+            })
+            .collect();
+        assert_eq!(pulses, vec![12, 20, 28]);
+    }
+
+    #[test]
+    fn test_gates() {
+        let truth_table = |circuit: Circuit, output: Cell, a: Cell, b: Cell| {
+            [vec![], vec![a], vec![b], vec![a, b]].map(|inputs| {
+                let mut grid = wireworld();
+                grid.seed(circuit, (0, 0));
+                signal(&mut grid, &inputs, output).is_some()
+            })
+        };
+
+        assert_eq!(
+            truth_table(Circuit::OrGate, (15, 3), (0, 1), (0, 5)),
+            [false, true, true, true]
+        );
+        assert_eq!(
+            truth_table(Circuit::XorGate, (16, 4), (0, 1), (0, 7)),
+            [false, true, true, false]
+        );
+    }
+}